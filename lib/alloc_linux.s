@@ -0,0 +1,88 @@
+.text
+alloc:
+  push rbx
+  push rcx
+  push rdx
+  push rsi
+  push rdi
+  mov rbx, rdi
+  add rbx, 23
+  and rbx, -16
+  lea rsi, depth.free_list
+.alloc.find:
+  mov rax, [rsi]
+  cmp rax, 0
+  jz .alloc.bump
+  mov rcx, -8[rax]
+  mov rdx, rcx
+  sub rdx, rbx
+  sar rdx, 63
+  cmp rdx, 0
+  jz .alloc.reuse
+  mov rsi, rax
+  jmp .alloc.find
+.alloc.reuse:
+  mov rcx, [rax]
+  mov [rsi], rcx
+  jmp .alloc.end
+.alloc.bump:
+  mov rax, QWORD PTR depth.heap_next
+  mov rdx, QWORD PTR depth.heap_end
+  sub rdx, rax
+  sub rdx, rbx
+  sar rdx, 63
+  cmp rdx, 0
+  jz .alloc.carve
+  mov rsi, rbx
+  add rsi, 65535
+  and rsi, -65536
+  mov rax, 9
+  mov rdi, 0
+  mov rdx, 3
+  mov r10, 34
+  mov r8, -1
+  mov r9, 0
+  syscall
+  mov rdx, rax
+  sar rdx, 63
+  cmp rdx, 0
+  jz .alloc.grow
+  mov rax, 0
+  jmp .alloc.end
+.alloc.grow:
+  mov QWORD PTR depth.heap_next, rax
+  add rax, rsi
+  mov QWORD PTR depth.heap_end, rax
+  mov rax, QWORD PTR depth.heap_next
+.alloc.carve:
+  mov [rax], rbx
+  add rax, 8
+  mov rcx, rax
+  add rcx, rbx
+  sub rcx, 8
+  mov QWORD PTR depth.heap_next, rcx
+.alloc.end:
+  pop rdi
+  pop rsi
+  pop rdx
+  pop rcx
+  pop rbx
+  ret
+free:
+  push rcx
+  mov rax, 0
+  cmp rdi, 0
+  jz .free.end
+  mov rcx, QWORD PTR depth.free_list
+  mov [rdi], rcx
+  mov QWORD PTR depth.free_list, rdi
+.free.end:
+  pop rcx
+  ret
+.bss
+depth.free_list:
+  .zero 8
+depth.heap_next:
+  .zero 8
+depth.heap_end:
+  .zero 8
@@ -0,0 +1,10 @@
+.text
+_start:
+  mov rax, rsp
+  mov rdi, [rax]
+  mov rsi, rax
+  add rsi, 8
+  call main
+  mov rdi, rax
+  mov rax, 60
+  syscall
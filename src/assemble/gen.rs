@@ -1,18 +1,20 @@
 use crate::assemble::parse::{Info, Inst, Operand};
-use crate::object::elf::elf64::Rela;
+use crate::object::elf::elf64::{self, Rela};
 
 use std::collections::BTreeMap;
 use std::ops::Deref;
 
 type LabelName = String;
 type CodeIndex = usize;
-type Offset = usize;
+/* each site to relocate, with the symbol it refers */
+type Relas = Vec<(String, Rela)>;
 struct Generator {
     insts: Vec<Inst>,
     info_map: BTreeMap<usize, Info>,
-    jump_map: BTreeMap<LabelName, (CodeIndex, Offset)>,
+    label_map: BTreeMap<LabelName, CodeIndex>,
+    jump_sites: Vec<(LabelName, CodeIndex)>,
     codes: Vec<u8>,
-    rels_map: BTreeMap<String, Rela>,
+    relas: Relas,
     symbol_map: BTreeMap<String, Vec<u8>>,
     offset: u64,
 }
@@ -25,44 +27,24 @@ impl Generator {
                     self.gen_inst(&num);
                 }
                 Inst::LABEL(_, name) => {
-                    if let Some(tup) = self.jump_map.get_mut(name) {
-                        tup.1 = self.codes.len() - tup.1 - 1;
-                        continue;
-                    }
-                    self.jump_map
-                        .insert(name.to_string(), (0, self.codes.len()));
+                    self.label_map.insert(name.to_string(), self.codes.len());
                 }
             }
         }
-        for inst in insts.iter() {
-            if let Inst::UNARG(num) = inst {
-                let info: &Info = self.info_map.get(&num).unwrap();
-                match info.inst_name.as_str() {
-                    "jmp" => {
-                        if let Some(Operand::SYMBOL(name)) = &info.lop {
-                            if let Some(tup) = self.jump_map.get(name) {
-                                for (idx, b) in (tup.1 as u32).to_le_bytes().iter().enumerate() {
-                                    self.codes[idx + tup.0] = *b;
-                                }
-                            }
-                        }
-                    }
-                    "jz" => {
-                        if let Some(Operand::SYMBOL(name)) = &info.lop {
-                            if let Some(tup) = self.jump_map.get(name) {
-                                for (idx, b) in (tup.1 as u32).to_le_bytes().iter().enumerate() {
-                                    self.codes[idx + tup.0] = *b;
-                                }
-                            }
-                        }
-                    }
-                    _ => (),
+        /* resolve every jump after all labels in the symbol are placed. */
+        for (name, code_index) in self.jump_sites.iter() {
+            if let Some(label_index) = self.label_map.get(name) {
+                let rel: i64 = *label_index as i64 - (*code_index + 4) as i64;
+                for (idx, b) in (rel as u32).to_le_bytes().iter().enumerate() {
+                    self.codes[idx + code_index] = *b;
                 }
             }
         }
+        self.jump_sites.clear();
+        self.label_map.clear();
     }
     fn gen_inst(&mut self, num: &usize) {
-        let info: &Info = self.info_map.get(&num).unwrap();
+        let info: Info = self.info_map.get(&num).unwrap().clone();
         match info.inst_name.as_str() {
             "add" => {
                 if let Some(Operand::REG(_reg)) = &info.lop {
@@ -70,7 +52,7 @@ impl Generator {
                     if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
                         self.codes.push(0x03); // REX.w add r64, r/m64 /r
                         self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                        self.codes.push(*offset as u8);
+                        self.gen_displacement(*offset);
                     } else if let Some(Operand::IMM(value)) = info.rop {
                         self.codes.push(0x81);
                         self.codes.push(self.set_modmi(&info.lop, &info.rop, None));
@@ -81,14 +63,39 @@ impl Generator {
                     }
                 }
             }
+            "and" => {
+                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                if let Some(Operand::REG(_reg)) = &info.lop {
+                    if let Some(Operand::REG(_reg)) = &info.rop {
+                        self.codes.push(0x21); // REX.w and r/m64, r64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                    } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
+                        self.codes.push(0x23); // REX.w and r64, r/m64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                        self.gen_displacement(*offset);
+                    } else if let Some(Operand::IMM(value)) = info.rop {
+                        self.codes.push(0x81); // REX.w and r/m64, imm32 /4 id
+                        self.codes
+                            .push(self.set_modmi(&info.lop, &info.rop, Some(0x20)));
+                        self.gen_immediate(value);
+                    }
+                }
+            }
+            "call" if matches!(info.lop, Some(Operand::REG(_))) => {
+                if let Some(Operand::REG(name)) = &info.lop {
+                    if Operand::is_expanded(name) {
+                        self.codes.push(0x41); // REX.b
+                    }
+                    self.codes.push(0xff); // call r/m64 /2
+                    self.codes.push(0xd0 | Operand::number(name));
+                }
+            }
             "call" => {
                 self.codes.push(0x48);
                 self.codes.push(0xc7);
                 self.codes.push(0xc0);
                 if let Some(Operand::SYMBOL(name)) = &info.lop {
-                    if let Some(rela) = self.rels_map.get_mut(name) {
-                        rela.r_offset = self.offset + self.codes.len() as u64;
-                    }
+                    self.add_rela(name, elf64::R_X86_64_64);
                     if let None = self.symbol_map.get(name) {
                         self.symbol_map.insert(name.to_string(), Vec::new());
                     }
@@ -108,9 +115,9 @@ impl Generator {
                     } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
                         self.codes.push(0x3b); // REX.w cmp r64, r/m64 /r
                         self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                        self.codes.push(*offset as u8);
+                        self.gen_displacement(*offset);
                     } else {
-                        self.codes.push(0x3b);
+                        self.codes.push(0x39); // REX.w cmp r/m64, r64 /r
                         self.codes.push(self.set_modrm(&info.lop, &info.rop));
                     }
                 } else if let Some(Operand::ADDRESS(_content, offset)) = &info.lop {
@@ -118,7 +125,7 @@ impl Generator {
                         self.codes.push(0x81); // cmp r/m64, imm32 /7 id
                         self.codes
                             .push(self.set_modmi(&info.lop, &info.rop, Some(0x38)));
-                        self.codes.push(*offset as u8);
+                        self.gen_displacement(*offset);
                         self.gen_immediate(value);
                     }
                 }
@@ -127,6 +134,39 @@ impl Generator {
                 self.codes.push(0x48);
                 self.codes.push(0x99);
             }
+            "div" if matches!(info.lop, Some(Operand::ADDRESS(_, _))) => {
+                if let Some(Operand::ADDRESS(content, offset)) = &info.lop {
+                    self.codes.push(0x48); // REX.w
+                    self.codes.push(0xf7); // div r/m64 /6
+                    let mut modrm: u8 = 0x70; // mod = 01, reg = /6
+                    if let Operand::REG(name) = content.deref() {
+                        modrm |= Operand::number(name);
+                    }
+                    self.codes.push(modrm);
+                    self.gen_displacement(*offset);
+                }
+            }
+            "div" => {
+                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                self.codes.push(0xf7);
+                let mut modrm: u8 = 0xf0; // mod = 11, reg = /6
+                if let Some(Operand::REG(name)) = &info.lop {
+                    modrm |= Operand::number(name); // r/m field
+                }
+                self.codes.push(modrm);
+            }
+            "idiv" if matches!(info.lop, Some(Operand::ADDRESS(_, _))) => {
+                if let Some(Operand::ADDRESS(content, offset)) = &info.lop {
+                    self.codes.push(0x48); // REX.w
+                    self.codes.push(0xf7); // idiv r/m64 /7
+                    let mut modrm: u8 = 0x78; // mod = 01, reg = /7
+                    if let Operand::REG(name) = content.deref() {
+                        modrm |= Operand::number(name);
+                    }
+                    self.codes.push(modrm);
+                    self.gen_displacement(*offset);
+                }
+            }
             "idiv" => {
                 self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
                 self.codes.push(0xf7);
@@ -143,26 +183,25 @@ impl Generator {
                         self.codes.push(0x0f); // REX.w imul r64, r/m64 /r
                         self.codes.push(0xaf);
                         self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                        self.codes.push(*offset as u8);
+                        self.gen_displacement(*offset);
                     } else if let Some(Operand::IMM(value)) = info.rop {
                         self.codes.push(self.set_rexprefix(&info.lop, &info.lop));
                         self.codes.push(0x69);
                         self.codes.push(self.set_modrm(&info.lop, &info.lop)); // special
                         self.gen_immediate(value);
-                    } else {
+                    } else if let Some(Operand::REG(_reg)) = &info.rop {
+                        /* reg = the destination, r/m = the source */
+                        self.codes.push(self.set_rexprefix(&info.rop, &info.lop));
+                        self.codes.push(0x0f); // REX.w imul r64, r/m64 /r
+                        self.codes.push(0xaf);
+                        self.codes.push(self.set_modrm(&info.rop, &info.lop));
                     }
                 }
             }
             "jmp" => {
                 self.codes.push(0xe9);
                 if let Some(Operand::SYMBOL(name)) = &info.lop {
-                    if let Some(tup) = self.jump_map.get_mut(name) {
-                        tup.0 = self.codes.len();
-                        tup.1 = !(self.codes.len() + 4 - tup.1) + 1;
-                    } else {
-                        self.jump_map
-                            .insert(name.to_string(), (self.codes.len(), self.codes.len() + 3));
-                    }
+                    self.jump_sites.push((name.to_string(), self.codes.len()));
                 }
                 self.gen_immediate(0x00);
             }
@@ -170,24 +209,24 @@ impl Generator {
                 self.codes.push(0x0f);
                 self.codes.push(0x84);
                 if let Some(Operand::SYMBOL(name)) = &info.lop {
-                    if let Some(tup) = self.jump_map.get_mut(name) {
-                        tup.0 = self.codes.len();
-                        tup.1 = !(self.codes.len() + 4 - tup.1) + 1;
-                    } else {
-                        self.jump_map
-                            .insert(name.to_string(), (self.codes.len(), self.codes.len() + 3));
-                    }
+                    self.jump_sites.push((name.to_string(), self.codes.len()));
                 }
                 self.gen_immediate(0x00);
             }
             "lea" => {
+                if let (Some(Operand::REG(reg)), Some(Operand::SYMBOL(name))) =
+                    (&info.lop, &info.rop)
+                {
+                    self.gen_absolute(0x8d, reg, name); // lea r64, m
+                    return;
+                }
                 self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
                 match &info.lop {
                     Some(Operand::REG(_reg)) => {
                         if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
                             self.codes.push(0x8d); // REX.w lea r64, r/m64 /r
                             self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                            self.codes.push(*offset as u8);
+                            self.gen_displacement(*offset);
                         }
                     }
                     _ => (),
@@ -195,17 +234,36 @@ impl Generator {
             }
             "mov" => {
                 match &info.lop {
-                    Some(Operand::REG(_reg)) => {
+                    Some(Operand::REG(reg)) => {
                         if let Some(Operand::IMM(value)) = info.rop {
                             self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
-                            self.codes.push(0xc7); // mov reg, immediate
-                            self.codes.push(self.set_modmi(&info.lop, &info.rop, None));
-                            self.gen_immediate(value);
+                            if Operand::size(reg) != 8
+                                || (i32::MIN as i128..=i32::MAX as i128).contains(&value)
+                            {
+                                self.codes.push(0xc7); // mov reg, immediate
+                                self.codes.push(self.set_modmi(&info.lop, &info.rop, None));
+                                self.gen_immediate(value);
+                            } else {
+                                self.codes.push(0xb8 + Operand::number(reg)); // REX.w mov r64, imm64
+                                for b in (value as u64).to_le_bytes().iter() {
+                                    self.codes.push(*b);
+                                }
+                            }
                         } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
-                            self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                            if Operand::size(reg) == 4 {
+                                if let Some(rexprefix) =
+                                    self.set_sized_rexprefix(&info.lop, &info.rop)
+                                {
+                                    self.codes.push(rexprefix);
+                                }
+                            } else {
+                                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                            }
                             self.codes.push(0x8b); // mov r64, r/m64
                             self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                            self.codes.push(*offset as u8);
+                            self.gen_displacement(*offset);
+                        } else if let Some(Operand::SYMBOL(name)) = &info.rop {
+                            self.gen_absolute(0x8b, reg, name); // mov r64, m64
                         } else if let Some(Operand::ELEMENT(base, idx, scale, offset)) = &info.rop {
                             self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
                             self.codes.push(0x8b); // mov r64, r/m64
@@ -220,22 +278,82 @@ impl Generator {
                             self.codes.push(modrm);
                         }
                     }
+                    Some(Operand::SYMBOL(name)) => {
+                        if let Some(Operand::REG(reg)) = &info.rop {
+                            self.gen_absolute(0x89, reg, name); // mov m64, r64
+                        }
+                    }
                     Some(Operand::ADDRESS(_content, offset)) => {
-                        if let Some(Operand::REG(_reg)) = &info.rop {
-                            self.codes.push(self.set_rexprefix(&info.rop, &info.lop)); // for MR
-                            self.codes.push(0x89); // mov r/m64, r64
+                        if let Some(Operand::REG(reg)) = &info.rop {
+                            let size: usize = Operand::size(reg);
+                            if size == 8 {
+                                self.codes.push(self.set_rexprefix(&info.rop, &info.lop));
+                            // for MR
+                            } else {
+                                if size == 2 {
+                                    self.codes.push(0x66); // operand-size prefix
+                                }
+                                if let Some(rexprefix) =
+                                    self.set_sized_rexprefix(&info.rop, &info.lop)
+                                {
+                                    self.codes.push(rexprefix);
+                                }
+                            }
+                            if size == 1 {
+                                self.codes.push(0x88); // mov r/m8, r8
+                            } else {
+                                self.codes.push(0x89); // mov r/m64, r64
+                            }
                             self.codes.push(self.set_modmr(&info.lop, &info.rop));
-                            self.codes.push(*offset as u8);
+                            self.gen_displacement(*offset);
                         } else if let Some(Operand::IMM(value)) = info.rop {
-                            self.codes.push(0xc7);
+                            match info.ptr_size {
+                                Some(1) => self.codes.push(0xc6), // mov r/m8, imm8
+                                Some(2) => {
+                                    self.codes.push(0x66);
+                                    self.codes.push(0xc7); // mov r/m16, imm16
+                                }
+                                Some(8) => {
+                                    self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                                    self.codes.push(0xc7); // REX.w mov r/m64, imm32
+                                }
+                                _ => self.codes.push(0xc7),
+                            }
                             self.codes.push(self.set_modmi(&info.lop, &info.rop, None));
-                            self.codes.push(*offset as u8);
-                            self.gen_immediate(value);
+                            self.gen_displacement(*offset);
+                            match info.ptr_size {
+                                Some(1) => self.codes.push(value as u8),
+                                Some(2) => {
+                                    for b in (value as u16).to_le_bytes().iter() {
+                                        self.codes.push(*b);
+                                    }
+                                }
+                                _ => self.gen_immediate(value),
+                            }
                         }
                     }
                     _ => (),
                 }
             }
+            "movsx" | "movsxd" | "movzx" if self.is_memory(&info.rop) => {
+                if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
+                    self.codes
+                        .push(self.set_rexprefix(&info.lop, &info.rop) | 0x08);
+                    match (info.inst_name.as_str(), info.ptr_size) {
+                        ("movsxd", _) => self.codes.push(0x63), // REX.w movsxd r64, r/m32
+                        (inst_name, size) => {
+                            self.codes.push(0x0f);
+                            let mut opcode: u8 = if inst_name == "movsx" { 0xbe } else { 0xb6 };
+                            if let Some(2) = size {
+                                opcode |= 0x01; // r/m16
+                            }
+                            self.codes.push(opcode);
+                        }
+                    }
+                    self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                    self.gen_displacement(*offset);
+                }
+            }
             "movzx" => {
                 if let Some(Operand::REG(reg)) = &info.rop {
                     if reg == "al" {
@@ -258,6 +376,33 @@ impl Generator {
                 }
                 self.codes.push(modrm);
             }
+            "not" => {
+                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                self.codes.push(0xf7);
+                let mut modrm: u8 = 0xd0; // mod = 11, reg = /2
+                if let Some(Operand::REG(name)) = &info.lop {
+                    modrm |= Operand::number(name); // r/m field
+                }
+                self.codes.push(modrm);
+            }
+            "or" => {
+                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                if let Some(Operand::REG(_reg)) = &info.lop {
+                    if let Some(Operand::REG(_reg)) = &info.rop {
+                        self.codes.push(0x09); // REX.w or r/m64, r64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                    } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
+                        self.codes.push(0x0b); // REX.w or r64, r/m64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                        self.gen_displacement(*offset);
+                    } else if let Some(Operand::IMM(value)) = info.rop {
+                        self.codes.push(0x81); // REX.w or r/m64, imm32 /1 id
+                        self.codes
+                            .push(self.set_modmi(&info.lop, &info.rop, Some(0x08)));
+                        self.gen_immediate(value);
+                    }
+                }
+            }
             "push" => {
                 if let Some(Operand::REG(name)) = &info.lop {
                     self.codes.push(0x50 | Operand::number(name));
@@ -294,6 +439,26 @@ impl Generator {
                 self.codes.push(0x9d);
                 self.codes.push(self.set_modrm(&info.lop, &info.rop));
             }
+            "setb" => {
+                self.codes.push(0x0f);
+                self.codes.push(0x92);
+                self.codes.push(self.set_modrm(&info.lop, &info.rop));
+            }
+            "setbe" => {
+                self.codes.push(0x0f);
+                self.codes.push(0x96);
+                self.codes.push(self.set_modrm(&info.lop, &info.rop));
+            }
+            "seta" => {
+                self.codes.push(0x0f);
+                self.codes.push(0x97);
+                self.codes.push(self.set_modrm(&info.lop, &info.rop));
+            }
+            "setae" => {
+                self.codes.push(0x0f);
+                self.codes.push(0x93);
+                self.codes.push(self.set_modrm(&info.lop, &info.rop));
+            }
             "sete" => {
                 self.codes.push(0x0f);
                 self.codes.push(0x94);
@@ -309,8 +474,9 @@ impl Generator {
                     if let Some(Operand::REG(r2)) = &info.rop {
                         if r2 == "cl" {
                             self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
-                            self.codes.push(0xd3);
-                            self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                            self.codes.push(0xd3); // REX.w sar r/m64, cl /7
+                            self.codes
+                                .push(self.set_modmi(&info.lop, &info.rop, Some(0x38)));
                         }
                     } else if let Some(Operand::IMM(value)) = &info.rop {
                         self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
@@ -326,8 +492,9 @@ impl Generator {
                     if let Some(Operand::REG(r2)) = &info.rop {
                         if r2 == "cl" {
                             self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
-                            self.codes.push(0xd3);
-                            self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                            self.codes.push(0xd3); // REX.w sal r/m64, cl /4
+                            self.codes
+                                .push(self.set_modmi(&info.lop, &info.rop, Some(0x20)));
                         }
                     } else if let Some(Operand::IMM(value)) = &info.rop {
                         self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
@@ -338,6 +505,24 @@ impl Generator {
                     }
                 }
             }
+            "shr" => {
+                if let Some(Operand::REG(_reg)) = &info.lop {
+                    if let Some(Operand::REG(r2)) = &info.rop {
+                        if r2 == "cl" {
+                            self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                            self.codes.push(0xd3); // REX.w shr r/m64, cl /5
+                            self.codes
+                                .push(self.set_modmi(&info.lop, &info.rop, Some(0x28)));
+                        }
+                    } else if let Some(Operand::IMM(value)) = &info.rop {
+                        self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                        self.codes.push(0xc1); // REX.w shr r/m64, imm8 /5 ib
+                        self.codes
+                            .push(self.set_modmi(&info.lop, &info.rop, Some(0x28)));
+                        self.codes.push(*value as u8);
+                    }
+                }
+            }
             "sub" => {
                 self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
                 if let Some(Operand::REG(_reg)) = &info.lop {
@@ -347,7 +532,7 @@ impl Generator {
                     } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
                         self.codes.push(0x2b);
                         self.codes.push(self.set_modrm(&info.lop, &info.rop));
-                        self.codes.push(*offset as u8);
+                        self.gen_displacement(*offset);
                     } else if let Some(Operand::IMM(value)) = info.rop {
                         self.codes.push(0x81);
                         self.codes
@@ -360,9 +545,61 @@ impl Generator {
                 self.codes.push(0x0f);
                 self.codes.push(0x05);
             }
+            "xor" => {
+                self.codes.push(self.set_rexprefix(&info.lop, &info.rop));
+                if let Some(Operand::REG(_reg)) = &info.lop {
+                    if let Some(Operand::REG(_reg)) = &info.rop {
+                        self.codes.push(0x31); // REX.w xor r/m64, r64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                    } else if let Some(Operand::ADDRESS(_content, offset)) = &info.rop {
+                        self.codes.push(0x33); // REX.w xor r64, r/m64 /r
+                        self.codes.push(self.set_modrm(&info.lop, &info.rop));
+                        self.gen_displacement(*offset);
+                    } else if let Some(Operand::IMM(value)) = info.rop {
+                        self.codes.push(0x81); // REX.w xor r/m64, imm32 /6 id
+                        self.codes
+                            .push(self.set_modmi(&info.lop, &info.rop, Some(0x30)));
+                        self.gen_immediate(value);
+                    }
+                }
+            }
             _ => (),
         }
     }
+    /* the symbol is addressed by an absolute disp32, which the linker fills. */
+    fn gen_absolute(&mut self, opcode: u8, reg: &str, name: &str) {
+        let mut rexprefix: u8 = 0x48;
+        if Operand::is_expanded(reg) {
+            rexprefix |= 0x04; // REX.r
+        }
+        self.codes.push(rexprefix);
+        self.codes.push(opcode);
+        self.codes.push(0x04 | Operand::number(reg) << 3); // mod = 00, r/m = SIB
+        self.codes.push(0x25); // no base, no index
+        self.add_rela(name, elf64::R_X86_64_32S);
+        self.gen_immediate(0x00);
+    }
+    fn add_rela(&mut self, name: &str, rela_type: u64) {
+        self.relas.push((
+            name.to_string(),
+            Rela {
+                r_offset: self.offset + self.codes.len() as u64,
+                r_info: rela_type,
+                r_addend: 0,
+            },
+        ));
+    }
+    /* disp8 if it fits, otherwise the ModR/M just before turns to take disp32. */
+    fn gen_displacement(&mut self, offset: i128) {
+        if (-128..128).contains(&offset) {
+            self.codes.push(offset as u8);
+            return;
+        }
+        if let Some(modrm) = self.codes.last_mut() {
+            *modrm = (*modrm & 0x3f) | 0x80; // mod = 10
+        }
+        self.gen_immediate(offset);
+    }
     fn gen_immediate(&mut self, value: i128) {
         for b in (value as u32).to_le_bytes().to_vec().iter() {
             self.codes.push(*b);
@@ -418,6 +655,34 @@ impl Generator {
         }
         rexprefix
     }
+    fn is_memory(&self, op: &Option<Operand>) -> bool {
+        if let Some(Operand::ADDRESS(_, _)) = op {
+            return true;
+        }
+        false
+    }
+    fn set_sized_rexprefix(&self, reg: &Option<Operand>, rm: &Option<Operand>) -> Option<u8> {
+        // REX is needed only for the extended registers and sil, dil ...
+        let mut rexprefix: u8 = 0x40;
+        let mut needed: bool = false;
+        if let Some(Operand::REG(name)) = reg {
+            if Operand::is_expanded(name) {
+                rexprefix |= 0x04;
+            }
+            needed = Operand::size(name) == 1;
+        }
+        if let Some(Operand::ADDRESS(content, _offset)) = rm {
+            if let Operand::REG(base) = content.deref() {
+                if Operand::is_expanded(base) {
+                    rexprefix |= 0x01;
+                }
+            }
+        }
+        if needed || rexprefix != 0x40 {
+            return Some(rexprefix);
+        }
+        None
+    }
     fn set_sib_byte(&self, base: &Operand, idx: &Operand, _scale: i128) -> u8 {
         let mut sib: u8 = 0xc0;
         if let Operand::REG(name) = base {
@@ -501,15 +766,15 @@ impl Generator {
 pub fn generate(
     inst_map: BTreeMap<String, Vec<Inst>>,
     info_map: BTreeMap<usize, Info>,
-    rels_map: BTreeMap<String, Rela>,
-) -> (BTreeMap<String, Vec<u8>>, BTreeMap<String, Rela>) {
+) -> (BTreeMap<String, Vec<u8>>, Relas) {
     let mut generator: Generator = Generator {
         insts: Vec::new(),
         info_map: info_map,
         codes: Vec::new(),
-        rels_map: rels_map,
+        relas: Vec::new(),
         symbol_map: BTreeMap::new(),
-        jump_map: BTreeMap::new(),
+        label_map: BTreeMap::new(),
+        jump_sites: Vec::new(),
         offset: 0,
     };
     for (symbol, insts) in inst_map.iter() {
@@ -525,5 +790,5 @@ pub fn generate(
             .insert(symbol.to_string(), generator.codes.to_vec());
         generator.codes = Vec::new();
     }
-    (generator.symbol_map, generator.rels_map)
+    (generator.symbol_map, generator.relas)
 }
@@ -14,16 +14,22 @@ pub enum Token {
     RBRACKET,
     MOV,
     MOVZX,
+    MOVSX,
+    MOVSXD,
     ADD,
+    AND,
     CALL,
     CMP,
     CQO,
+    DIV,
     IDIV,
     IMUL,
     JZ,
     JMP,
     LEA,
     NEG,
+    NOT,
+    OR,
     PUSH,
     POP,
     RET,
@@ -33,10 +39,21 @@ pub enum Token {
     SETLE,
     SETG,
     SETGE,
+    SETB,
+    SETBE,
+    SETA,
+    SETAE,
     SETE,
     SETNE,
+    SHR,
     SUB,
     SYSCALL,
+    XOR,
+    BYTE,
+    WORD,
+    DWORD,
+    QWORD,
+    PTR,
     BLANK,
     LF,
     SYMBOL(String),
@@ -52,9 +69,11 @@ impl Token {
     pub fn string(&self) -> String {
         match self {
             Token::ADD => "add".to_string(),
+            Token::AND => "and".to_string(),
             Token::CALL => "call".to_string(),
             Token::CMP => "cmp".to_string(),
             Token::CQO => "cqo".to_string(),
+            Token::DIV => "div".to_string(),
             Token::IDIV => "idiv".to_string(),
             Token::IMUL => "imul".to_string(),
             Token::JMP => "jmp".to_string(),
@@ -62,7 +81,11 @@ impl Token {
             Token::LEA => "lea".to_string(),
             Token::MOV => "mov".to_string(),
             Token::MOVZX => "movzx".to_string(),
+            Token::MOVSX => "movsx".to_string(),
+            Token::MOVSXD => "movsxd".to_string(),
             Token::NEG => "neg".to_string(),
+            Token::NOT => "not".to_string(),
+            Token::OR => "or".to_string(),
             Token::PUSH => "push".to_string(),
             Token::POP => "pop".to_string(),
             Token::RET => "ret".to_string(),
@@ -70,12 +93,23 @@ impl Token {
             Token::SETLE => "setle".to_string(),
             Token::SETG => "setg".to_string(),
             Token::SETGE => "setge".to_string(),
+            Token::SETB => "setb".to_string(),
+            Token::SETBE => "setbe".to_string(),
+            Token::SETA => "seta".to_string(),
+            Token::SETAE => "setae".to_string(),
             Token::SETE => "sete".to_string(),
             Token::SETNE => "setne".to_string(),
             Token::SAR => "sar".to_string(),
             Token::SAL => "sal".to_string(),
+            Token::SHR => "shr".to_string(),
             Token::SUB => "sub".to_string(),
             Token::SYSCALL => "syscall".to_string(),
+            Token::XOR => "xor".to_string(),
+            Token::BYTE => "BYTE".to_string(),
+            Token::WORD => "WORD".to_string(),
+            Token::DWORD => "DWORD".to_string(),
+            Token::QWORD => "QWORD".to_string(),
+            Token::PTR => "PTR".to_string(),
             Token::SYMBOL(name) => name.to_string(),
             Token::INTEGER(num) => format!("INTEGER<{}>", num),
             Token::COLON => "COLON".to_string(),
@@ -167,14 +201,17 @@ fn count_len(input: &String, f: fn(ch: &char) -> bool) -> usize {
 }
 
 fn build_keywords() -> HashMap<&'static str, (Token, usize)> {
-    let mut keywords: HashMap<&'static str, (Token, usize)> = HashMap::with_capacity(25);
+    let mut keywords: HashMap<&'static str, (Token, usize)> = HashMap::with_capacity(42);
     keywords.insert("movzx", (Token::MOVZX, 5));
+    keywords.insert("movsx", (Token::MOVSX, 5));
+    keywords.insert("movsxd", (Token::MOVSXD, 6));
     keywords.insert("ret", (Token::RET, 3));
     keywords.insert("push", (Token::PUSH, 4));
     keywords.insert("pop", (Token::POP, 3));
     keywords.insert("cqo", (Token::CQO, 3));
     keywords.insert("add", (Token::ADD, 3));
     keywords.insert("sub", (Token::SUB, 3));
+    keywords.insert("div", (Token::DIV, 3));
     keywords.insert("idiv", (Token::IDIV, 4));
     keywords.insert("imul", (Token::IMUL, 4));
     keywords.insert("cmp", (Token::CMP, 3));
@@ -184,6 +221,10 @@ fn build_keywords() -> HashMap<&'static str, (Token, usize)> {
     keywords.insert("setl", (Token::SETL, 4));
     keywords.insert("setge", (Token::SETGE, 5));
     keywords.insert("setg", (Token::SETG, 4));
+    keywords.insert("setbe", (Token::SETBE, 5));
+    keywords.insert("setb", (Token::SETB, 4));
+    keywords.insert("setae", (Token::SETAE, 5));
+    keywords.insert("seta", (Token::SETA, 4));
     keywords.insert("sete", (Token::SETE, 4));
     keywords.insert("setne", (Token::SETNE, 5));
     keywords.insert("lea", (Token::LEA, 3));
@@ -192,6 +233,16 @@ fn build_keywords() -> HashMap<&'static str, (Token, usize)> {
     keywords.insert("jmp", (Token::JMP, 3));
     keywords.insert("sal", (Token::SAL, 3));
     keywords.insert("sar", (Token::SAR, 3));
+    keywords.insert("shr", (Token::SHR, 3));
+    keywords.insert("and", (Token::AND, 3));
+    keywords.insert("or", (Token::OR, 2));
+    keywords.insert("xor", (Token::XOR, 3));
+    keywords.insert("not", (Token::NOT, 3));
     keywords.insert("jz", (Token::JZ, 2));
+    keywords.insert("BYTE", (Token::BYTE, 4));
+    keywords.insert("WORD", (Token::WORD, 4));
+    keywords.insert("DWORD", (Token::DWORD, 5));
+    keywords.insert("QWORD", (Token::QWORD, 5));
+    keywords.insert("PTR", (Token::PTR, 3));
     keywords
 }
@@ -10,6 +10,11 @@ use crate::compile;
 use crate::object;
 use object::elf::elf64::ELF;
 
+/* the section numbers the objects in .data, .bss and .rodata are defined in. */
+static DATA_SECTION: u16 = 5;
+static BSS_SECTION: u16 = 6;
+static RODATA_SECTION: u16 = 7;
+
 pub fn assemble(
     assembler_code: String,
     matches: &clap::ArgMatches,
@@ -21,30 +26,30 @@ pub fn assemble(
     let tokens: Vec<lex::Token> = lex::lexing(assembler_code);
 
     /* parse */
-    let (instructions, info_map, relas) = parse::parsing(tokens);
+    let (instructions, info_map, data_map, bss_map, rodata_map) = parse::parsing(tokens);
     if matches.is_present("dump-inst") {
         dump_inst(&instructions, &info_map);
     }
 
-    let (code_map, mut relas) = gen::generate(instructions, info_map, relas);
-
-    let shstrtab = build_shstrtab();
+    let (code_map, relas) = gen::generate(instructions, info_map);
 
-    /* build symbol-names from map */
+    /* build symbol-names from map, the objects in .data, .bss and .rodata follow the code. */
     let symbol_names = code_map
-        .iter()
-        .map(|(name, _)| name.as_str())
+        .keys()
+        .chain(data_map.keys())
+        .chain(bss_map.keys())
+        .chain(rodata_map.keys())
+        .map(|name| name.as_str())
         .collect::<Vec<&str>>();
 
     /* initialize with null symbol. */
     let mut symbols: Vec<elf64::Symbol> = vec![elf64::init_nullsym()];
 
-    let total_len: u64 = sum_all_code_length(&code_map);
     let mut total_code: Vec<u8> = Vec::with_capacity(2048);
 
     /* initialize string-index with null byte. */
     let mut name: u32 = 1;
-    for (idx, (symbol_name, codes)) in code_map.iter().enumerate() {
+    for (symbol_name, codes) in code_map.iter() {
         if codes.len() != 0 {
             symbols.push(elf64::init_sym(
                 name,
@@ -63,39 +68,141 @@ pub fn assemble(
         for b in codes.iter() {
             total_code.push(*b);
         }
-        if let Some(rela) = relas.get_mut(symbol_name) {
-            rela.r_info = (((idx + 1) << 32) + 1) as u64;
-        }
     }
 
+    let mut data: Vec<u8> = Vec::new();
+    for (symbol_name, bytes) in data_map.iter() {
+        symbols.push(elf64::init_objsym(
+            name,
+            elf64::STB_GLOBAL,
+            DATA_SECTION,
+            bytes.len() as u64,
+            data.len() as u64,
+        ));
+        name += symbol_name.len() as u32 + 1;
+        data.extend_from_slice(bytes);
+    }
+
+    let mut bss_size: u64 = 0;
+    for (symbol_name, size) in bss_map.iter() {
+        symbols.push(elf64::init_objsym(
+            name,
+            elf64::STB_GLOBAL,
+            BSS_SECTION,
+            *size,
+            bss_size,
+        ));
+        name += symbol_name.len() as u32 + 1;
+        bss_size += size;
+    }
+
+    let mut rodata: Vec<u8> = Vec::new();
+    for (symbol_name, bytes) in rodata_map.iter() {
+        symbols.push(elf64::init_objsym(
+            name,
+            elf64::STB_GLOBAL,
+            RODATA_SECTION,
+            bytes.len() as u64,
+            rodata.len() as u64,
+        ));
+        name += symbol_name.len() as u32 + 1;
+        rodata.extend_from_slice(bytes);
+    }
+
+    /* each site refers to its symbol by the index in .symtab. */
+    let relas: Vec<elf64::Rela> = relas
+        .into_iter()
+        .map(|(symbol_name, mut rela)| {
+            if let Some(idx) = symbol_names.iter().position(|n| *n == symbol_name) {
+                rela.r_info += ((idx + 1) as u64) << 32;
+            }
+            rela
+        })
+        .collect();
+
+    let mut elf_file = build_object(Sections {
+        text: total_code,
+        symbols,
+        strtab: elf64::strtab(symbol_names),
+        relas,
+        data,
+        bss_size,
+        rodata,
+    });
+
+    /* .dbg.depth and .documents */
+    let debug_section_binary =
+        object::debug::build_debug_information(&elf_file, debug_funcs.clone());
+    let documents_binary = object::debug::build_documents(debug_funcs);
+    finish_object(&mut elf_file, debug_section_binary, documents_binary);
+    elf_file
+}
+
+/* the contents of the sections which make up a relocatable object. */
+pub struct Sections {
+    pub text: Vec<u8>,
+    pub symbols: Vec<object::elf::elf64::Symbol>,
+    pub strtab: Vec<u8>,
+    pub relas: Vec<object::elf::elf64::Rela>,
+    pub data: Vec<u8>,
+    pub bss_size: u64,
+    pub rodata: Vec<u8>,
+}
+
+/* add the sections up to .rodata, the debug sections refer the symbol table so they follow. */
+pub fn build_object(sections: Sections) -> ELF {
+    use object::elf::elf64;
     let mut elf_file = ELF::init();
 
     /* add all-sections. */
-    let strtab: Vec<u8> = elf64::strtab(symbol_names);
     elf_file.add_section(vec![], elf64::init_nullhdr(), "null");
 
     /* .text */
-    elf_file.add_section(total_code, elf64::init_texthdr(total_len), ".text");
+    let total_len = sections.text.len() as u64;
+    elf_file.add_section(sections.text, elf64::init_texthdr(total_len), ".text");
 
     /* .symtab */
-    let symbol_length = symbols.len();
-    let symtab: Vec<u8> = elf64::symbols_to_vec(symbols);
+    let symbol_length = sections.symbols.len();
+    let symtab: Vec<u8> = elf64::symbols_to_vec(sections.symbols);
     let symtab_size = elf64::Symbol::size() as u64 * symbol_length as u64;
     elf_file.add_section(symtab, elf64::init_symtabhdr(symtab_size), ".symtab");
 
     /* .strtab */
-    let strtab_length = strtab.len() as u64;
-    elf_file.add_section(strtab, elf64::init_strtabhdr(strtab_length), ".strtab");
+    let strtab_length = sections.strtab.len() as u64;
+    elf_file.add_section(
+        sections.strtab,
+        elf64::init_strtabhdr(strtab_length),
+        ".strtab",
+    );
 
     /* .rela.text */
-    let relas_length = relas.len() as u64;
-    let relas_tab = elf64::relas_to_vec(relas.values().collect::<Vec<&elf64::Rela>>());
+    let relas_length = sections.relas.len() as u64;
+    let relas_tab = elf64::relas_to_vec(sections.relas.iter().collect::<Vec<&elf64::Rela>>());
     let relas_size = elf64::Rela::size() as u64 * relas_length;
     elf_file.add_section(relas_tab, elf64::init_relahdr(relas_size), ".rela.text");
 
+    /* .data */
+    let data_length = sections.data.len() as u64;
+    elf_file.add_section(sections.data, elf64::init_datahdr(data_length), ".data");
+
+    /* .bss */
+    elf_file.add_section(vec![], elf64::init_bsshdr(sections.bss_size), ".bss");
+
+    /* .rodata */
+    let rodata_length = sections.rodata.len() as u64;
+    elf_file.add_section(
+        sections.rodata,
+        elf64::init_rodatahdr(rodata_length),
+        ".rodata",
+    );
+    elf_file
+}
+
+/* add .dbg.depth, .documents and .shstrtab to the object built by build_object(). */
+pub fn finish_object(elf_file: &mut ELF, debug_section_binary: Vec<u8>, documents_binary: Vec<u8>) {
+    use object::elf::elf64;
+
     /* .dbg.depth */
-    let debug_section_binary =
-        object::debug::build_debug_information(&elf_file, debug_funcs.clone());
     let debug_length = debug_section_binary.len();
     elf_file.add_section(
         debug_section_binary,
@@ -104,20 +211,20 @@ pub fn assemble(
     );
 
     /* .documents */
-    let documents_binary = object::debug::build_documents(debug_funcs);
     let documents_length = documents_binary.len();
     elf_file.add_section(
         documents_binary,
         elf64::init_documenthdr(documents_length as u64),
         ".documents",
     );
+
     /* .shstrtab */
+    let shstrtab = build_shstrtab();
     let shstrtab_length = shstrtab.len() as u64;
     let shstrtab_hdr = elf64::init_strtabhdr(shstrtab_length);
     elf_file.add_section(shstrtab, shstrtab_hdr, ".shstrtab");
 
     elf_file.condition();
-    elf_file
 }
 
 fn dump_inst(
@@ -158,17 +265,11 @@ fn build_shstrtab() -> Vec<u8> {
         ".symtab",
         ".strtab",
         ".rela.text",
+        ".data",
+        ".bss",
+        ".rodata",
         ".dbg.depth",
         ".documents",
         ".shstrtab",
     ])
 }
-
-fn sum_all_code_length(
-    code_map: &std::collections::BTreeMap<std::string::String, std::vec::Vec<u8>>,
-) -> u64 {
-    code_map
-        .iter()
-        .map(|(_, codes)| codes.len() as u64)
-        .sum::<u64>()
-}
@@ -1,11 +1,17 @@
 use crate::assemble::lex::Token;
 use crate::ce::types::Error;
-use crate::object::elf::elf64::Rela;
 
 use std::collections::BTreeMap;
 
 static mut CUR: usize = 0;
 static mut NEXT: usize = 1;
+static REGISTERS: [&str; 64] = [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rsp", "rbp", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15", "eax", "ebx", "ecx", "edx", "esi", "edi", "r8d", "r9d", "r10d", "r11d", "r12d",
+    "r13d", "r14d", "r15d", "ax", "bx", "cx", "dx", "si", "di", "r8w", "r9w", "r10w", "r11w",
+    "r12w", "r13w", "r14w", "r15w", "al", "bl", "cl", "dl", "sil", "dil", "r8b", "r9b", "r10b",
+    "r11b", "r12b", "r13b", "r14b", "r15b", "spl", "bpl", "sp", "bp", "esp", "ebp",
+];
 #[derive(Debug, Clone)]
 pub enum Inst {
     BINARG(usize),
@@ -13,6 +19,7 @@ pub enum Inst {
     NOARG(usize),
     LABEL(usize, String),
 }
+#[derive(Clone)]
 pub enum Operand {
     REG(String),
     SYMBOL(String),
@@ -35,26 +42,49 @@ impl Operand {
         }
     }
     pub fn number(name: &str) -> u8 {
-        match name {
+        match Self::base_name(name) {
             "al" | "ax" | "eax" | "rax" | "r8" => 0b000,
             "cl" | "cx" | "ecx" | "rcx" | "r9" => 0b001,
-            "rdx" | "r10" => 0b010,
-            "rbx" | "r11" => 0b011,
-            "rsp" | "r12" => 0b100,
-            "rbp" | "r13" => 0b101,
-            "rsi" | "r14" => 0b110,
-            "rdi" | "r15" => 0b111,
+            "dl" | "dx" | "edx" | "rdx" | "r10" => 0b010,
+            "bl" | "bx" | "ebx" | "rbx" | "r11" => 0b011,
+            "spl" | "sp" | "esp" | "rsp" | "r12" => 0b100,
+            "bpl" | "bp" | "ebp" | "rbp" | "r13" => 0b101,
+            "sil" | "si" | "esi" | "rsi" | "r14" => 0b110,
+            "dil" | "di" | "edi" | "rdi" | "r15" => 0b111,
             c => {
                 Error::ASSEMBLE.found(&format!("invalid Register<{}>", c));
                 0
             }
         }
     }
+    /* r8b, r8w and r8d are encoded as r8. */
+    fn base_name(name: &str) -> &str {
+        if name.starts_with('r') && name.len() > 2 && name.as_bytes()[1].is_ascii_digit() {
+            return name.trim_end_matches(&['b', 'w', 'd'][..]);
+        }
+        name
+    }
+    pub fn is_expanded(name: &str) -> bool {
+        name.starts_with('r') && name.len() > 1 && name.as_bytes()[1].is_ascii_digit()
+    }
+    pub fn size(name: &str) -> usize {
+        match name {
+            "al" | "bl" | "cl" | "dl" | "sil" | "dil" | "spl" | "bpl" => 1,
+            "ax" | "bx" | "cx" | "dx" | "si" | "di" | "sp" | "bp" => 2,
+            n if n.starts_with('e') => 4,
+            n if Self::is_expanded(n) && n.ends_with('b') => 1,
+            n if Self::is_expanded(n) && n.ends_with('w') => 2,
+            n if Self::is_expanded(n) && n.ends_with('d') => 4,
+            _ => 8,
+        }
+    }
 }
+#[derive(Clone)]
 pub struct Info {
     pub inst_name: String,
     pub lop: Option<Operand>,
     pub rop: Option<Operand>,
+    pub ptr_size: Option<usize>,
 }
 
 impl Info {
@@ -63,6 +93,25 @@ impl Info {
             inst_name: name,
             lop: None,
             rop: None,
+            ptr_size: None,
+        }
+    }
+}
+
+enum Section {
+    TEXT,
+    DATA,
+    BSS,
+    RODATA,
+}
+impl Section {
+    fn from_directive(name: &str) -> Option<Section> {
+        match name {
+            ".text" => Some(Section::TEXT),
+            ".data" => Some(Section::DATA),
+            ".bss" => Some(Section::BSS),
+            ".rodata" => Some(Section::RODATA),
+            _ => None,
         }
     }
 }
@@ -72,8 +121,11 @@ struct Parser {
     info_map: BTreeMap<usize, Info>,
     insts: Vec<Inst>,
     inst_map: BTreeMap<String, Vec<Inst>>,
+    data_map: DataMap,
+    bss_map: BssMap,
+    rodata_map: DataMap,
+    section: Section,
     entry: usize,
-    rels: BTreeMap<String, Rela>,
 }
 impl Parser {
     fn parse(&mut self) {
@@ -86,15 +138,80 @@ impl Parser {
                 break;
             };
             self.next_token();
+            /* the symbols below a directive belong to its section. */
+            if let Some(section) = Section::from_directive(&n) {
+                self.section = section;
+                continue;
+            }
+            /* '.section .rodata' */
+            if n == ".section" {
+                if let Token::SYMBOL(name) = self.cur_token() {
+                    match Section::from_directive(name) {
+                        Some(section) => self.section = section,
+                        None => Error::ASSEMBLE.found(&format!("unknown section '{}'", name)),
+                    }
+                }
+                self.next_token();
+                continue;
+            }
             if let Token::COLON = self.cur_token() {
             } else {
                 break;
             }
             self.next_token();
-            while let Some(()) = self.parse_inst() {}
-            self.inst_map.insert(n, self.insts.clone());
-            self.insts = Vec::new();
+            match self.section {
+                Section::TEXT => {
+                    while let Some(()) = self.parse_inst() {}
+                    self.inst_map.insert(n, self.insts.clone());
+                    self.insts = Vec::new();
+                }
+                Section::DATA => {
+                    let bytes: Vec<u8> = self.parse_data();
+                    self.data_map.insert(n, bytes);
+                }
+                Section::BSS => {
+                    let size: u64 = self.parse_data().len() as u64;
+                    self.bss_map.insert(n, size);
+                }
+                Section::RODATA => {
+                    let bytes: Vec<u8> = self.parse_data();
+                    self.rodata_map.insert(n, bytes);
+                }
+            }
+        }
+    }
+    /* '.quad value' places 8 bytes, '.zero n' places n zeroed bytes,
+     * '.byte b1, b2, ...' places the bytes. */
+    fn parse_data(&mut self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        loop {
+            let directive: String = match self.cur_token() {
+                Token::SYMBOL(name) if name == ".quad" || name == ".zero" || name == ".byte" => {
+                    name.to_string()
+                }
+                _ => break,
+            };
+            self.next_token();
+            loop {
+                let value: i128 = match self.get_operand() {
+                    Some(Operand::IMM(value)) => value,
+                    _ => {
+                        Error::ASSEMBLE.found(&format!("'{}' expects an integer", directive));
+                        0
+                    }
+                };
+                match directive.as_str() {
+                    ".quad" => bytes.extend_from_slice(&(value as i64).to_le_bytes()),
+                    ".byte" => bytes.push(value as u8),
+                    _ => bytes.resize(bytes.len() + value as usize, 0x00),
+                }
+                if directive != ".byte" || self.cur_token() != &Token::COMMA {
+                    break;
+                }
+                self.next_token();
+            }
         }
+        bytes
     }
     fn parse_inst(&mut self) -> Option<()> {
         let inst: Token = self.get_token();
@@ -109,15 +226,21 @@ impl Parser {
             }
             Token::PUSH
             | Token::POP
+            | Token::DIV
             | Token::IDIV
             | Token::SETL
             | Token::SETLE
             | Token::SETG
             | Token::SETGE
+            | Token::SETB
+            | Token::SETBE
+            | Token::SETA
+            | Token::SETAE
             | Token::SETE
             | Token::SETNE
             | Token::CALL
             | Token::NEG
+            | Token::NOT
             | Token::JMP
             | Token::JZ => {
                 self.next_token();
@@ -126,36 +249,41 @@ impl Parser {
                 self.insts.push(Inst::UNARG(entry));
                 let mut info: Info = Info::new(inst.string());
                 info.lop = self.get_operand();
-                if let Some(Operand::SYMBOL(name)) = &info.lop {
-                    if !name.starts_with(".") {
-                        self.rels.insert(name.to_string(), Rela::new());
-                    }
-                }
                 self.info_map.insert(entry, info);
                 Some(())
             }
             Token::MOV
             | Token::MOVZX
+            | Token::MOVSX
+            | Token::MOVSXD
             | Token::ADD
             | Token::SUB
             | Token::CMP
             | Token::LEA
             | Token::IMUL
             | Token::SAR
-            | Token::SAL => {
+            | Token::SAL
+            | Token::SHR
+            | Token::AND
+            | Token::OR
+            | Token::XOR => {
                 self.next_token();
                 let entry: usize = self.entry;
                 self.entry += 1;
                 self.insts.push(Inst::BINARG(entry));
                 let mut info: Info = Info::new(inst.string());
+                info.ptr_size = self.consume_ptr_size();
                 info.lop = self.get_operand();
                 self.next_token();
+                if let Some(size) = self.consume_ptr_size() {
+                    info.ptr_size = Some(size);
+                }
                 info.rop = self.get_operand();
                 self.info_map.insert(entry, info);
                 Some(())
             }
             Token::SYMBOL(name) => {
-                if name.starts_with(".") {
+                if name.starts_with(".") && Section::from_directive(&name).is_none() {
                     let entry: usize = self.entry;
                     self.insts.push(Inst::LABEL(entry, name.to_string()));
                     self.next_token();
@@ -171,17 +299,14 @@ impl Parser {
     fn get_operand(&self) -> Option<Operand> {
         let t: &Token = self.cur_token();
         match t {
-            Token::SYMBOL(name) => match name.as_str() {
-                "rax" | "rbx" | "rcx" | "rdx" | "rsi" | "rdi" | "rsp" | "rbp" | "r8" | "r9"
-                | "r10" | "r11" | "r12" | "r13" | "r14" | "r15" | "al" => {
-                    self.next_token();
+            Token::SYMBOL(name) => {
+                self.next_token();
+                if REGISTERS.contains(&name.as_str()) {
                     Some(Operand::REG(name.to_string()))
-                }
-                _ => {
-                    self.next_token();
+                } else {
                     Some(Operand::SYMBOL(name.to_string()))
                 }
-            },
+            }
             Token::INTEGER(value) => {
                 self.next_token();
                 Some(Operand::IMM(*value))
@@ -189,6 +314,12 @@ impl Parser {
             Token::MINUS => {
                 self.next_token();
                 let integer: Option<Operand> = self.get_operand();
+                /* a negative immediate like 'mov rax, -1' */
+                if self.cur_token() != &Token::LBRACKET {
+                    if let Some(Operand::IMM(value)) = integer {
+                        return Some(Operand::IMM(-value));
+                    }
+                }
                 let mut address: Option<Operand> = self.get_operand();
                 if let Some(Operand::ADDRESS(ref mut _content, ref mut offset)) = address {
                     if let Some(Operand::IMM(value)) = integer {
@@ -235,6 +366,21 @@ impl Parser {
             _ => None,
         }
     }
+    /* BYTE PTR, WORD PTR ... tell the size of the memory operand. */
+    fn consume_ptr_size(&self) -> Option<usize> {
+        let size: usize = match self.cur_token() {
+            Token::BYTE => 1,
+            Token::WORD => 2,
+            Token::DWORD => 4,
+            Token::QWORD => 8,
+            _ => return None,
+        };
+        self.next_token();
+        if let Token::PTR = self.cur_token() {
+            self.next_token();
+        }
+        Some(size)
+    }
     fn cur_token(&self) -> &Token {
         unsafe {
             if CUR == self.tokens.len() {
@@ -258,13 +404,11 @@ impl Parser {
         }
     }
 }
-pub fn parsing(
-    tokens: Vec<Token>,
-) -> (
-    BTreeMap<String, Vec<Inst>>,
-    BTreeMap<usize, Info>,
-    BTreeMap<String, Rela>,
-) {
+pub type InstMap = BTreeMap<String, Vec<Inst>>;
+/* the initial bytes of each object in .data or .rodata, and the size of each object in .bss */
+pub type DataMap = BTreeMap<String, Vec<u8>>;
+pub type BssMap = BTreeMap<String, u64>;
+pub fn parsing(tokens: Vec<Token>) -> (InstMap, BTreeMap<usize, Info>, DataMap, BssMap, DataMap) {
     unsafe {
         CUR = 0;
         NEXT = 1;
@@ -274,9 +418,18 @@ pub fn parsing(
         info_map: BTreeMap::new(),
         inst_map: BTreeMap::new(),
         insts: Vec::new(),
+        data_map: BTreeMap::new(),
+        bss_map: BTreeMap::new(),
+        rodata_map: BTreeMap::new(),
+        section: Section::TEXT,
         entry: 0,
-        rels: BTreeMap::new(),
     };
     parser.parse();
-    (parser.inst_map, parser.info_map, parser.rels)
+    (
+        parser.inst_map,
+        parser.info_map,
+        parser.data_map,
+        parser.bss_map,
+        parser.rodata_map,
+    )
 }
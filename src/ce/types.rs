@@ -1,4 +1,5 @@
 extern crate colored;
+use crate::compile::frontend::token::token::Position;
 use colored::*;
 pub enum Error {
     PARSE,
@@ -13,6 +14,10 @@ impl Error {
     pub fn found(&self, message: &String) {
         eprintln!("{}:{}", self.string().red().bold(), message);
     }
+    pub fn found_at(&self, pos: &Position, message: &str) {
+        eprintln!("{}:{}", self.string().red().bold(), message);
+        render_source(pos);
+    }
     fn string(&self) -> String {
         match self {
             Self::PARSE => "ParseError".to_string(),
@@ -25,6 +30,43 @@ impl Error {
     }
 }
 
+/* collect errors through lex/parse/sema so that a run reports all of them. */
+#[derive(Default)]
+pub struct Diagnostics {
+    pub errors: usize,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self { errors: 0 }
+    }
+    pub fn error(&mut self, kind: Error, pos: &Position, message: &str) {
+        kind.found_at(pos, message);
+        self.errors += 1;
+    }
+    /* warnings are reported but never abort the compilation. */
+    pub fn warning(&mut self, pos: &Position, message: &str) {
+        eprintln!("{}:{}", "Warning".yellow().bold(), message);
+        render_source(pos);
+    }
+    pub fn has_errors(&self) -> bool {
+        self.errors != 0
+    }
+    pub fn abort_if_errors(&self) {
+        if !self.has_errors() {
+            return;
+        }
+        let plural: &str = if self.errors == 1 { "" } else { "s" };
+        eprintln!(
+            "{}:aborting due to {} previous error{}",
+            "Error".red().bold(),
+            self.errors,
+            plural
+        );
+        std::process::exit(1);
+    }
+}
+
 pub enum Info {
     TYPE,
 }
@@ -39,3 +81,27 @@ impl Info {
         }
     }
 }
+
+/* render the offending source line with a caret underline like rustc. */
+fn render_source(pos: &Position) {
+    let gutter: String = " ".repeat(pos.line.to_string().len());
+    eprintln!("{}{} {}", gutter, "-->".blue().bold(), pos.string());
+    let content: String = match std::fs::read_to_string(&pos.file) {
+        Ok(content) => content,
+        Err(_) => return,
+    };
+    if let Some(line) = content.lines().nth(pos.line.saturating_sub(1)) {
+        let bar = "|".blue().bold();
+        let caret: String = "^".repeat(std::cmp::max(pos.length, 1));
+
+        /* keep tabs so that the caret lines up with the source line. */
+        let padding: String = line
+            .chars()
+            .take(pos.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        eprintln!("{} {}", gutter, bar);
+        eprintln!("{} {} {}", pos.line.to_string().blue().bold(), bar, line);
+        eprintln!("{} {} {}{}", gutter, bar, padding, caret.red().bold());
+    }
+}
@@ -1,5 +1,225 @@
 use super::Optimizer;
+use crate::compile::ir::tac::{Operand, Tac};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/* the operator and its operands, an unary one has an operand. */
+pub type Expression = (String, Vec<Operand>);
 
 impl Optimizer {
-    pub fn available_expression(&mut self) {}
+    /* the expressions computed on every path to each tac and not changed after that. */
+    pub fn available_expression(&mut self) {
+        let universe: BTreeSet<Expression> = self.tacs.iter().filter_map(expression_of).collect();
+        let mut gen: Vec<BTreeSet<Expression>> = vec![BTreeSet::new(); self.tacs.len()];
+        let mut kill: Vec<BTreeSet<Expression>> = vec![BTreeSet::new(); self.tacs.len()];
+        for (idx, t) in self.tacs.iter().enumerate() {
+            kill[idx] = universe.iter().filter(|e| kills(t, e)).cloned().collect();
+            if let Some(e) = expression_of(t) {
+                if !kill[idx].contains(&e) {
+                    gen[idx].insert(e);
+                }
+            }
+        }
+        let len: usize = self.cfg.blocks.len();
+        let mut block_gen: Vec<BTreeSet<Expression>> = vec![BTreeSet::new(); len];
+        let mut block_kill: Vec<BTreeSet<Expression>> = vec![BTreeSet::new(); len];
+        for b in 0..len {
+            for n in self.cfg.blocks[b].range() {
+                block_gen[b] = &(&block_gen[b] - &kill[n]) | &gen[n];
+                block_kill[b] = &block_kill[b] | &kill[n];
+            }
+        }
+        let mut avail_in: Vec<BTreeSet<Expression>> = vec![BTreeSet::new(); len];
+        let mut avail_out: Vec<BTreeSet<Expression>> = vec![universe.clone(); len];
+        'outer: loop {
+            let mut chg_flg: bool = false;
+            for b in 0..len {
+                /* nothing is available at the entry of the function */
+                let mut in_set: BTreeSet<Expression> = BTreeSet::new();
+                let mut preds = self.cfg.pred[b].iter();
+                if let (Some(first), false) = (preds.next(), self.entry_block(b)) {
+                    in_set = avail_out[*first].clone();
+                    for p in preds {
+                        in_set = &in_set & &avail_out[*p];
+                    }
+                }
+                let out_set: BTreeSet<Expression> = &block_gen[b] | &(&in_set - &block_kill[b]);
+                if in_set != avail_in[b] || out_set != avail_out[b] {
+                    chg_flg = true;
+                }
+                avail_in[b] = in_set;
+                avail_out[b] = out_set;
+            }
+            if !chg_flg {
+                break 'outer;
+            }
+        }
+
+        /* the expressions available at each tac, from the entry of its block */
+        self.available = vec![BTreeSet::new(); self.tacs.len()];
+        for (b, block_in) in avail_in.into_iter().enumerate() {
+            let mut avail: BTreeSet<Expression> = block_in;
+            for n in self.cfg.blocks[b].range() {
+                let out: BTreeSet<Expression> = &gen[n] | &(&avail - &kill[n]);
+                self.available[n] = std::mem::replace(&mut avail, out);
+            }
+        }
+    }
+    /* the available expression is computed into a new register where it reaches from, and reused. */
+    pub fn eliminate_common_subexpression(&mut self) {
+        let mut virt: usize = self
+            .tacs
+            .iter()
+            .filter_map(|t| match t {
+                Tac::EX(Operand::REG(virt, _, _, _), _, _, _)
+                | Tac::UNEX(Operand::REG(virt, _, _, _), _, _)
+                | Tac::LET(Operand::REG(virt, _, _, _), _) => Some(*virt + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        let mut temps: BTreeMap<(usize, Expression), Operand> = BTreeMap::new();
+        let mut sources: BTreeMap<usize, Operand> = BTreeMap::new();
+        let mut reuses: BTreeMap<usize, Operand> = BTreeMap::new();
+        let mut func: usize = 0;
+        for (idx, t) in self.tacs.iter().enumerate() {
+            if let Tac::FUNCNAME(_) = t {
+                func = idx;
+            }
+            let e: Expression = match expression_of(t) {
+                Some(e) if self.available[idx].contains(&e) => e,
+                _ => continue,
+            };
+            let computations: BTreeSet<usize> = self.find_computations(idx, &e);
+            /* it reaches itself around the loop */
+            if computations.contains(&idx) {
+                continue;
+            }
+            let temp: Operand = temps
+                .entry((func, e))
+                .or_insert_with(|| {
+                    virt += 1;
+                    Operand::REG(virt - 1, 0, None, None)
+                })
+                .clone();
+            for c in computations {
+                sources.entry(c).or_insert_with(|| temp.clone());
+            }
+            reuses.insert(idx, temp);
+        }
+
+        let mut tacs: Vec<Tac> = Vec::new();
+        for (idx, mut t) in self.tacs.clone().into_iter().enumerate() {
+            let temp: &Operand = match reuses.get(&idx) {
+                Some(temp) => {
+                    if let Some(lv) = defined(&t) {
+                        tacs.push(Tac::LET(lv, temp.clone()));
+                    }
+                    continue;
+                }
+                None => match sources.get(&idx) {
+                    Some(temp) => temp,
+                    None => {
+                        tacs.push(t);
+                        continue;
+                    }
+                },
+            };
+            let lv: Operand = match &mut t {
+                Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) => std::mem::replace(lv, temp.clone()),
+                _ => continue,
+            };
+            tacs.push(t);
+            tacs.push(Tac::LET(lv, temp.clone()));
+        }
+
+        self.tacs = tacs;
+        self.build_cfg();
+    }
+    /* the tacs computing 'e' which reach the one at 'idx' without passing another */
+    fn find_computations(&self, idx: usize, e: &Expression) -> BTreeSet<usize> {
+        let mut found: BTreeSet<usize> = BTreeSet::new();
+        let mut visited: BTreeSet<usize> = BTreeSet::new();
+        let mut stack: Vec<usize> = self.tac_preds(idx);
+        while let Some(n) = stack.pop() {
+            if !visited.insert(n) {
+                continue;
+            }
+            let t: &Tac = &self.tacs[n];
+            if let Tac::FUNCNAME(_) = t {
+                continue;
+            }
+            if expression_of(t).as_ref() == Some(e) && !kills(t, e) {
+                found.insert(n);
+                continue;
+            }
+            stack.extend(self.tac_preds(n));
+        }
+        found
+    }
+}
+
+pub fn expression_string(e: &Expression) -> String {
+    let (op, operands) = e;
+    match operands.as_slice() {
+        [lop, rop] => format!("{} {} {}", lop.string(), op, rop.string()),
+        [op1] => format!("{}{}", op, op1.string()),
+        _ => op.to_string(),
+    }
+}
+
+/* the pure computations, the calls and the memory accesses aren't reused. */
+fn expression_of(t: &Tac) -> Option<Expression> {
+    let (op, operands): (&String, Vec<Operand>) = match t {
+        Tac::EX(Operand::REG(_, _, None, None), op, lop, rop) => {
+            (op, vec![lop.clone(), rop.clone()])
+        }
+        /* '*' is the dereference */
+        Tac::UNEX(Operand::REG(_, _, None, None), op, lop) if !op.starts_with('*') => {
+            (op, vec![lop.clone()])
+        }
+        _ => return None,
+    };
+    if op == "call" || !operands.iter().all(pure) {
+        return None;
+    }
+    Some((op.to_string(), operands))
+}
+
+fn pure(op: &Operand) -> bool {
+    match op {
+        Operand::REG(_, _, oind, omember) | Operand::ID(_, _, oind, omember, _) => {
+            oind.is_none() && omember.is_none()
+        }
+        Operand::CALL(_, _) => false,
+        _ => true,
+    }
+}
+
+fn is_call(op: &Operand) -> bool {
+    matches!(op, Operand::CALL(_, _))
+}
+
+fn defined(t: &Tac) -> Option<Operand> {
+    match t {
+        Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) | Tac::LET(lv, _) => Some(lv.clone()),
+        _ => None,
+    }
+}
+
+/* the definition changes the operand of 'e', a call may change anything. */
+fn kills(t: &Tac, e: &Expression) -> bool {
+    let calls: bool = match t {
+        Tac::UNEX(_, op, _) if op == "call" => true,
+        Tac::EX(_, _, lop, rop) => is_call(lop) || is_call(rop),
+        Tac::UNEX(_, _, op) | Tac::LET(_, op) | Tac::RET(op) | Tac::PARAM(_, op) => is_call(op),
+        _ => false,
+    };
+    if calls {
+        return true;
+    }
+    match defined(t) {
+        Some(lv) => e.1.iter().any(|op| op.string() == lv.string()),
+        None => false,
+    }
 }
@@ -1,24 +1,132 @@
 use crate::ce::types::Info;
+use crate::compile::frontend::parse::node::{Global, NodeKind};
 use crate::compile::ir;
 use ir::lir::x64;
 use ir::tac::{Operand, Tac};
 
-static X64_REGS: [&str; 9] = [
-    "r10", "r11", "r12", "r13", "r14", "r15", "rax", "rdx", "rcx",
+static X64_REGS: [&str; 10] = [
+    "r10", "r11", "r12", "r13", "r14", "r15", "rax", "rdx", "rcx", "rbx",
 ];
-static X64_ARGREGS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+static X64_REGS32: [&str; 10] = [
+    "r10d", "r11d", "r12d", "r13d", "r14d", "r15d", "eax", "edx", "ecx", "ebx",
+];
+static X64_REGS16: [&str; 10] = [
+    "r10w", "r11w", "r12w", "r13w", "r14w", "r15w", "ax", "dx", "cx", "bx",
+];
+static X64_REGS8: [&str; 10] = [
+    "r10b", "r11b", "r12b", "r13b", "r14b", "r15b", "al", "dl", "cl", "bl",
+];
+/* the 7th argument is passed in rax, 'syscall(n, a1, .., a6)' needs it. */
+static X64_ARGREGS: [&str; 7] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9", "rax"];
+static X64_ARGREGS32: [&str; 7] = ["edi", "esi", "edx", "ecx", "r8d", "r9d", "eax"];
+static X64_ARGREGS16: [&str; 7] = ["di", "si", "dx", "cx", "r8w", "r9w", "ax"];
+static X64_ARGREGS8: [&str; 7] = ["dil", "sil", "dl", "cl", "r8b", "r9b", "al"];
 static mut ARGREG: usize = 0;
 static RETURN_REG: usize = 6;
+/* never allocated, holds a narrower integer while it is extended. */
+static SCRATCH_REG: usize = 9;
 fn gr(n: &usize) -> &str {
     X64_REGS[*n]
 }
 fn argr(r: usize) -> &'static str {
     X64_ARGREGS[r]
 }
-pub fn genx64(tacs: Vec<Tac>) -> String {
+fn sized_gr(n: &usize, size: usize) -> &'static str {
+    match size {
+        1 => X64_REGS8[*n],
+        2 => X64_REGS16[*n],
+        4 => X64_REGS32[*n],
+        _ => X64_REGS[*n],
+    }
+}
+fn sized_argr(r: usize, size: usize) -> &'static str {
+    match size {
+        1 => X64_ARGREGS8[r],
+        2 => X64_ARGREGS16[r],
+        4 => X64_ARGREGS32[r],
+        _ => X64_ARGREGS[r],
+    }
+}
+fn ptr_size(size: usize) -> &'static str {
+    match size {
+        1 => "BYTE",
+        2 => "WORD",
+        4 => "DWORD",
+        _ => "QWORD",
+    }
+}
+pub fn genx64(tacs: Vec<Tac>, globals: &[Global], strings: &[String]) -> String {
     let mut generator = Generator::new(tacs);
     generator.gen_ir();
-    generator.emit()
+    let mut out: String = emit_globals(globals) + &emit_strings(strings);
+    if !out.is_empty() {
+        out += ".text\n";
+    }
+    out + &generator.emit()
+}
+/* the string-literals go to .rodata with the terminating null byte. */
+fn emit_strings(strings: &[String]) -> String {
+    let mut out: String = String::new();
+    for (idx, contents) in strings.iter().enumerate() {
+        let bytes: Vec<String> = contents
+            .bytes()
+            .chain(std::iter::once(0))
+            .map(|b| b.to_string())
+            .collect();
+        out += &format!("str.{}:\n  .byte {}\n", idx, bytes.join(", "));
+    }
+    if !out.is_empty() {
+        out.insert_str(0, ".section .rodata\n");
+    }
+    out
+}
+/* 'syscall' moves the arguments to the registers of the Linux system call instead of calling. */
+fn call_site(name: &str) -> String {
+    if name != "syscall" {
+        return format!("  call {}\n", name);
+    }
+    let mut out: String = String::new();
+    out += "  mov r10, r8\n";
+    out += "  mov r8, r9\n";
+    out += "  mov r9, rax\n";
+    out += "  mov rax, rdi\n";
+    out += "  mov rdi, rsi\n";
+    out += "  mov rsi, rdx\n";
+    out += "  mov rdx, rcx\n";
+    out += "  syscall\n";
+    out
+}
+/* '*u8' -> (1, false), '*i32' -> (4, true) */
+fn deref_width(op: &str) -> (usize, bool) {
+    let bits: usize = op[2..].parse::<usize>().unwrap_or(64);
+    (bits / 8, op.starts_with("*i"))
+}
+/* the initialized globals go to .data, the zeroed ones to .bss. */
+fn emit_globals(globals: &[Global]) -> String {
+    let mut data: String = String::new();
+    let mut bss: String = String::new();
+    for g in globals.iter() {
+        let value: i128 = match g.init.kind {
+            NodeKind::INTEGER(v) => g.ty.wrap(v),
+            NodeKind::BOOLEAN(b) => b as i128,
+            _ => 0,
+        };
+        if value == 0 {
+            bss += &format!("{}:\n  .zero 8\n", g.symbol);
+        } else {
+            data += &format!("{}:\n  .quad {}\n", g.symbol, value as i64);
+        }
+    }
+    let mut out: String = String::new();
+    if !data.is_empty() {
+        out += ".data\n";
+        out += &data;
+    }
+    if !bss.is_empty() {
+        out += ".bss\n";
+        out += &bss;
+    }
+    out
 }
 struct Generator {
     tacs: Vec<Tac>,
@@ -33,7 +141,11 @@ impl Generator {
     }
     fn gen_ir(&mut self) {
         let tacs = self.tacs.clone();
-        for t in tacs.iter() {
+        for tac in tacs.iter() {
+            let t: &Tac = &match self.lower_sized(tac) {
+                Some(lowered) => lowered,
+                None => continue,
+            };
             match t {
                 Tac::EX(lv, op, lop, rop) => {
                     if let Operand::REG(_virt, phys, _oind, _omember) = lv {
@@ -50,7 +162,7 @@ impl Generator {
                         self.lirs.push(x64::IR::RETURNREG(*phys));
                     } else if let Operand::INTLIT(value) = op {
                         self.lirs.push(x64::IR::RETURNIMM(*value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = op {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = op {
                         if let Some(ind_op) = oind {
                             if let Operand::INTLIT(value) = *ind_op.clone() {
                                 self.lirs
@@ -71,7 +183,23 @@ impl Generator {
                     }
                 }
                 Tac::LET(lv, op) => {
-                    if let Operand::ID(_name, offset, oind, omember) = lv {
+                    if let Operand::REG(_virt, phys, _oind, _omember) = lv {
+                        if let Operand::REG(_virt, p, _oind, _omember) = op {
+                            self.lirs.push(x64::IR::LOADREG(*phys, *p));
+                        } else if let Operand::INTLIT(v) = op {
+                            self.lirs.push(x64::IR::REGIMM(*phys, *v));
+                        } else if let Operand::ID(_name, offset, oind, omember, _) = op {
+                            if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                                self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                            }
+                        } else if let Operand::GLOBAL(name) = op {
+                            self.lirs.push(x64::IR::LOADGLOBAL(*phys, name.to_owned()));
+                        } else if let Operand::STRLIT(_) = op {
+                            self.lirs.push(x64::IR::LOADADDR(*phys, op.string()));
+                        }
+                    } else if let Operand::GLOBAL(name) = lv {
+                        self.store_global(name, op);
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = lv {
                         if let Some(bop) = oind {
                             let ind_op: Operand = *bop.clone();
                             if let Operand::INTLIT(idx) = ind_op {
@@ -81,11 +209,13 @@ impl Generator {
                                 } else if let Operand::INTLIT(v) = op {
                                     self.lirs
                                         .push(x64::IR::STOREIMM(*offset - (idx as usize) * 8, *v));
-                                } else if let Operand::ID(_name, off, _oind, _omember) = op {
-                                    self.lirs.push(x64::IR::STOREMEM(
-                                        *offset - (idx as usize) * 8,
-                                        *off,
-                                    ));
+                                } else if let Operand::ID(_name, off, soind, somember, _) = op {
+                                    if let Some(src) = self.mem_offset(*off, soind, somember, 8) {
+                                        self.lirs.push(x64::IR::STOREMEM(
+                                            *offset - (idx as usize) * 8,
+                                            src,
+                                        ));
+                                    }
                                 } else if let Operand::CALL(name, _length) = op {
                                     self.lirs.push(x64::IR::STORECALL(
                                         *offset - (idx as usize) * 8,
@@ -101,8 +231,10 @@ impl Generator {
                                 self.lirs.push(x64::IR::STOREREG(*member_offset, *p));
                             } else if let Operand::INTLIT(v) = op {
                                 self.lirs.push(x64::IR::STOREIMM(*member_offset, *v));
-                            } else if let Operand::ID(_name, off, _oind, _member) = op {
-                                self.lirs.push(x64::IR::STOREMEM(*member_offset, *off));
+                            } else if let Operand::ID(_name, off, soind, somember, _) = op {
+                                if let Some(src) = self.mem_offset(*off, soind, somember, 8) {
+                                    self.lirs.push(x64::IR::STOREMEM(*member_offset, src));
+                                }
                             } else if let Operand::CALL(name, _length) = op {
                                 self.lirs
                                     .push(x64::IR::STORECALL(*member_offset, name.to_owned()));
@@ -112,9 +244,11 @@ impl Generator {
                                 self.lirs.push(x64::IR::STOREREG(*offset, *p));
                             } else if let Operand::INTLIT(v) = op {
                                 self.lirs.push(x64::IR::STOREIMM(*offset, *v));
-                            } else if let Operand::ID(n, off, _oind, _omember) = op {
+                            } else if let Operand::ID(n, off, soind, somember, _) = op {
                                 if !n.contains("Array") {
-                                    self.lirs.push(x64::IR::STOREMEM(*offset, *off));
+                                    if let Some(src) = self.mem_offset(*off, soind, somember, 8) {
+                                        self.lirs.push(x64::IR::STOREMEM(*offset, src));
+                                    }
                                 }
                             } else if let Operand::CALL(name, _length) = op {
                                 self.lirs.push(x64::IR::STORECALL(*offset, name.to_owned()));
@@ -131,15 +265,15 @@ impl Generator {
                 Tac::PROLOGUE(stack_offset) => {
                     self.lirs.push(x64::IR::PROLOGUE(*stack_offset));
                 }
-                Tac::PUSHARG(reg, arg) => {
-                    self.lirs.push(x64::IR::PUSHARG(*reg, *arg));
+                Tac::PUSHARG(reg, arg, size) => {
+                    self.lirs.push(x64::IR::PUSHARG(*reg, *arg, *size));
                 }
                 Tac::PARAM(reg, op) => {
                     if let Operand::REG(_virt, p, _oind, _omember) = op {
                         self.lirs.push(x64::IR::ARGREG(*reg, *p));
                     } else if let Operand::INTLIT(v) = op {
                         self.lirs.push(x64::IR::ARGIMM(*reg, *v));
-                    } else if let Operand::ID(_name, offset, oind, omember) = op {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = op {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -161,7 +295,7 @@ impl Generator {
                 Tac::IFF(op, label) => {
                     if let Operand::REG(_virt, p, _oind, _omember) = op {
                         self.lirs.push(x64::IR::CMPREG(*p));
-                    } else if let Operand::ID(_name, offset, _oind, _omember) = op {
+                    } else if let Operand::ID(_name, offset, _oind, _omember, _) = op {
                         self.lirs.push(x64::IR::CMPMEM(*offset));
                     } else if let Operand::CALL(name, _length) = op {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -178,6 +312,220 @@ impl Generator {
             };
         }
     }
+    /* extend narrower integers into registers before using them,
+    and store into narrower integers only the bytes they have. */
+    fn lower_sized(&mut self, t: &Tac) -> Option<Tac> {
+        match t {
+            Tac::EX(lv, op, lop, rop) => {
+                let mut phys: usize = SCRATCH_REG;
+                if let Operand::REG(_virt, p, _oind, _omember) = lv {
+                    phys = *p;
+                }
+                let mut lop_reg: usize = phys;
+                if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                    if *p2 == phys {
+                        lop_reg = SCRATCH_REG;
+                    }
+                }
+                let mut lop: Operand = self.load_sized(lop, lop_reg);
+                /* the destination holds rop, so build lop elsewhere not to overwrite it. */
+                if lop_reg == SCRATCH_REG {
+                    match &lop {
+                        Operand::ID(_name, offset, oind, omember, _) => {
+                            if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                                self.lirs.push(x64::IR::LOADMEM(SCRATCH_REG, src));
+                            }
+                            lop = Operand::REG(0, SCRATCH_REG, None, None);
+                        }
+                        Operand::INTLIT(value) => {
+                            self.lirs.push(x64::IR::REGIMM(SCRATCH_REG, *value));
+                            lop = Operand::REG(0, SCRATCH_REG, None, None);
+                        }
+                        _ => (),
+                    }
+                }
+                let rop: Operand = self.load_sized(rop, SCRATCH_REG);
+                let rop: Operand = self.load_wide(&rop, SCRATCH_REG);
+                Some(Tac::EX(lv.clone(), op.to_string(), lop, rop))
+            }
+            Tac::UNEX(lv, op, lop) => {
+                if op == "&" {
+                    return Some(t.clone());
+                }
+                let mut phys: usize = SCRATCH_REG;
+                if let Operand::REG(_virt, p, _oind, _omember) = lv {
+                    phys = *p;
+                }
+                let lop: Operand = self.load_sized(lop, phys);
+                Some(Tac::UNEX(lv.clone(), op.to_string(), lop))
+            }
+            Tac::RET(op) => Some(Tac::RET(self.load_sized(op, SCRATCH_REG))),
+            Tac::PARAM(reg, op) => Some(Tac::PARAM(*reg, self.load_sized(op, SCRATCH_REG))),
+            Tac::IFF(op, label) => Some(Tac::IFF(
+                self.load_sized(op, SCRATCH_REG),
+                label.to_string(),
+            )),
+            Tac::LET(lv, op) => {
+                let op: Operand = self.load_sized(op, SCRATCH_REG);
+                if let Operand::ID(_name, offset, oind, omember, (size, _signed)) = lv {
+                    if *size < 8 {
+                        if let Some(dst) = self.mem_offset(*offset, oind, omember, *size) {
+                            match &op {
+                                Operand::REG(_virt, p, _oind, _omember) => {
+                                    self.lirs.push(x64::IR::STORESIZEDREG(dst, *p, *size));
+                                }
+                                Operand::INTLIT(v) => {
+                                    self.lirs.push(x64::IR::STORESIZEDIMM(dst, *v, *size));
+                                }
+                                Operand::ID(_name, off, soind, somember, _) => {
+                                    if let Some(src) = self.mem_offset(*off, soind, somember, 8) {
+                                        self.lirs.push(x64::IR::LOADMEM(SCRATCH_REG, src));
+                                        self.lirs.push(x64::IR::STORESIZEDREG(
+                                            dst,
+                                            SCRATCH_REG,
+                                            *size,
+                                        ));
+                                    }
+                                }
+                                Operand::CALL(name, _length) => {
+                                    self.lirs.push(x64::IR::CALL(name.to_owned()));
+                                    self.lirs
+                                        .push(x64::IR::STORESIZEDREG(dst, RETURN_REG, *size));
+                                }
+                                Operand::GLOBAL(name) => {
+                                    self.lirs
+                                        .push(x64::IR::LOADGLOBAL(SCRATCH_REG, name.to_owned()));
+                                    self.lirs
+                                        .push(x64::IR::STORESIZEDREG(dst, SCRATCH_REG, *size));
+                                }
+                                Operand::STRLIT(_) => {
+                                    self.lirs.push(x64::IR::LOADADDR(SCRATCH_REG, op.string()));
+                                    self.lirs
+                                        .push(x64::IR::STORESIZEDREG(dst, SCRATCH_REG, *size));
+                                }
+                            }
+                        }
+                        return None;
+                    }
+                }
+                if let Operand::ID(_, _, _, _, _) = lv {
+                    return Some(Tac::LET(lv.clone(), self.load_wide(&op, SCRATCH_REG)));
+                }
+                Some(Tac::LET(lv.clone(), op))
+            }
+            _ => Some(t.clone()),
+        }
+    }
+    fn store_global(&mut self, name: &str, op: &Operand) {
+        let mut src: usize = SCRATCH_REG;
+        match op {
+            Operand::REG(_virt, p, _oind, _omember) => src = *p,
+            Operand::INTLIT(v) => self.lirs.push(x64::IR::REGIMM(SCRATCH_REG, *v)),
+            Operand::ID(_name, offset, oind, omember, _) => {
+                if let Some(off) = self.mem_offset(*offset, oind, omember, 8) {
+                    self.lirs.push(x64::IR::LOADMEM(SCRATCH_REG, off));
+                }
+            }
+            Operand::CALL(func, _length) => {
+                self.lirs.push(x64::IR::CALL(func.to_owned()));
+                src = RETURN_REG;
+            }
+            Operand::GLOBAL(other) => {
+                self.lirs
+                    .push(x64::IR::LOADGLOBAL(SCRATCH_REG, other.to_owned()));
+            }
+            Operand::STRLIT(_) => {
+                self.lirs.push(x64::IR::LOADADDR(SCRATCH_REG, op.string()));
+            }
+        }
+        self.lirs.push(x64::IR::STOREGLOBAL(name.to_string(), src));
+    }
+    /* the immediate not fitting a sign-extended imm32 is moved into the register first. */
+    fn load_wide(&mut self, op: &Operand, reg: usize) -> Operand {
+        if let Operand::INTLIT(value) = op {
+            if !(i32::MIN as i128..=i32::MAX as i128).contains(value) {
+                self.lirs.push(x64::IR::REGIMM(reg, *value));
+                return Operand::REG(0, reg, None, None);
+            }
+        }
+        op.clone()
+    }
+    fn load_sized(&mut self, op: &Operand, reg: usize) -> Operand {
+        if let Operand::ID(_name, offset, Some(ind), None, (size, signed)) = op {
+            if !matches!(**ind, Operand::INTLIT(_)) {
+                self.load_indexed(*offset, ind, reg, *size, *signed);
+                return Operand::REG(0, reg, None, None);
+            }
+        }
+        if let Operand::ID(_name, offset, oind, omember, (size, signed)) = op {
+            if *size < 8 {
+                if let Some(src) = self.mem_offset(*offset, oind, omember, *size) {
+                    if *signed {
+                        self.lirs.push(x64::IR::LOADMEMSX(reg, src, *size));
+                    } else {
+                        self.lirs.push(x64::IR::LOADMEMZX(reg, src, *size));
+                    }
+                }
+                return Operand::REG(0, reg, None, None);
+            }
+        }
+        op.clone()
+    }
+    /* the element at the variable index is read through its address, 'rbp - offset + index * size'. */
+    fn load_indexed(
+        &mut self,
+        offset: usize,
+        index: &Operand,
+        reg: usize,
+        size: usize,
+        signed: bool,
+    ) {
+        match self.load_sized(index, reg) {
+            Operand::REG(_virt, p, _oind, _omember) if p != reg => {
+                self.lirs.push(x64::IR::LOADREG(reg, p));
+            }
+            Operand::ID(_name, off, oind, omember, _) => {
+                if let Some(src) = self.mem_offset(off, &oind, &omember, 8) {
+                    self.lirs.push(x64::IR::LOADMEM(reg, src));
+                }
+            }
+            Operand::GLOBAL(name) => {
+                self.lirs.push(x64::IR::LOADGLOBAL(reg, name));
+            }
+            Operand::INTLIT(value) => {
+                self.lirs.push(x64::IR::REGIMM(reg, value));
+            }
+            _ => (),
+        }
+        if size != 1 {
+            self.lirs.push(x64::IR::MULIMM(reg, size as i128));
+        }
+        self.lirs.push(x64::IR::ADDRESSINDEX(reg, offset));
+        if size == 8 {
+            self.lirs.push(x64::IR::DEREFREG(reg));
+        } else {
+            self.lirs.push(x64::IR::DEREFSIZED(reg, size, signed));
+        }
+    }
+    fn mem_offset(
+        &self,
+        offset: usize,
+        oind: &Option<Box<Operand>>,
+        omember: &Option<usize>,
+        size: usize,
+    ) -> Option<usize> {
+        if let Some(ind) = oind {
+            if let Operand::INTLIT(idx) = **ind {
+                return Some(offset - idx as usize * size);
+            }
+            Info::TYPE.found(&"index without int-lit not implemented".to_string());
+            return None;
+        }
+        if let Some(member_offset) = omember {
+            return Some(*member_offset);
+        }
+        Some(offset)
+    }
     fn unex_reg(&mut self, phys: &usize, op: &String, lop: &Operand) {
         if let Operand::REG(_virs, p, _oind, _omember) = lop {
             match op.as_str() {
@@ -187,6 +535,20 @@ impl Generator {
                 "*" => {
                     self.lirs.push(x64::IR::DEREFREG(*p));
                 }
+                op if op.starts_with('*') => {
+                    let (size, signed) = deref_width(op);
+                    self.lirs.push(x64::IR::DEREFSIZED(*p, size, signed));
+                }
+                "!" => {
+                    self.lirs.push(x64::IR::EQIMM(*p, 0));
+                }
+                "~" => {
+                    self.lirs.push(x64::IR::NOTREG(*p));
+                }
+                "call" => {
+                    self.lirs.push(x64::IR::CALLREG(*p));
+                    self.lirs.push(x64::IR::LOADREG(*p, RETURN_REG));
+                }
                 _ => (),
             }
             self.lirs.push(x64::IR::LOADREG(*phys, *p));
@@ -196,21 +558,62 @@ impl Generator {
                 "-" => {
                     self.lirs.push(x64::IR::NEGREG(*phys));
                 }
+                "!" => {
+                    self.lirs.push(x64::IR::EQIMM(*phys, 0));
+                }
+                "~" => {
+                    self.lirs.push(x64::IR::NOTREG(*phys));
+                }
                 _ => (),
             }
-        } else if let Operand::ID(_virt, offset, _oind, _omember) = lop {
+        } else if let Operand::GLOBAL(name) = lop {
+            /* the address of a function */
+            if op == "&" {
+                self.lirs.push(x64::IR::LOADADDR(*phys, name.to_owned()));
+            }
+        } else if let Operand::ID(_virt, offset, oind, omember, _) = lop {
             match op.as_str() {
+                "call" => {
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(SCRATCH_REG, src));
+                    }
+                    self.lirs.push(x64::IR::CALLREG(SCRATCH_REG));
+                    self.lirs.push(x64::IR::LOADREG(*phys, RETURN_REG));
+                }
                 "-" => {
-                    self.lirs.push(x64::IR::LOADMEM(*phys, *offset));
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                    }
                     self.lirs.push(x64::IR::NEGREG(*phys));
                 }
                 "&" => {
                     self.lirs.push(x64::IR::ADDRESSMEM(*phys, *offset));
                 }
                 "*" => {
-                    self.lirs.push(x64::IR::LOADMEM(*phys, *offset));
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                    }
                     self.lirs.push(x64::IR::DEREFREG(*phys));
                 }
+                op if op.starts_with('*') => {
+                    let (size, signed) = deref_width(op);
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                    }
+                    self.lirs.push(x64::IR::DEREFSIZED(*phys, size, signed));
+                }
+                "!" => {
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                    }
+                    self.lirs.push(x64::IR::EQIMM(*phys, 0));
+                }
+                "~" => {
+                    if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                        self.lirs.push(x64::IR::LOADMEM(*phys, src));
+                    }
+                    self.lirs.push(x64::IR::NOTREG(*phys));
+                }
                 _ => (),
             }
         }
@@ -223,7 +626,7 @@ impl Generator {
                         self.lirs.push(x64::IR::ADDREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::ADDIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -247,7 +650,7 @@ impl Generator {
                         self.lirs.push(x64::IR::SUBREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::SUBIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -271,7 +674,7 @@ impl Generator {
                         self.lirs.push(x64::IR::MULREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::MULIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -295,7 +698,7 @@ impl Generator {
                         self.lirs.push(x64::IR::DIVREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::DIVIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -314,12 +717,36 @@ impl Generator {
                         self.lirs.push(x64::IR::DIVREG(*p, 0));
                     }
                 }
+                "/u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UDIVREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UDIVIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::UDIVMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::UDIVMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::UDIVMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UDIVREG(*p, 0));
+                    }
+                }
                 "%" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::MODREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::MODIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -338,12 +765,36 @@ impl Generator {
                         self.lirs.push(x64::IR::MODREG(*p, 0));
                     }
                 }
+                "%u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UMODREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UMODIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::UMODMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::UMODMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::UMODMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UMODREG(*p, 0));
+                    }
+                }
                 "<<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LSHIFTREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LSHIFTIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -367,7 +818,7 @@ impl Generator {
                         self.lirs.push(x64::IR::RSHIFTREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::RSHIFTIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -386,12 +837,108 @@ impl Generator {
                         self.lirs.push(x64::IR::RSHIFTREG(*p, 0));
                     }
                 }
+                ">>>" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::URSHIFTREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::URSHIFTIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::URSHIFTMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::URSHIFTMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::URSHIFTMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::URSHIFTREG(*p, 0));
+                    }
+                }
+                "&" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ANDREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ANDIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::ANDMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::ANDMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::ANDMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ANDREG(*p, 0));
+                    }
+                }
+                "|" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ORREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ORIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::ORMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::ORMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::ORMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ORREG(*p, 0));
+                    }
+                }
+                "^" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::XORREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::XORIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::XORMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::XORMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::XORMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::XORREG(*p, 0));
+                    }
+                }
                 "<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LTREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LTIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -410,12 +957,36 @@ impl Generator {
                         self.lirs.push(x64::IR::LTREG(*p, 0));
                     }
                 }
+                "<u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ULTREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ULTIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::ULTMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::ULTMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::ULTMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ULTREG(*p, 0));
+                    }
+                }
                 "<=" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LTEQREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LTEQIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -434,366 +1005,680 @@ impl Generator {
                         self.lirs.push(x64::IR::LTEQREG(*p, 0));
                     }
                 }
+                "<=u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ULTEQREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ULTEQIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::ULTEQMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::ULTEQMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::ULTEQMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ULTEQREG(*p, 0));
+                    }
+                }
                 ">" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::GTREG(*p, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::GTIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::GTIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::GTMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::GTMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::GTMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::GTREG(*p, 0));
+                    }
+                }
+                ">u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UGTREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UGTIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::UGTMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::UGTMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::UGTMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UGTREG(*p, 0));
+                    }
+                }
+                ">=" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::GTEQREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::GTEQIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::GTEQMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::GTEQMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::GTEQMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::GTEQREG(*p, 0));
+                    }
+                }
+                ">=u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UGTEQREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UGTEQIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::UGTEQMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::UGTEQMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::UGTEQMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UGTEQREG(*p, 0));
+                    }
+                }
+                "==" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::EQREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::EQIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::EQMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::EQMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::EQMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::EQREG(*p, 0));
+                    }
+                }
+                "!=" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::NTEQREG(*p, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::NTEQIMM(*p, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::NTEQMEM(*p, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::NTEQMEM(*p, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::NTEQMEM(*p, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::NTEQREG(*p, 0));
+                    }
+                }
+                _ => (),
+            }
+            self.lirs.push(x64::IR::LOADREG(*phys, *p));
+        } else if let Operand::ID(_name, offset, oind, omember, _) = lop {
+            if let Some(src) = self.mem_offset(*offset, oind, omember, 8) {
+                self.lirs.push(x64::IR::LOADMEM(*phys, src));
+            }
+            match op.as_str() {
+                "+" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ADDREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ADDIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::ADDMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::ADDMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::ADDMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ADDREG(*phys, 0));
+                    }
+                }
+                "-" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::SUBREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::SUBIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::SUBMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::SUBMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::SUBMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::SUBREG(*phys, 0));
+                    }
+                }
+                "*" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::MULREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::MULIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::MULMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::MULMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::MULMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::MULREG(*phys, 0));
+                    }
+                }
+                "/" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::DIVREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::DIVIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::DIVMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::DIVMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::DIVMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::DIVREG(*phys, 0));
+                    }
+                }
+                "/u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UDIVREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UDIVIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::UDIVMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::UDIVMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::UDIVMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UDIVREG(*phys, 0));
+                    }
+                }
+                "%" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::MODREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::MODIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
+                        if let Some(ind) = oind {
+                            if let Operand::INTLIT(idx) = *ind.clone() {
+                                self.lirs
+                                    .push(x64::IR::MODMEM(*phys, *offset - idx as usize * 8));
+                            } else {
+                                Info::TYPE
+                                    .found(&"index without int-lit not implemented".to_string());
+                            }
+                        } else if let Some(member_offset) = omember {
+                            self.lirs.push(x64::IR::MODMEM(*phys, *member_offset));
+                        } else {
+                            self.lirs.push(x64::IR::MODMEM(*phys, *offset));
+                        }
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::MODREG(*phys, 0));
+                    }
+                }
+                "%u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UMODREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UMODIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::GTMEM(*p, *offset - idx as usize * 8));
+                                    .push(x64::IR::UMODMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::GTMEM(*p, *member_offset));
+                            self.lirs.push(x64::IR::UMODMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::GTMEM(*p, *offset));
+                            self.lirs.push(x64::IR::UMODMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::GTREG(*p, 0));
+                        self.lirs.push(x64::IR::UMODREG(*phys, 0));
                     }
                 }
-                ">=" => {
+                "<<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::GTEQREG(*p, *p2));
+                        self.lirs.push(x64::IR::LSHIFTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::GTEQIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::LSHIFTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::GTEQMEM(*p, *offset - idx as usize * 8));
+                                    .push(x64::IR::LSHIFTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::GTEQMEM(*p, *member_offset));
+                            self.lirs.push(x64::IR::LSHIFTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::GTEQMEM(*p, *offset));
+                            self.lirs.push(x64::IR::LSHIFTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::GTEQREG(*p, 0));
+                        self.lirs.push(x64::IR::LSHIFTREG(*phys, 0));
                     }
                 }
-                "==" => {
+                ">>" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::EQREG(*p, *p2));
+                        self.lirs.push(x64::IR::RSHIFTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::EQIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::RSHIFTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::EQMEM(*p, *offset - idx as usize * 8));
+                                    .push(x64::IR::RSHIFTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::EQMEM(*p, *member_offset));
+                            self.lirs.push(x64::IR::RSHIFTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::EQMEM(*p, *offset));
+                            self.lirs.push(x64::IR::RSHIFTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::EQREG(*p, 0));
+                        self.lirs.push(x64::IR::RSHIFTREG(*phys, 0));
                     }
                 }
-                "!=" => {
+                ">>>" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::NTEQREG(*p, *p2));
+                        self.lirs.push(x64::IR::URSHIFTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::NTEQIMM(*p, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::URSHIFTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::NTEQMEM(*p, *offset - idx as usize * 8));
+                                    .push(x64::IR::URSHIFTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::NTEQMEM(*p, *member_offset));
+                            self.lirs.push(x64::IR::URSHIFTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::NTEQMEM(*p, *offset));
+                            self.lirs.push(x64::IR::URSHIFTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::NTEQREG(*p, 0));
+                        self.lirs.push(x64::IR::URSHIFTREG(*phys, 0));
                     }
                 }
-                _ => (),
-            }
-            self.lirs.push(x64::IR::LOADREG(*phys, *p));
-        } else if let Operand::ID(_name, offset, _oind, _omember) = lop {
-            self.lirs.push(x64::IR::LOADMEM(*phys, *offset));
-            match op.as_str() {
-                "+" => {
+                "&" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::ADDREG(*phys, *p2));
+                        self.lirs.push(x64::IR::ANDREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::ADDIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::ANDIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::ADDMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::ANDMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::ADDMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::ANDMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::ADDMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::ANDMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::ADDREG(*phys, 0));
+                        self.lirs.push(x64::IR::ANDREG(*phys, 0));
                     }
                 }
-                "-" => {
+                "|" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::SUBREG(*phys, *p2));
+                        self.lirs.push(x64::IR::ORREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::SUBIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::ORIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::SUBMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::ORMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::SUBMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::ORMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::SUBMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::ORMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::SUBREG(*phys, 0));
+                        self.lirs.push(x64::IR::ORREG(*phys, 0));
                     }
                 }
-                "*" => {
+                "^" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::MULREG(*phys, *p2));
+                        self.lirs.push(x64::IR::XORREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::MULIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::XORIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::MULMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::XORMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::MULMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::XORMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::MULMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::XORMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::MULREG(*phys, 0));
+                        self.lirs.push(x64::IR::XORREG(*phys, 0));
                     }
                 }
-                "/" => {
+                "<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::DIVREG(*phys, *p2));
+                        self.lirs.push(x64::IR::LTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::DIVIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::LTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::DIVMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::LTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::DIVMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::LTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::DIVMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::LTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::DIVREG(*phys, 0));
+                        self.lirs.push(x64::IR::LTREG(*phys, 0));
                     }
                 }
-                "%" => {
+                "<u" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::MODREG(*phys, *p2));
+                        self.lirs.push(x64::IR::ULTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::MODIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::ULTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::MODMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::ULTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::MODMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::ULTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::MODMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::ULTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::MODREG(*phys, 0));
+                        self.lirs.push(x64::IR::ULTREG(*phys, 0));
                     }
                 }
-                "<<" => {
+                "<=" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::LSHIFTREG(*phys, *p2));
+                        self.lirs.push(x64::IR::LTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::LSHIFTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::LTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::LSHIFTMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::LTEQMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::LSHIFTMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::LTEQMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::LSHIFTMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::LTEQMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::LSHIFTREG(*phys, 0));
+                        self.lirs.push(x64::IR::LTEQREG(*phys, 0));
                     }
                 }
-                ">>" => {
+                "<=u" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::RSHIFTREG(*phys, *p2));
+                        self.lirs.push(x64::IR::ULTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::RSHIFTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::ULTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::RSHIFTMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::ULTEQMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::RSHIFTMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::ULTEQMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::RSHIFTMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::ULTEQMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::RSHIFTREG(*phys, 0));
+                        self.lirs.push(x64::IR::ULTEQREG(*phys, 0));
                     }
                 }
-                "<" => {
+                ">" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::LTREG(*phys, *p2));
+                        self.lirs.push(x64::IR::GTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::LTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::GTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::LTMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::GTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::LTMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::GTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::LTMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::GTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::LTREG(*phys, 0));
+                        self.lirs.push(x64::IR::GTREG(*phys, 0));
                     }
                 }
-                "<=" => {
+                ">u" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::LTEQREG(*phys, *p2));
+                        self.lirs.push(x64::IR::UGTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::LTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::UGTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::LTEQMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::UGTMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::LTEQMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::UGTMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::LTEQMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::UGTMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
-                        self.lirs.push(x64::IR::LTEQREG(*phys, 0));
+                        self.lirs.push(x64::IR::UGTREG(*phys, 0));
                     }
                 }
-                ">" => {
+                ">=" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::GTREG(*phys, *p2));
+                        self.lirs.push(x64::IR::GTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::GTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::GTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::GTMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::GTEQMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::GTMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::GTEQMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::GTMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::GTEQMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::GTREG(*phys, 0));
                     }
                 }
-                ">=" => {
+                ">=u" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
-                        self.lirs.push(x64::IR::GTEQREG(*phys, *p2));
+                        self.lirs.push(x64::IR::UGTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
-                        self.lirs.push(x64::IR::GTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                        self.lirs.push(x64::IR::UGTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
-                                    .push(x64::IR::GTEQMEM(*phys, *offset - idx as usize * 8));
+                                    .push(x64::IR::UGTEQMEM(*phys, *offset - idx as usize * 8));
                             } else {
                                 Info::TYPE
                                     .found(&"index without int-lit not implemented".to_string());
                             }
                         } else if let Some(member_offset) = omember {
-                            self.lirs.push(x64::IR::GTEQMEM(*phys, *member_offset));
+                            self.lirs.push(x64::IR::UGTEQMEM(*phys, *member_offset));
                         } else {
-                            self.lirs.push(x64::IR::GTEQMEM(*phys, *offset));
+                            self.lirs.push(x64::IR::UGTEQMEM(*phys, *offset));
                         }
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -805,7 +1690,7 @@ impl Generator {
                         self.lirs.push(x64::IR::EQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::EQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -829,7 +1714,7 @@ impl Generator {
                         self.lirs.push(x64::IR::NTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::NTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, offset, oind, omember) = rop {
+                    } else if let Operand::ID(_name, offset, oind, omember, _) = rop {
                         if let Some(ind) = oind {
                             if let Operand::INTLIT(idx) = *ind.clone() {
                                 self.lirs
@@ -858,7 +1743,7 @@ impl Generator {
                         self.lirs.push(x64::IR::ADDREG(*phys, *p2));
                     } else if let Operand::INTLIT(v2) = rop {
                         self.lirs.push(x64::IR::ADDIMM(*phys, *v2));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::ADDMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -870,7 +1755,7 @@ impl Generator {
                         self.lirs.push(x64::IR::SUBREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::SUBIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::SUBMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -882,7 +1767,7 @@ impl Generator {
                         self.lirs.push(x64::IR::MULREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::MULIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::MULMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -894,31 +1779,55 @@ impl Generator {
                         self.lirs.push(x64::IR::DIVREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::DIVIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::DIVMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::DIVREG(*phys, 0));
                     }
                 }
+                "/u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UDIVREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UDIVIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::UDIVMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UDIVREG(*phys, 0));
+                    }
+                }
                 "%" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::MODREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::MODIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::MODMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::MODREG(*phys, 0));
                     }
                 }
+                "%u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UMODREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UMODIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::UMODMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UMODREG(*phys, 0));
+                    }
+                }
                 "<<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LSHIFTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LSHIFTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::LSHIFTMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -930,67 +1839,163 @@ impl Generator {
                         self.lirs.push(x64::IR::RSHIFTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::RSHIFTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::RSHIFTMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::RSHIFTREG(*phys, 0));
                     }
                 }
+                ">>>" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::URSHIFTREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::URSHIFTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::URSHIFTMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::URSHIFTREG(*phys, 0));
+                    }
+                }
+                "&" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ANDREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ANDIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::ANDMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ANDREG(*phys, 0));
+                    }
+                }
+                "|" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ORREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ORIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::ORMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ORREG(*phys, 0));
+                    }
+                }
+                "^" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::XORREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::XORIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::XORMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::XORREG(*phys, 0));
+                    }
+                }
                 "<" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::LTMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::LTREG(*phys, 0));
                     }
                 }
+                "<u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ULTREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ULTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::ULTMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ULTREG(*phys, 0));
+                    }
+                }
                 ">" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::GTREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::GTIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::GTMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::GTREG(*phys, 0));
                     }
                 }
+                ">u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UGTREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UGTIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::UGTMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UGTREG(*phys, 0));
+                    }
+                }
                 "<=" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::LTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::LTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::LTEQMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::LTEQREG(*phys, 0));
                     }
                 }
+                "<=u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::ULTEQREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::ULTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::ULTEQMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::ULTEQREG(*phys, 0));
+                    }
+                }
                 ">=" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::GTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::GTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::GTEQMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
                         self.lirs.push(x64::IR::GTEQREG(*phys, 0));
                     }
                 }
+                ">=u" => {
+                    if let Operand::REG(_virt, p2, _oind, _omember) = rop {
+                        self.lirs.push(x64::IR::UGTEQREG(*phys, *p2));
+                    } else if let Operand::INTLIT(value) = rop {
+                        self.lirs.push(x64::IR::UGTEQIMM(*phys, *value));
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
+                        self.lirs.push(x64::IR::UGTEQMEM(*phys, *p2));
+                    } else if let Operand::CALL(name, _length) = rop {
+                        self.lirs.push(x64::IR::CALL(name.to_owned()));
+                        self.lirs.push(x64::IR::UGTEQREG(*phys, 0));
+                    }
+                }
                 "==" => {
                     if let Operand::REG(_virt, p2, _oind, _omember) = rop {
                         self.lirs.push(x64::IR::EQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::EQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::EQMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -1002,7 +2007,7 @@ impl Generator {
                         self.lirs.push(x64::IR::NTEQREG(*phys, *p2));
                     } else if let Operand::INTLIT(value) = rop {
                         self.lirs.push(x64::IR::NTEQIMM(*phys, *value));
-                    } else if let Operand::ID(_name, p2, _oind, _omember) = rop {
+                    } else if let Operand::ID(_name, p2, _oind, _omember, _) = rop {
                         self.lirs.push(x64::IR::NTEQMEM(*phys, *p2));
                     } else if let Operand::CALL(name, _length) = rop {
                         self.lirs.push(x64::IR::CALL(name.to_owned()));
@@ -1021,14 +2026,32 @@ impl Generator {
                     out += &(format!("  mov -{}[rbp], {}\n", dst, gr(src)).as_str());
                 }
                 x64::IR::STOREIMM(dst, value) => {
-                    out += &(format!("  mov -{}[rbp], {}\n", dst, value).as_str());
+                    out += &(format!("  mov QWORD PTR -{}[rbp], {}\n", dst, value).as_str());
+                }
+                x64::IR::STORESIZEDREG(dst, src, size) => {
+                    out += &format!(
+                        "  mov {} PTR -{}[rbp], {}\n",
+                        ptr_size(*size),
+                        dst,
+                        sized_gr(src, *size)
+                    );
+                }
+                x64::IR::STORESIZEDIMM(dst, value, size) => {
+                    /* the immediate is truncated as the store does. */
+                    let mask: i128 = (1 << (size * 8)) - 1;
+                    out += &format!(
+                        "  mov {} PTR -{}[rbp], {}\n",
+                        ptr_size(*size),
+                        dst,
+                        value & mask
+                    );
                 }
                 x64::IR::STOREMEM(dst, offset) => {
                     out += &(format!("  mov r12, -{}[rbp]\n", offset).as_str());
                     out += &(format!("  mov -{}[rbp], r12\n", dst).as_str());
                 }
                 x64::IR::STORECALL(dst, symbol) => {
-                    out += &(format!("  call {}\n", symbol).as_str());
+                    out += &call_site(symbol);
                     out += &(format!("  mov -{}[rbp], rax\n", dst).as_str());
                 }
                 x64::IR::ADDREG(dst, src) => {
@@ -1064,6 +2087,12 @@ impl Generator {
                     out += &(format!("  idiv {}\n", gr(src)).as_str());
                     out += &(format!("  mov {}, rax\n", gr(dst)).as_str());
                 }
+                x64::IR::UDIVREG(dst, src) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += "  xor rdx, rdx\n";
+                    out += &format!("  div {}\n", gr(src));
+                    out += &format!("  mov {}, rax\n", gr(dst));
+                }
                 x64::IR::DIVIMM(dst, value) => {
                     out += &(format!("  mov rax, {}\n", gr(dst)).as_str());
                     out += &(format!("  mov rcx, {}\n", value).as_str());
@@ -1071,18 +2100,37 @@ impl Generator {
                     out += &("  idiv rcx\n".to_string().as_str());
                     out += &(format!("  mov {}, rax\n", gr(dst)).as_str());
                 }
+                x64::IR::UDIVIMM(dst, value) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += &format!("  mov rcx, {}\n", value);
+                    out += "  xor rdx, rdx\n";
+                    out += "  div rcx\n";
+                    out += &format!("  mov {}, rax\n", gr(dst));
+                }
                 x64::IR::DIVMEM(dst, offset) => {
                     out += &(format!("  mov rax, {}\n", gr(dst)).as_str());
                     out += "  cqo\n";
                     out += &(format!("  idiv -{}[rbp]\n", offset).as_str());
                     out += &(format!("  mov {}, rax\n", gr(dst)).as_str());
                 }
+                x64::IR::UDIVMEM(dst, offset) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += "  xor rdx, rdx\n";
+                    out += &format!("  div -{}[rbp]\n", offset);
+                    out += &format!("  mov {}, rax\n", gr(dst));
+                }
                 x64::IR::MODREG(dst, src) => {
                     out += &(format!("  mov rax, {}\n", gr(dst)).as_str());
                     out += "  cqo\n";
                     out += &(format!("  idiv {}\n", gr(src)).as_str());
                     out += &(format!("  mov {}, rdx\n", gr(dst)).as_str());
                 }
+                x64::IR::UMODREG(dst, src) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += "  xor rdx, rdx\n";
+                    out += &format!("  div {}\n", gr(src));
+                    out += &format!("  mov {}, rdx\n", gr(dst));
+                }
                 x64::IR::MODIMM(dst, value) => {
                     out += &(format!("  mov rax, {}\n", gr(dst)).as_str());
                     out += "  cqo\n";
@@ -1090,12 +2138,25 @@ impl Generator {
                     out += "  idiv rcx\n";
                     out += &(format!("  mov {}, rdx\n", gr(dst)).as_str());
                 }
+                x64::IR::UMODIMM(dst, value) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += "  xor rdx, rdx\n";
+                    out += &format!("  mov rcx, {}\n", value);
+                    out += "  div rcx\n";
+                    out += &format!("  mov {}, rdx\n", gr(dst));
+                }
                 x64::IR::MODMEM(dst, offset) => {
                     out += &(format!("  mov rax, {}\n", gr(dst)).as_str());
                     out += "  cqo\n";
                     out += &(format!("  idiv -{}[rbp]\n", offset).as_str());
                     out += &(format!("  mov {}, rdx\n", gr(dst)).as_str());
                 }
+                x64::IR::UMODMEM(dst, offset) => {
+                    out += &format!("  mov rax, {}\n", gr(dst));
+                    out += "  xor rdx, rdx\n";
+                    out += &format!("  div -{}[rbp]\n", offset);
+                    out += &format!("  mov {}, rdx\n", gr(dst));
+                }
                 x64::IR::LSHIFTREG(dst, src) => {
                     out += "  push rcx\n";
                     out += &(format!("  mov rcx, {}\n", gr(src)).as_str());
@@ -1126,66 +2187,168 @@ impl Generator {
                     out += &(format!("  sar {}, cl\n", gr(dst)).as_str());
                     out += "  pop rcx\n";
                 }
+                x64::IR::URSHIFTREG(dst, src) => {
+                    out += "  push rcx\n";
+                    out += &format!("  mov rcx, {}\n", gr(src));
+                    out += &format!("  shr {}, cl\n", gr(dst));
+                    out += "  pop rcx\n";
+                }
+                x64::IR::URSHIFTIMM(dst, value) => {
+                    out += &format!("  shr {}, {}\n", gr(dst), value);
+                }
+                x64::IR::URSHIFTMEM(dst, offset) => {
+                    out += "  push rcx\n";
+                    out += &format!("  mov rcx, -{}[rbp]\n", offset);
+                    out += &format!("  shr {}, cl\n", gr(dst));
+                    out += "  pop rcx\n";
+                }
+                x64::IR::ANDREG(dst, src) => {
+                    out += &format!("  and {}, {}\n", gr(dst), gr(src));
+                }
+                x64::IR::ANDIMM(dst, value) => {
+                    out += &format!("  and {}, {}\n", gr(dst), value);
+                }
+                x64::IR::ANDMEM(dst, offset) => {
+                    out += &format!("  and {}, -{}[rbp]\n", gr(dst), offset);
+                }
+                x64::IR::ORREG(dst, src) => {
+                    out += &format!("  or {}, {}\n", gr(dst), gr(src));
+                }
+                x64::IR::ORIMM(dst, value) => {
+                    out += &format!("  or {}, {}\n", gr(dst), value);
+                }
+                x64::IR::ORMEM(dst, offset) => {
+                    out += &format!("  or {}, -{}[rbp]\n", gr(dst), offset);
+                }
+                x64::IR::XORREG(dst, src) => {
+                    out += &format!("  xor {}, {}\n", gr(dst), gr(src));
+                }
+                x64::IR::XORIMM(dst, value) => {
+                    out += &format!("  xor {}, {}\n", gr(dst), value);
+                }
+                x64::IR::XORMEM(dst, offset) => {
+                    out += &format!("  xor {}, -{}[rbp]\n", gr(dst), offset);
+                }
                 x64::IR::LTREG(dst, src) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), gr(src)).as_str());
                     out += "  setl al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTREG(dst, src) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), gr(src));
+                    out += "  setb al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::LTIMM(dst, value) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), value).as_str());
                     out += "  setl al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTIMM(dst, value) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), value);
+                    out += "  setb al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::LTMEM(dst, offset) => {
                     out += &(format!("  cmp {}, -{}[rbp]\n", gr(dst), offset).as_str());
                     out += "  setl al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTMEM(dst, offset) => {
+                    out += &format!("  cmp {}, -{}[rbp]\n", gr(dst), offset);
+                    out += "  setb al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTREG(dst, src) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), gr(src)).as_str());
                     out += "  setg al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTREG(dst, src) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), gr(src));
+                    out += "  seta al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTIMM(dst, value) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), value).as_str());
                     out += "  setg al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTIMM(dst, value) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), value);
+                    out += "  seta al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTMEM(dst, offset) => {
                     out += &(format!("  cmp {}, -{}[rbp]\n", gr(dst), offset).as_str());
                     out += "  setg al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTMEM(dst, offset) => {
+                    out += &format!("  cmp {}, -{}[rbp]\n", gr(dst), offset);
+                    out += "  seta al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::LTEQREG(dst, src) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), gr(src)).as_str());
                     out += "  setle al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTEQREG(dst, src) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), gr(src));
+                    out += "  setbe al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::LTEQIMM(dst, value) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), value).as_str());
                     out += "  setle al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTEQIMM(dst, value) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), value);
+                    out += "  setbe al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::LTEQMEM(dst, offset) => {
                     out += &(format!("  cmp {}, -{}[rbp]\n", gr(dst), offset).as_str());
                     out += "  setle al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::ULTEQMEM(dst, offset) => {
+                    out += &format!("  cmp {}, -{}[rbp]\n", gr(dst), offset);
+                    out += "  setbe al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTEQREG(dst, src) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), gr(src)).as_str());
                     out += "  setge al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTEQREG(dst, src) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), gr(src));
+                    out += "  setae al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTEQIMM(dst, value) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), value).as_str());
                     out += "  setge al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTEQIMM(dst, value) => {
+                    out += &format!("  cmp {}, {}\n", gr(dst), value);
+                    out += "  setae al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::GTEQMEM(dst, offset) => {
                     out += &(format!("  cmp {}, -{}[rbp]\n", gr(dst), offset).as_str());
                     out += "  setge al\n";
                     out += &(format!("  movzx {}, al\n", gr(dst)).as_str());
                 }
+                x64::IR::UGTEQMEM(dst, offset) => {
+                    out += &format!("  cmp {}, -{}[rbp]\n", gr(dst), offset);
+                    out += "  setae al\n";
+                    out += &format!("  movzx {}, al\n", gr(dst));
+                }
                 x64::IR::EQREG(dst, src) => {
                     out += &(format!("  cmp {}, {}\n", gr(dst), gr(src)).as_str());
                     out += "  sete al\n";
@@ -1219,14 +2382,46 @@ impl Generator {
                 x64::IR::NEGREG(r) => {
                     out += &(format!("  neg {}\n", gr(r)));
                 }
+                x64::IR::NOTREG(r) => {
+                    out += &format!("  not {}\n", gr(r));
+                }
                 x64::IR::ADDRESSMEM(r, offset) => {
                     out += &(format!("  lea {}, -{}[rbp]\n", gr(r), offset).as_str());
                 }
+                x64::IR::ADDRESSINDEX(r, offset) => {
+                    out += &format!("  add {}, rbp\n", gr(r));
+                    out += &format!("  sub {}, {}\n", gr(r), offset);
+                }
                 x64::IR::DEREFREG(r) => {
                     out += &(format!("  mov {}, [{}]\n", gr(r), gr(r)).as_str());
                 }
+                x64::IR::DEREFSIZED(r, size, signed) => {
+                    if *signed {
+                        let inst_name: &str = if *size == 4 { "movsxd" } else { "movsx" };
+                        out += &format!(
+                            "  {} {}, {} PTR [{}]\n",
+                            inst_name,
+                            gr(r),
+                            ptr_size(*size),
+                            gr(r)
+                        );
+                    } else if *size == 4 {
+                        out += &format!("  mov {}, DWORD PTR [{}]\n", sized_gr(r, 4), gr(r));
+                    } else {
+                        out += &format!("  movzx {}, {} PTR [{}]\n", gr(r), ptr_size(*size), gr(r));
+                    }
+                }
+                x64::IR::LOADADDR(r, name) => {
+                    out += &format!("  lea {}, {}\n", gr(r), name);
+                }
                 x64::IR::CALL(name) => {
-                    out += &(format!("  call {}\n", name).as_str());
+                    out += &call_site(name);
+                    unsafe {
+                        ARGREG = 0;
+                    };
+                }
+                x64::IR::CALLREG(r) => {
+                    out += &format!("  call {}\n", gr(r));
                     unsafe {
                         ARGREG = 0;
                     };
@@ -1258,7 +2453,7 @@ impl Generator {
                     out += "  ret\n";
                 }
                 x64::IR::RETURNCALL(symbol) => {
-                    out += &(format!("  call {}\n", symbol).as_str());
+                    out += &call_site(symbol);
                     out += "  mov rsp, rbp\n";
                     out += "  pop rbp\n";
                     out += "  ret\n";
@@ -1266,14 +2461,52 @@ impl Generator {
                 x64::IR::LOADMEM(r, offset) => {
                     out += &(format!("  mov {}, -{}[rbp]\n", gr(r), offset).as_str());
                 }
+                x64::IR::LOADMEMSX(r, offset, size) => {
+                    let inst_name: &str = if *size == 4 { "movsxd" } else { "movsx" };
+                    out += &format!(
+                        "  {} {}, {} PTR -{}[rbp]\n",
+                        inst_name,
+                        gr(r),
+                        ptr_size(*size),
+                        offset
+                    );
+                }
+                x64::IR::LOADMEMZX(r, offset, size) => {
+                    /* writing a 32bit register clears the upper half. */
+                    if *size == 4 {
+                        out += &format!("  mov {}, DWORD PTR -{}[rbp]\n", sized_gr(r, 4), offset);
+                    } else {
+                        out += &format!(
+                            "  movzx {}, {} PTR -{}[rbp]\n",
+                            gr(r),
+                            ptr_size(*size),
+                            offset
+                        );
+                    }
+                }
                 x64::IR::LOADREG(r, r2) => {
                     out += &(format!("  mov {}, {}\n", gr(r), gr(r2)).as_str());
                 }
+                x64::IR::LOADGLOBAL(r, name) => {
+                    out += &format!("  mov {}, QWORD PTR {}\n", gr(r), name);
+                }
+                x64::IR::STOREGLOBAL(name, r) => {
+                    out += &format!("  mov QWORD PTR {}, {}\n", name, gr(r));
+                }
                 x64::IR::REGIMM(r, value) => {
                     out += &(format!("  mov {}, {}\n", gr(r), value).as_str());
                 }
-                x64::IR::PUSHARG(r, offset) => {
-                    out += &(format!("  mov -{}[rbp], {}\n", offset, argr(*r)).as_str());
+                x64::IR::PUSHARG(r, offset, size) => {
+                    if *size == 8 {
+                        out += &(format!("  mov -{}[rbp], {}\n", offset, argr(*r)).as_str());
+                    } else {
+                        out += &format!(
+                            "  mov {} PTR -{}[rbp], {}\n",
+                            ptr_size(*size),
+                            offset,
+                            sized_argr(*r, *size)
+                        );
+                    }
                 }
                 x64::IR::ARGREG(r, r2) => {
                     out += &(format!("  mov {}, {}\n", argr(*r), gr(r2)));
@@ -1,202 +1,162 @@
-use super::Optimizer;
+use super::{BasicBlock, ControlFlowGraph, Optimizer};
 use crate::compile::ir::tac::{Operand, Tac};
 
 use std::collections::{BTreeMap, BTreeSet};
 
 impl Optimizer {
+    /* a basic block starts at a label, a function, or just after a jump and 'ret'. */
     pub fn build_cfg(&mut self) {
+        let mut cfg: ControlFlowGraph = ControlFlowGraph::new();
+        for (n, t) in self.tacs.iter().enumerate() {
+            let leader: bool = match t {
+                Tac::LABEL(_) | Tac::FUNCNAME(_) => true,
+                _ => n == 0 || self.check_jump(n - 1),
+            };
+            if leader {
+                cfg.blocks.push(BasicBlock::new(n));
+            }
+            if let Some(block) = cfg.blocks.last_mut() {
+                block.last = n;
+            }
+            cfg.block_of.push(cfg.blocks.len() - 1);
+        }
+        let len: usize = cfg.blocks.len();
+        cfg.succ = vec![BTreeSet::new(); len];
+        cfg.pred = vec![BTreeSet::new(); len];
+        cfg.used = vec![BTreeSet::new(); len];
+        cfg.def = vec![BTreeSet::new(); len];
+
         let label_map: BTreeMap<String, usize> = self.build_labelmap();
-        let tacs = self.tacs.clone();
-        for (n, t) in tacs.iter().enumerate() {
-            match t {
-                Tac::EX(_lv, _, _lop, _rop) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
-                Tac::UNEX(_lv, _, _op) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
-                Tac::PUSHARG(_, _) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
-                Tac::PARAM(_, _op) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
-                Tac::LET(_lv, _op) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
-                Tac::RET(_op) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                }
+        for b in 0..len {
+            let last: usize = cfg.blocks[b].last;
+            /* the next function doesn't follow the block */
+            let falls: bool = b + 1 < len && !self.check_funcname(cfg.blocks[b + 1].leader);
+            match &self.tacs[last] {
                 Tac::GOTO(label) => {
-                    self.add_pred(n, n - 1);
                     if let Some(goto) = label_map.get(label) {
-                        self.add_succ(n, *goto);
-                        self.add_pred(*goto, n);
+                        cfg.connect(b, cfg.block_of[*goto]);
                     }
                 }
                 Tac::IFF(_op, label) => {
-                    self.add_pred(n, n - 1);
-                    self.add_succ(n, n + 1);
-                    if let Some(goto) = label_map.get(label) {
-                        self.add_succ(n, *goto);
-                        self.add_pred(*goto, n);
-                    }
-                }
-                Tac::FUNCNAME(_) => {
-                    if n != 0 && !self.check_goto(n - 1) {
-                        self.add_pred(n, n - 1);
+                    if falls {
+                        cfg.connect(b, b + 1);
                     }
-                    self.add_succ(n, n + 1);
-                }
-                Tac::PROLOGUE(_) => {
-                    if n != 0 && !self.check_goto(n - 1) {
-                        self.add_pred(n, n - 1);
+                    if let Some(goto) = label_map.get(label) {
+                        cfg.connect(b, cfg.block_of[*goto]);
                     }
-                    self.add_succ(n, n + 1);
                 }
-                Tac::LABEL(_) => {
-                    if n != 0 && !self.check_goto(n - 1) {
-                        self.add_pred(n, n - 1);
+                Tac::RET(_op) => {}
+                _ => {
+                    if falls {
+                        cfg.connect(b, b + 1);
                     }
-                    self.add_succ(n, n + 1);
                 }
             }
         }
+        self.cfg = cfg;
     }
+    /* gen() and kill() of each block, the definition at n is INTLIT(n). */
     pub fn build_cfg_for_reaching(&mut self) {
-        self.cfg.used = vec![BTreeSet::new(); self.tacs.len()]; // gen()
-        self.cfg.def = vec![BTreeSet::new(); self.tacs.len()]; // kill()
+        let definitions: BTreeMap<Operand, BTreeSet<Operand>> = self.definitions();
+        for b in 0..self.cfg.blocks.len() {
+            let mut gen: BTreeSet<Operand> = BTreeSet::new();
+            let mut kill: BTreeSet<Operand> = BTreeSet::new();
+            for n in self.cfg.blocks[b].range() {
+                let (tac_gen, tac_kill) = self.reaching_gen_kill(n, &definitions);
+                gen = &(&gen - &tac_kill) | &tac_gen;
+                kill = &kill | &tac_kill;
+            }
+            self.cfg.used[b] = gen;
+            self.cfg.def[b] = kill;
+        }
+    }
+    /* the values used before defined in each block, and defined in it. */
+    pub fn build_cfg_for_liveness(&mut self) {
+        self.living = BTreeMap::new();
+        for t in self.tacs.iter() {
+            if let Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) | Tac::LET(lv, _) = t {
+                self.living.insert(lv.clone(), (0, 0));
+            }
+        }
+        for b in 0..self.cfg.blocks.len() {
+            let mut used: BTreeSet<Operand> = BTreeSet::new();
+            let mut def: BTreeSet<Operand> = BTreeSet::new();
+            for n in self.cfg.blocks[b].range().rev() {
+                let (tac_used, tac_def) = self.liveness_use_def(&self.tacs[n]);
+                used = &(&used - &tac_def) | &tac_used;
+                def = &def | &tac_def;
+            }
+            self.cfg.used[b] = used;
+            self.cfg.def[b] = def;
+        }
+    }
+    /* the definitions of each value */
+    pub fn definitions(&self) -> BTreeMap<Operand, BTreeSet<Operand>> {
         let mut var_map: BTreeMap<Operand, BTreeSet<Operand>> = BTreeMap::new();
-        let tacs = self.tacs.clone();
-        for (n, t) in tacs.iter().enumerate() {
-            match t {
-                Tac::EX(lv, _, _lop, _rop) => {
-                    if let Some(set) = var_map.get_mut(lv) {
-                        set.insert(Operand::INTLIT(n as i128));
-                    } else {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        var_map.insert(lv.clone(), tmp_set);
-                    }
-                }
-                Tac::UNEX(lv, _, _op) => {
-                    if let Some(set) = var_map.get_mut(lv) {
-                        set.insert(Operand::INTLIT(n as i128));
-                    } else {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        var_map.insert(lv.clone(), tmp_set);
-                    }
-                }
-                Tac::LET(lv, _op) => {
-                    if let Some(set) = var_map.get_mut(lv) {
-                        set.insert(Operand::INTLIT(n as i128));
-                    } else {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        var_map.insert(lv.clone(), tmp_set);
-                    }
-                }
-                _ => {}
+        for (n, t) in self.tacs.iter().enumerate() {
+            if let Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) | Tac::LET(lv, _) = t {
+                var_map
+                    .entry(lv.clone())
+                    .or_default()
+                    .insert(Operand::INTLIT(n as i128));
             }
         }
-        for (n, t) in tacs.iter().enumerate() {
-            match t {
-                Tac::EX(lv, _, _lop, _rop) => {
-                    self.cfg.used[n].insert(Operand::INTLIT(n as i128));
-                    if let Some(set) = var_map.get(lv) {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        self.cfg.def[n] = set - &tmp_set;
-                    }
-                }
-                Tac::UNEX(lv, _, _op) => {
-                    self.cfg.used[n].insert(Operand::INTLIT(n as i128));
-                    if let Some(set) = var_map.get(lv) {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        self.cfg.def[n] = set - &tmp_set;
-                    }
-                }
-                Tac::PUSHARG(_, _) => {}
-                Tac::PARAM(_, _op) => {}
-                Tac::LET(lv, _op) => {
-                    self.cfg.used[n].insert(Operand::INTLIT(n as i128));
-                    if let Some(set) = var_map.get(lv) {
-                        let mut tmp_set: BTreeSet<Operand> = BTreeSet::new();
-                        tmp_set.insert(Operand::INTLIT(n as i128));
-                        self.cfg.def[n] = set - &tmp_set;
-                    }
-                }
-                Tac::RET(_op) => {}
-                Tac::GOTO(_label) => {}
-                Tac::IFF(_op, _label) => {}
-                Tac::FUNCNAME(_) => {}
-                Tac::PROLOGUE(_) => {}
-                Tac::LABEL(_) => {}
+        var_map
+    }
+    /* the tac at n defines itself and kills the other definitions of the value */
+    pub fn reaching_gen_kill(
+        &self,
+        n: usize,
+        definitions: &BTreeMap<Operand, BTreeSet<Operand>>,
+    ) -> (BTreeSet<Operand>, BTreeSet<Operand>) {
+        let mut gen: BTreeSet<Operand> = BTreeSet::new();
+        let mut kill: BTreeSet<Operand> = BTreeSet::new();
+        if let Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) | Tac::LET(lv, _) = &self.tacs[n] {
+            gen.insert(Operand::INTLIT(n as i128));
+            if let Some(set) = definitions.get(lv) {
+                kill = set - &gen;
             }
         }
+        (gen, kill)
     }
-    pub fn build_cfg_for_liveness(&mut self) {
-        self.cfg.used = vec![BTreeSet::new(); self.tacs.len()];
-        self.cfg.def = vec![BTreeSet::new(); self.tacs.len()];
-        let tacs = self.tacs.clone();
-        for (n, t) in tacs.iter().enumerate() {
-            match t {
-                Tac::EX(lv, _, lop, rop) => {
-                    self.cfg.def[n].insert(lv.clone());
-                    if self.check_use_value(&lop) {
-                        self.cfg.used[n].insert(lop.clone());
-                    }
-                    if self.check_use_value(&rop) {
-                        self.cfg.used[n].insert(rop.clone());
-                    }
-                    self.living.insert(lv.clone(), (0, 0));
-                }
-                Tac::UNEX(lv, _, op) => {
-                    self.cfg.def[n].insert(lv.clone());
-                    if self.check_use_value(&op) {
-                        self.cfg.used[n].insert(op.clone());
-                    }
-                    self.living.insert(lv.clone(), (0, 0));
-                }
-                Tac::PUSHARG(_, _) => {}
-                Tac::PARAM(_, op) => {
-                    if self.check_use_value(&op) {
-                        self.cfg.used[n].insert(op.clone());
-                    }
-                }
-                Tac::LET(lv, op) => {
-                    self.cfg.def[n].insert(lv.clone());
-                    if self.check_use_value(&op) {
-                        self.cfg.used[n].insert(op.clone());
-                    }
-                    self.living.insert(lv.clone(), (0, 0));
-                }
-                Tac::RET(op) => {
-                    if self.check_use_value(&op) {
-                        self.cfg.used[n].insert(op.clone());
-                    }
-                }
-                Tac::GOTO(_label) => {}
-                Tac::IFF(op, _label) => {
-                    if self.check_use_value(&op) {
-                        self.cfg.used[n].insert(op.clone());
-                    }
-                }
-                Tac::FUNCNAME(_) => {}
-                Tac::PROLOGUE(_) => {}
-                Tac::LABEL(_) => {}
+    pub fn liveness_use_def(&self, t: &Tac) -> (BTreeSet<Operand>, BTreeSet<Operand>) {
+        let mut used: BTreeSet<Operand> = BTreeSet::new();
+        let mut def: BTreeSet<Operand> = BTreeSet::new();
+        match t {
+            Tac::EX(lv, _, lop, rop) => {
+                def.insert(lv.clone());
+                self.insert_index_use(lv, &mut used);
+                self.insert_use(lop, &mut used);
+                self.insert_use(rop, &mut used);
+            }
+            Tac::UNEX(lv, _, op) | Tac::LET(lv, op) => {
+                def.insert(lv.clone());
+                self.insert_index_use(lv, &mut used);
+                self.insert_use(op, &mut used);
             }
+            Tac::PARAM(_, op) | Tac::RET(op) | Tac::IFF(op, _) => {
+                self.insert_use(op, &mut used);
+            }
+            _ => {}
+        }
+        (used, def)
+    }
+    /* the tacs executed just before the one at n */
+    pub fn tac_preds(&self, n: usize) -> Vec<usize> {
+        let b: usize = self.cfg.block_of[n];
+        if self.cfg.blocks[b].leader != n {
+            return vec![n - 1];
         }
+        self.cfg.pred[b]
+            .iter()
+            .map(|p| self.cfg.blocks[*p].last)
+            .collect()
+    }
+    /* the block starting the function */
+    pub fn entry_block(&self, b: usize) -> bool {
+        self.check_funcname(self.cfg.blocks[b].leader)
     }
-    fn build_labelmap(&self) -> BTreeMap<String, usize> {
+    pub(super) fn build_labelmap(&self) -> BTreeMap<String, usize> {
         let mut map: BTreeMap<String, usize> = BTreeMap::new();
         for (idx, t) in self.tacs.iter().enumerate() {
             if let Tac::LABEL(name) = t {
@@ -205,26 +165,30 @@ impl Optimizer {
         }
         map
     }
+    /* the operand and the values indexing it are read */
+    fn insert_use(&self, op: &Operand, used: &mut BTreeSet<Operand>) {
+        if self.check_use_value(op) {
+            used.insert(op.clone());
+        }
+        self.insert_index_use(op, used);
+    }
+    fn insert_index_use(&self, op: &Operand, used: &mut BTreeSet<Operand>) {
+        if let Operand::REG(_, _, Some(index), _) | Operand::ID(_, _, Some(index), _, _) = op {
+            self.insert_use(index, used);
+        }
+    }
     fn check_use_value(&self, op: &Operand) -> bool {
         match op {
             Operand::REG(_, _, _, _) => true,
-            Operand::ID(_, _, _, _) => true,
+            Operand::ID(_, _, _, _, _) => true,
+            Operand::GLOBAL(_) => true,
             _ => false,
         }
     }
-    fn check_goto(&self, n: usize) -> bool {
-        match self.tacs[n] {
-            Tac::IFF(_, _) => true,
-            Tac::GOTO(_) => true,
-            _ => false,
-        }
-    }
-    fn add_succ(&mut self, n: usize, edge: usize) {
-        if edge < self.tacs.len() {
-            self.cfg.succ[n].insert(edge);
-        }
+    fn check_jump(&self, n: usize) -> bool {
+        matches!(self.tacs[n], Tac::GOTO(_) | Tac::IFF(_, _) | Tac::RET(_))
     }
-    fn add_pred(&mut self, n: usize, edge: usize) {
-        self.cfg.pred[n].insert(edge);
+    fn check_funcname(&self, n: usize) -> bool {
+        matches!(self.tacs[n], Tac::FUNCNAME(_))
     }
 }
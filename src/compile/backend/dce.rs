@@ -0,0 +1,162 @@
+use super::Optimizer;
+use crate::compile::ir::tac::{Operand, Tac};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+impl Optimizer {
+    /* remove the jumps to the jumps, the tacs never executed and the results never used,
+    until nothing changes. */
+    pub fn dead_code_elimination(&mut self) {
+        loop {
+            let collapsed: bool = self.collapse_jumps();
+            let removed: bool = self.remove_unreachable();
+            self.build_cfg_for_liveness();
+            self.liveness();
+            let eliminated: bool = self.remove_dead_definitions();
+            if !collapsed && !removed && !eliminated {
+                break;
+            }
+        }
+    }
+    /* 'goto .L1' to '.L1: goto .L2' jumps to '.L2' directly, the jump to the next tac is dropped. */
+    fn collapse_jumps(&mut self) -> bool {
+        let label_map: BTreeMap<String, usize> = self.build_labelmap();
+        let mut tacs: Vec<Tac> = Vec::new();
+        for (n, t) in self.tacs.iter().enumerate() {
+            match t {
+                Tac::GOTO(label) => {
+                    let dest: String = self.final_destination(label, &label_map);
+                    if !self.falls_into(n, &dest) {
+                        tacs.push(Tac::GOTO(dest));
+                    }
+                }
+                Tac::IFF(cond, label) => {
+                    let dest: String = self.final_destination(label, &label_map);
+                    tacs.push(Tac::IFF(cond.clone(), dest));
+                }
+                _ => tacs.push(t.clone()),
+            }
+        }
+        let collapsed: bool = tacs.len() != self.tacs.len()
+            || tacs
+                .iter()
+                .zip(self.tacs.iter())
+                .any(|(new, old)| new.string() != old.string());
+        if collapsed {
+            self.tacs = tacs;
+            self.build_cfg();
+        }
+        collapsed
+    }
+    /* the blocks not reached from the entry of the function */
+    fn remove_unreachable(&mut self) -> bool {
+        let mut reached_blocks: BTreeSet<usize> = BTreeSet::new();
+        let mut stack: Vec<usize> = (0..self.cfg.blocks.len())
+            .filter(|b| self.entry_block(*b))
+            .collect();
+        while let Some(b) = stack.pop() {
+            if reached_blocks.insert(b) {
+                stack.extend(self.cfg.succ[b].iter());
+            }
+        }
+        let reached: BTreeSet<usize> = reached_blocks
+            .iter()
+            .flat_map(|b| self.cfg.blocks[*b].range())
+            .collect();
+
+        /* the labels nobody jumps to are dropped with them */
+        let mut jumped: BTreeSet<String> = BTreeSet::new();
+        for n in reached.iter() {
+            if let Tac::GOTO(label) | Tac::IFF(_, label) = &self.tacs[*n] {
+                jumped.insert(label.to_string());
+            }
+        }
+        let tacs: Vec<Tac> = self
+            .tacs
+            .iter()
+            .enumerate()
+            .filter(|(n, t)| match t {
+                Tac::LABEL(label) => reached.contains(n) && jumped.contains(label),
+                _ => reached.contains(n),
+            })
+            .map(|(_, t)| t.clone())
+            .collect();
+        if tacs.len() == self.tacs.len() {
+            return false;
+        }
+        self.tacs = tacs;
+        self.build_cfg();
+        true
+    }
+    /* the pure definitions not living after them */
+    fn remove_dead_definitions(&mut self) -> bool {
+        let unsafe_vars: BTreeSet<(String, usize)> = self.unsafe_variables();
+        let len: usize = self.tacs.len();
+        let tacs: Vec<Tac> = self
+            .tacs
+            .iter()
+            .enumerate()
+            .filter(|(n, t)| match dead_candidate(t, &unsafe_vars) {
+                Some(lv) => self.live_out[*n].contains(lv),
+                None => true,
+            })
+            .map(|(_, t)| t.clone())
+            .collect();
+        if tacs.len() == len {
+            return false;
+        }
+        self.tacs = tacs;
+        self.build_cfg();
+        true
+    }
+    /* follow the label followed by 'goto' until the real destination */
+    fn final_destination(&self, label: &str, label_map: &BTreeMap<String, usize>) -> String {
+        let mut dest: String = label.to_string();
+        let mut visited: BTreeSet<String> = BTreeSet::new();
+        while visited.insert(dest.clone()) {
+            let next: Option<&Tac> = label_map.get(&dest).and_then(|idx| {
+                self.tacs[*idx..]
+                    .iter()
+                    .find(|t| !matches!(t, Tac::LABEL(_)))
+            });
+            match next {
+                Some(Tac::GOTO(next_label)) => dest = next_label.to_string(),
+                _ => break,
+            }
+        }
+        dest
+    }
+    /* only the labels are between the jump at 'n' and 'label' */
+    fn falls_into(&self, n: usize, label: &str) -> bool {
+        for t in self.tacs[n + 1..].iter() {
+            match t {
+                Tac::LABEL(name) if name == label => return true,
+                Tac::LABEL(_) => (),
+                _ => return false,
+            }
+        }
+        false
+    }
+}
+
+/* the result of the tac without any side effect */
+fn dead_candidate<'a>(t: &'a Tac, unsafe_vars: &BTreeSet<(String, usize)>) -> Option<&'a Operand> {
+    let (lv, operands): (&Operand, Vec<&Operand>) = match t {
+        Tac::EX(lv, _, lop, rop) => (lv, vec![lop, rop]),
+        Tac::UNEX(_, op, _) if op == "call" => return None,
+        Tac::UNEX(lv, _, op) | Tac::LET(lv, op) => (lv, vec![op]),
+        _ => return None,
+    };
+    if operands.iter().any(|op| matches!(op, Operand::CALL(_, _))) {
+        return None;
+    }
+    match lv {
+        Operand::REG(_, _, None, None) => Some(lv),
+        Operand::ID(name, offset, None, None, _)
+            if !unsafe_vars.contains(&(name.to_string(), *offset)) =>
+        {
+            Some(lv)
+        }
+        _ => None,
+    }
+}
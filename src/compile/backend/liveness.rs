@@ -5,41 +5,47 @@ use std::collections::BTreeSet;
 
 impl Optimizer {
     pub fn liveness(&mut self) {
-        let mut live_in: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
-        let mut live_out: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
+        let len: usize = self.cfg.blocks.len();
+        let mut block_in: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); len];
+        let mut block_out: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); len];
         'outer: loop {
-            let mut in_sets: Vec<BTreeSet<Operand>> = Vec::new();
-            let mut out_sets: Vec<BTreeSet<Operand>> = Vec::new();
-            for (idx, _t) in self.tacs.iter().rev().enumerate() {
-                in_sets.push(live_in[idx].clone());
-                out_sets.push(live_out[idx].clone());
-                for s in self.cfg.succ[idx].iter() {
-                    live_out[idx] = &live_out[idx] | &live_in[*s];
-                }
-                live_in[idx] = &self.cfg.used[idx] | &(&live_out[idx] - &self.cfg.def[idx]);
-            }
-            let mut chg_flg: bool = true;
-            for idx in 0..live_in.len() {
-                if live_in[idx] != in_sets[idx] {
-                    chg_flg = false;
+            let mut chg_flg: bool = false;
+            for b in (0..len).rev() {
+                let mut out_set: BTreeSet<Operand> = BTreeSet::new();
+                for s in self.cfg.succ[b].iter() {
+                    out_set = &out_set | &block_in[*s];
                 }
-                if live_out[idx] != out_sets[idx] {
-                    chg_flg = false;
+                let in_set: BTreeSet<Operand> = &self.cfg.used[b] | &(&out_set - &self.cfg.def[b]);
+                if in_set != block_in[b] || out_set != block_out[b] {
+                    chg_flg = true;
                 }
+                block_in[b] = in_set;
+                block_out[b] = out_set;
             }
-            if chg_flg {
+            if !chg_flg {
                 break 'outer;
             }
         }
+
+        /* the values living at each tac, from the end of its block */
+        let mut live_in: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
+        let mut live_out: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
+        for (b, out_set) in block_out.into_iter().enumerate() {
+            let mut live: BTreeSet<Operand> = out_set;
+            for n in self.cfg.blocks[b].range().rev() {
+                let (used, def) = self.liveness_use_def(&self.tacs[n]);
+                live_in[n] = &used | &(&live - &def);
+                live_out[n] = std::mem::replace(&mut live, live_in[n].clone());
+            }
+        }
+        /* the range covers every tac the value lives at, it may be defined in each branch or live around a loop. */
         for (op, range) in self.living.iter_mut() {
-            for (idx, _t) in self.tacs.iter().enumerate() {
-                if !live_in[idx].contains(op) && live_out[idx].contains(op) {
-                    range.0 = idx;
-                }
-                if live_in[idx].contains(op) && !live_out[idx].contains(op) {
-                    range.1 = idx;
-                }
+            let defined = (0..self.tacs.len()).filter(|idx| live_out[*idx].contains(op));
+            let used = (0..self.tacs.len()).filter(|idx| live_in[*idx].contains(op));
+            if let (Some(start), Some(end)) = (defined.min(), used.max()) {
+                *range = (start, end);
             }
         }
+        self.live_out = live_out;
     }
 }
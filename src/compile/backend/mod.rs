@@ -1,4 +1,5 @@
 use crate::compile::ir::tac::{Operand, Tac};
+use available::Expression;
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
@@ -7,7 +8,9 @@ use std::io::Write;
 pub mod available;
 pub mod codegen;
 pub mod data_flow;
+pub mod dce;
 pub mod liveness;
+pub mod propagation;
 pub mod reaching;
 pub mod regalloc;
 
@@ -15,26 +18,45 @@ pub struct Optimizer {
     pub tacs: Vec<Tac>,
     pub cfg: ControlFlowGraph,
     pub living: BTreeMap<Operand, (usize, usize)>,
+    pub live_out: Vec<BTreeSet<Operand>>,
+    pub reaching: Vec<BTreeSet<Operand>>,
+    pub available: Vec<BTreeSet<Expression>>,
 }
 
 impl Optimizer {
     pub fn new(tac_vec: Vec<Tac>) -> Self {
-        let len: usize = tac_vec.len();
         Self {
             tacs: tac_vec,
-            cfg: ControlFlowGraph::new(len),
+            cfg: ControlFlowGraph::new(),
             living: BTreeMap::new(),
+            live_out: Vec::new(),
+            reaching: Vec::new(),
+            available: Vec::new(),
         }
     }
+    /* a cluster per function, a node per basic block */
     pub fn dump_cfg(&self) {
         let mut out: String = String::new();
         out += "digraph { \n";
-        for (idx, t) in self.tacs.iter().enumerate() {
-            out += &(format!("\t{}[label=\"{}\",shape=\"box\"];\n", idx, t.string()).as_str());
+        for (idx, block) in self.cfg.blocks.iter().enumerate() {
+            if let Tac::FUNCNAME(name) = &self.tacs[block.leader] {
+                if idx != 0 {
+                    out += "\t}\n";
+                }
+                out += &format!("\tsubgraph cluster_{} {{\n\t\tlabel=\"{}\";\n", idx, name);
+            }
+            let label: String = self.tacs[block.leader..=block.last]
+                .iter()
+                .map(|t| format!("{}\\l", t.string()))
+                .collect();
+            out += &format!("\t\t{}[label=\"{}\",shape=\"box\"];\n", idx, label);
+        }
+        if !self.cfg.blocks.is_empty() {
+            out += "\t}\n";
         }
-        for idx in 0..self.tacs.len() {
-            for pred in self.cfg.pred[idx].iter() {
-                out += &(format!("\t{} -> {};\n", pred, idx).as_str());
+        for idx in 0..self.cfg.blocks.len() {
+            for succ in self.cfg.succ[idx].iter() {
+                out += &(format!("\t{} -> {};\n", idx, succ).as_str());
             }
         }
         out += "}";
@@ -47,21 +69,58 @@ impl Optimizer {
             eprintln!("{} --> {}...{}", op.string(), range.0, range.1);
         }
     }
+    pub fn dump_available(&self) {
+        for (idx, exprs) in self.available.iter().enumerate() {
+            let exprs: Vec<String> = exprs.iter().map(available::expression_string).collect();
+            eprintln!(
+                "{}: {} --> {{{}}}",
+                idx,
+                self.tacs[idx].string(),
+                exprs.join(", ")
+            );
+        }
+    }
+}
+
+/* the tacs from 'leader' to 'last' run in a row */
+pub struct BasicBlock {
+    pub leader: usize,
+    pub last: usize,
+}
+impl BasicBlock {
+    fn new(leader: usize) -> Self {
+        Self {
+            leader,
+            last: leader,
+        }
+    }
+    fn range(&self) -> std::ops::RangeInclusive<usize> {
+        self.leader..=self.last
+    }
 }
 
+/* the graph of the basic blocks, the sets of the dataflow analysis are per block. */
 pub struct ControlFlowGraph {
+    blocks: Vec<BasicBlock>,
+    block_of: Vec<usize>,
     succ: Vec<BTreeSet<usize>>,
     pred: Vec<BTreeSet<usize>>,
     used: Vec<BTreeSet<Operand>>,
     def: Vec<BTreeSet<Operand>>,
 }
 impl ControlFlowGraph {
-    fn new(len: usize) -> Self {
+    fn new() -> Self {
         Self {
-            succ: vec![BTreeSet::new(); len],
-            pred: vec![BTreeSet::new(); len],
-            used: vec![BTreeSet::new(); len],
-            def: vec![BTreeSet::new(); len],
+            blocks: Vec::new(),
+            block_of: Vec::new(),
+            succ: Vec::new(),
+            pred: Vec::new(),
+            used: Vec::new(),
+            def: Vec::new(),
         }
     }
+    fn connect(&mut self, from: usize, to: usize) {
+        self.succ[from].insert(to);
+        self.pred[to].insert(from);
+    }
 }
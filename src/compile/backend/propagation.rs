@@ -0,0 +1,309 @@
+use super::Optimizer;
+use crate::compile::ir::tac::{Operand, Tac};
+
+use std::collections::BTreeSet;
+
+impl Optimizer {
+    /* replace the operands with the constants or the copies all the reaching definitions agree,
+    and fold the tacs got the constants, until nothing changes. */
+    pub fn propagation(&mut self) {
+        loop {
+            let propagated: bool = self.propagate();
+            let folded: bool = self.constant_folding();
+            if !propagated && !folded {
+                break;
+            }
+            self.build_cfg_for_reaching();
+            self.reaching_definition();
+        }
+        self.fold_branches();
+    }
+    fn propagate(&mut self) -> bool {
+        let unsafe_vars: BTreeSet<(String, usize)> = self.unsafe_variables();
+        let mut replaces: Vec<(usize, usize, Operand)> = Vec::new();
+        for (n, t) in self.tacs.iter().enumerate() {
+            for (slot, op) in reads(t).into_iter().enumerate() {
+                if !plain(op, &unsafe_vars) {
+                    continue;
+                }
+                let defs: Vec<usize> = self.reaching_defs(n, op);
+                if let Some(value) = self.constant_of(&defs) {
+                    /* the call and the dereference take the register or the memory */
+                    if let Tac::UNEX(_, unop, _) = t {
+                        if unop == "call" || unop.starts_with('*') {
+                            continue;
+                        }
+                    }
+                    replaces.push((n, slot, Operand::INTLIT(value)));
+                } else if let Some(src) = self.copy_of(n, op, &defs, &unsafe_vars) {
+                    replaces.push((n, slot, src));
+                }
+            }
+        }
+        let propagated: bool = !replaces.is_empty();
+        for (n, slot, new_op) in replaces {
+            if let Some(op) = reads_mut(&mut self.tacs[n]).into_iter().nth(slot) {
+                *op = new_op;
+            }
+        }
+        propagated
+    }
+    /* the operands became constants are computed at compile time. */
+    fn constant_folding(&mut self) -> bool {
+        let mut folded: bool = false;
+        for t in self.tacs.iter_mut() {
+            let value: Option<i128> = match t {
+                Tac::EX(_, op, Operand::INTLIT(lval), Operand::INTLIT(rval)) => {
+                    fold_binary(op, *lval as i64, *rval as i64)
+                }
+                Tac::UNEX(_, op, Operand::INTLIT(val)) => fold_unary(op, *val as i64),
+                _ => None,
+            };
+            if let Some(value) = value.filter(|v| fits(*v)) {
+                if let Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) = t {
+                    *t = Tac::LET(lv.clone(), Operand::INTLIT(value));
+                    folded = true;
+                }
+            }
+        }
+        folded
+    }
+    /* the branch with the constant condition is always taken or never. */
+    fn fold_branches(&mut self) {
+        let mut tacs: Vec<Tac> = Vec::new();
+        for t in self.tacs.iter() {
+            match t {
+                Tac::IFF(Operand::INTLIT(0), label) => tacs.push(Tac::GOTO(label.to_string())),
+                Tac::IFF(Operand::INTLIT(_), _) => (),
+                _ => tacs.push(t.clone()),
+            }
+        }
+        if tacs.len() != self.tacs.len() || tacs.iter().zip(self.tacs.iter()).any(is_folded) {
+            self.tacs = tacs;
+            self.build_cfg();
+        }
+    }
+    /* the definitions of 'op' which reach the tac at 'n' */
+    fn reaching_defs(&self, n: usize, op: &Operand) -> Vec<usize> {
+        self.reaching[n]
+            .iter()
+            .filter_map(|def| match def {
+                Operand::INTLIT(d) => Some(*d as usize),
+                _ => None,
+            })
+            .filter(|d| match defined(&self.tacs[*d]) {
+                Some(lv) => same_variable(lv, op),
+                None => false,
+            })
+            .collect()
+    }
+    fn constant_of(&self, defs: &[usize]) -> Option<i128> {
+        let mut value: Option<i128> = None;
+        for d in defs.iter() {
+            match &self.tacs[*d] {
+                Tac::LET(_, Operand::INTLIT(v)) if fits(*v) && value.unwrap_or(*v) == *v => {
+                    value = Some(*v);
+                }
+                _ => return None,
+            }
+        }
+        value
+    }
+    /* 'x <- y' is the only definition and 'y' isn't changed until 'n' */
+    fn copy_of(
+        &self,
+        n: usize,
+        op: &Operand,
+        defs: &[usize],
+        unsafe_vars: &BTreeSet<(String, usize)>,
+    ) -> Option<Operand> {
+        if defs.len() != 1 {
+            return None;
+        }
+        match &self.tacs[defs[0]] {
+            /* the registers are overwritten by the calls and the operations using them */
+            Tac::LET(_, src @ Operand::ID(_, _, _, _, _))
+                if plain(src, unsafe_vars) && !same_variable(src, op) =>
+            {
+                if self.reaching_defs(defs[0], src) == self.reaching_defs(n, src) {
+                    return Some(src.clone());
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+    /* the variables changed out of the definitions or without them. */
+    pub fn unsafe_variables(&self) -> BTreeSet<(String, usize)> {
+        let mut vars: BTreeSet<(String, usize)> = BTreeSet::new();
+        let mut params: BTreeSet<usize> = BTreeSet::new();
+        for t in self.tacs.iter() {
+            let mut ops: Vec<&Operand> = uses(t);
+            match t {
+                Tac::PUSHARG(_, offset, _) => {
+                    params.insert(*offset);
+                }
+                /* the address is taken */
+                Tac::UNEX(_, op, Operand::ID(name, offset, _, _, _)) if op == "&" => {
+                    vars.insert((name.to_string(), *offset));
+                }
+                _ => (),
+            }
+            if let Some(lv) = defined(t) {
+                ops.push(lv);
+            }
+            for op in ops {
+                if let Operand::ID(name, offset, oind, omember, (size, _)) = op {
+                    if oind.is_some() || omember.is_some() || *size < 8 {
+                        vars.insert((name.to_string(), *offset));
+                    }
+                }
+            }
+        }
+        for t in self.tacs.iter() {
+            for op in uses(t) {
+                if let Operand::ID(name, offset, _, _, _) = op {
+                    if params.contains(offset) {
+                        vars.insert((name.to_string(), *offset));
+                    }
+                }
+            }
+        }
+        vars
+    }
+}
+
+fn is_folded((new, old): (&Tac, &Tac)) -> bool {
+    matches!((new, old), (Tac::GOTO(_), Tac::IFF(_, _)))
+}
+
+/* the value fits the immediate of the instruction */
+fn fits(value: i128) -> bool {
+    (i32::MIN as i128..=i32::MAX as i128).contains(&value)
+}
+
+fn fold_binary(op: &str, lval: i64, rval: i64) -> Option<i128> {
+    let value: i64 = match op {
+        "+" => lval.wrapping_add(rval),
+        "-" => lval.wrapping_sub(rval),
+        "*" => lval.wrapping_mul(rval),
+        "/" => lval.checked_div(rval)?,
+        "%" => lval.checked_rem(rval)?,
+        "/u" => (lval as u64).checked_div(rval as u64)? as i64,
+        "%u" => (lval as u64).checked_rem(rval as u64)? as i64,
+        "<<" => lval.wrapping_shl(rval as u32),
+        ">>" => lval.wrapping_shr(rval as u32),
+        ">>>" => (lval as u64).wrapping_shr(rval as u32) as i64,
+        "&" => lval & rval,
+        "|" => lval | rval,
+        "^" => lval ^ rval,
+        "<" => (lval < rval) as i64,
+        "<=" => (lval <= rval) as i64,
+        ">" => (lval > rval) as i64,
+        ">=" => (lval >= rval) as i64,
+        "<u" => ((lval as u64) < rval as u64) as i64,
+        "<=u" => (lval as u64 <= rval as u64) as i64,
+        ">u" => (lval as u64 > rval as u64) as i64,
+        ">=u" => (lval as u64 >= rval as u64) as i64,
+        "==" => (lval == rval) as i64,
+        "!=" => (lval != rval) as i64,
+        _ => return None,
+    };
+    Some(value as i128)
+}
+
+fn fold_unary(op: &str, val: i64) -> Option<i128> {
+    let value: i64 = match op {
+        "-" => val.wrapping_neg(),
+        "!" => (val == 0) as i64,
+        "~" => !val,
+        _ => return None,
+    };
+    Some(value as i128)
+}
+
+/* the register or the variable read as a whole */
+fn plain(op: &Operand, unsafe_vars: &BTreeSet<(String, usize)>) -> bool {
+    match op {
+        Operand::REG(_, _, None, None) => true,
+        Operand::ID(name, offset, None, None, _) => {
+            !unsafe_vars.contains(&(name.to_string(), *offset))
+        }
+        _ => false,
+    }
+}
+
+fn same_variable(a: &Operand, b: &Operand) -> bool {
+    match (a, b) {
+        (Operand::REG(v1, _, None, None), Operand::REG(v2, _, None, None)) => v1 == v2,
+        (Operand::ID(n1, o1, None, None, _), Operand::ID(n2, o2, None, None, _)) => {
+            n1 == n2 && o1 == o2
+        }
+        _ => false,
+    }
+}
+
+fn defined(t: &Tac) -> Option<&Operand> {
+    match t {
+        Tac::EX(lv, _, _, _) | Tac::UNEX(lv, _, _) | Tac::LET(lv, _) => Some(lv),
+        _ => None,
+    }
+}
+
+/* the operands read by the tac, the operand of '&' is the address, not the value. */
+fn uses(t: &Tac) -> Vec<&Operand> {
+    match t {
+        Tac::EX(_, _, lop, rop) => vec![lop, rop],
+        Tac::UNEX(_, op, _) if op == "&" => Vec::new(),
+        Tac::UNEX(_, _, op)
+        | Tac::LET(_, op)
+        | Tac::RET(op)
+        | Tac::PARAM(_, op)
+        | Tac::IFF(op, _) => {
+            vec![op]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn uses_mut(t: &mut Tac) -> Vec<&mut Operand> {
+    match t {
+        Tac::EX(_, _, lop, rop) => vec![lop, rop],
+        Tac::UNEX(_, op, _) if op == "&" => Vec::new(),
+        Tac::UNEX(_, _, op)
+        | Tac::LET(_, op)
+        | Tac::RET(op)
+        | Tac::PARAM(_, op)
+        | Tac::IFF(op, _) => {
+            vec![op]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/* the operands read by the tac, the index is read in place of the indexed operand. */
+fn reads(t: &Tac) -> Vec<&Operand> {
+    uses(t).into_iter().map(innermost).collect()
+}
+
+fn reads_mut(t: &mut Tac) -> Vec<&mut Operand> {
+    uses_mut(t).into_iter().map(innermost_mut).collect()
+}
+
+fn innermost(op: &Operand) -> &Operand {
+    match op {
+        Operand::REG(_, _, Some(index), _) | Operand::ID(_, _, Some(index), _, _) => {
+            innermost(index)
+        }
+        _ => op,
+    }
+}
+
+fn innermost_mut(op: &mut Operand) -> &mut Operand {
+    match op {
+        Operand::REG(_, _, Some(index), _) | Operand::ID(_, _, Some(index), _, _) => {
+            innermost_mut(index)
+        }
+        _ => op,
+    }
+}
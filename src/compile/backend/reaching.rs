@@ -1,35 +1,42 @@
 use super::Optimizer;
 use crate::compile::ir::tac::Operand;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 impl Optimizer {
     pub fn reaching_definition(&mut self) {
-        let mut reach_in: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
-        let mut reach_out: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); self.tacs.len()];
+        let len: usize = self.cfg.blocks.len();
+        let mut reach_in: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); len];
+        let mut reach_out: Vec<BTreeSet<Operand>> = vec![BTreeSet::new(); len];
         'outer: loop {
-            let mut in_sets: Vec<BTreeSet<Operand>> = Vec::new();
-            let mut out_sets: Vec<BTreeSet<Operand>> = Vec::new();
-            for (idx, _t) in self.tacs.iter().enumerate() {
-                in_sets.push(reach_in[idx].clone());
-                out_sets.push(reach_out[idx].clone());
-                for s in self.cfg.pred[idx].iter() {
-                    reach_in[idx] = &reach_in[idx] | &reach_out[*s];
+            let mut chg_flg: bool = false;
+            for b in 0..len {
+                let mut in_set: BTreeSet<Operand> = BTreeSet::new();
+                for p in self.cfg.pred[b].iter() {
+                    in_set = &in_set | &reach_out[*p];
                 }
-                reach_out[idx] = &self.cfg.used[idx] | &(&reach_in[idx] - &self.cfg.def[idx]);
-            }
-            let mut chg_flg: bool = true;
-            for idx in 0..reach_in.len() {
-                if reach_in[idx] != in_sets[idx] {
-                    chg_flg = false;
-                }
-                if reach_out[idx] != out_sets[idx] {
-                    chg_flg = false;
+                let out_set: BTreeSet<Operand> = &self.cfg.used[b] | &(&in_set - &self.cfg.def[b]);
+                if in_set != reach_in[b] || out_set != reach_out[b] {
+                    chg_flg = true;
                 }
+                reach_in[b] = in_set;
+                reach_out[b] = out_set;
             }
-            if chg_flg {
+            if !chg_flg {
                 break 'outer;
             }
         }
+
+        /* the definitions reaching each tac, from the entry of its block */
+        let definitions: BTreeMap<Operand, BTreeSet<Operand>> = self.definitions();
+        self.reaching = vec![BTreeSet::new(); self.tacs.len()];
+        for (b, block_in) in reach_in.into_iter().enumerate() {
+            let mut reach: BTreeSet<Operand> = block_in;
+            for n in self.cfg.blocks[b].range() {
+                let (gen, kill) = self.reaching_gen_kill(n, &definitions);
+                let out: BTreeSet<Operand> = &gen | &(&reach - &kill);
+                self.reaching[n] = std::mem::replace(&mut reach, out);
+            }
+        }
     }
 }
@@ -1,185 +1,233 @@
 use super::Optimizer;
 use crate::compile::ir::tac::{Operand, Tac};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 static AVAILABLE_X64: usize = 9;
+/* a spilled value is computed in it before stored, it isn't allocated once a value spills. */
+static SPILL_REG: usize = 5;
 impl Optimizer {
     pub fn regalloc(&mut self) {
+        let (mut reg_map, mut spilled) = self.linear_scan(None);
+        if !spilled.is_empty() {
+            let (retried_map, retried_spilled) = self.linear_scan(Some(SPILL_REG));
+            reg_map = retried_map;
+            spilled = retried_spilled;
+        }
+
+        /* allocating reg into tac*/
+        let mut tacs = self.tacs.clone();
+        for t in tacs.iter_mut() {
+            match t {
+                Tac::EX(lv, _, lop, rop) => {
+                    assign(lop, &reg_map);
+                    assign(rop, &reg_map);
+                    assign(lv, &reg_map);
+                }
+                Tac::UNEX(lv, _, op) | Tac::LET(lv, op) => {
+                    assign(op, &reg_map);
+                    assign(lv, &reg_map);
+                }
+                Tac::RET(op) | Tac::PARAM(_, op) | Tac::IFF(op, _) => {
+                    assign(op, &reg_map);
+                }
+                _ => (),
+            }
+        }
+        self.tacs = tacs;
+        if !spilled.is_empty() {
+            self.spill(&spilled);
+        }
+    }
+    /* returns the register of each value and the values spilled into the stack. */
+    fn linear_scan(
+        &mut self,
+        reserved: Option<usize>,
+    ) -> (BTreeMap<String, usize>, BTreeSet<String>) {
         use std::iter::FromIterator;
         let mut living_list = Vec::from_iter(self.living.clone());
-        let mut living_list_copy = living_list.clone();
         let mut reg_map: BTreeMap<String, usize> = BTreeMap::new();
+        let mut spilled: BTreeSet<String> = BTreeSet::new();
         let mut active_list: Vec<(Operand, (usize, usize))> = Vec::new();
-        let mut registers: Vec<Option<usize>> = (0..9).map(|num| Some(num)).collect();
+        let mut registers: Vec<Option<usize>> = (0..AVAILABLE_X64)
+            .map(|num| Some(num).filter(|n| Some(*n) != reserved))
+            .collect();
         living_list.sort_by(|&(_, r1), &(_, r2)| r1.0.cmp(&r2.0));
+        let pinned: BTreeSet<String> = self.indexed_registers();
         for (var, range) in living_list.iter_mut() {
-            let mut num: usize = 100;
-            if let Some(n) = registers.iter().find(|x| x.is_some()) {
-                num = n.unwrap();
-            }
-            match var {
-                Operand::REG(ref mut _virt, ref mut phys, ref mut _oind, ref mut _omember) => {
-                    if num < AVAILABLE_X64 {
-                        *phys = num;
-                        reg_map.insert(var.string(), num);
-                        registers[num] = None;
-                        for (op, r) in living_list_copy.iter_mut() {
-                            *op = var.clone();
-                            if range.0 <= r.0 && r.1 <= range.1 || range.0 <= r.0 && r.0 <= range.1
-                            {
-                                active_list.push((op.clone(), *range));
+            /* the registers whose ranges ended are free before this one starts. */
+            active_list.retain(|(op, r)| {
+                if r.1 < range.0 {
+                    if let Operand::REG(_, phys, _oind, _omember) = op {
+                        registers[*phys] = Some(*phys);
+                    }
+                    return false;
+                }
+                true
+            });
+            let mut num: Option<usize> = registers.iter().flatten().next().copied();
+            let pinned_var: bool = pinned.contains(&var.string());
+            if let Operand::REG(ref mut _virt, ref mut phys, ref mut _oind, ref mut _omember) = var
+            {
+                if num.is_none() {
+                    /* the active one living the longest gives up its register, or this one spills.
+                    the slot can't be indexed, so the base of an index or a member keeps its register. */
+                    let victim: Option<usize> = active_list.iter().rposition(|(op, r)| {
+                        !pinned.contains(&op.string()) && (r.1 > range.1 || pinned_var)
+                    });
+                    match victim {
+                        Some(idx) => {
+                            let (victim, _) = active_list.remove(idx);
+                            if let Some(victim_phys) = reg_map.remove(&victim.string()) {
+                                spilled.insert(victim.string());
+                                registers[victim_phys] = Some(victim_phys);
+                                num = Some(victim_phys);
                             }
                         }
-                        self.sort_active(&mut active_list);
-                    } else {
-                        eprintln!("spill occured (not implemented)");
+                        None => {
+                            spilled.insert(var.string());
+                            continue;
+                        }
                     }
                 }
-                _ => (),
-            }
-            let remove_list: Vec<usize> = active_list
-                .iter()
-                .filter(|(_op, r)| r.1 < range.0)
-                .enumerate()
-                .map(|(idx, _)| idx)
-                .collect();
-            for i in remove_list.iter() {
-                let mut return_reg: usize = 0;
-                if *i < active_list.len() {
-                    if let Operand::REG(_, phys, _oind, _omember) = &active_list[*i].0 {
-                        return_reg = *phys;
-                    }
-                    active_list.remove(*i);
-                    registers[return_reg] = Some(return_reg);
+                if let Some(num) = num {
+                    *phys = num;
+                    reg_map.insert(var.string(), num);
+                    registers[num] = None;
+                    active_list.push((var.clone(), *range));
+                    self.sort_active(&mut active_list);
                 }
             }
         }
         self.living = living_list
             .into_iter()
             .collect::<BTreeMap<Operand, (usize, usize)>>();
-        /* allocating reg into tac*/
-        let mut tacs = self.tacs.clone();
-        for t in tacs.iter_mut() {
-            match t {
-                Tac::EX(lv, _, lop, rop) => {
-                    let op2 = lop.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = lop
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
-                    let op2 = rop.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = rop
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
-                    let op2 = lv.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = lv
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+        (reg_map, spilled)
+    }
+    /* each spilled value gets a slot below the frame of its function,
+     * it's read from the slot directly and stored there after computed in SPILL_REG. */
+    fn spill(&mut self, spilled: &BTreeSet<String>) {
+        let mut tacs: Vec<Tac> = Vec::new();
+        let mut slots: BTreeMap<String, usize> = BTreeMap::new();
+        let mut prologue: Option<usize> = None;
+        let mut frame_size: usize = 0;
+        for mut t in self.tacs.clone() {
+            let mut store: Option<Tac> = None;
+            match &mut t {
+                Tac::FUNCNAME(_) => {
+                    prologue = None;
+                    slots.clear();
                 }
-                Tac::UNEX(lv, _, op) => {
-                    let op2 = op.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = op
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
-                    let op2 = lv.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = lv
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+                Tac::PROLOGUE(offset) => {
+                    prologue = Some(tacs.len());
+                    frame_size = *offset;
                 }
-                Tac::RET(op) => {
-                    let op2 = op.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = op
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+                Tac::EX(lv, _, lop, rop) => {
+                    reload(lop, spilled, &mut slots, &mut frame_size);
+                    reload(rop, spilled, &mut slots, &mut frame_size);
+                    store = spill_def(lv, spilled, &mut slots, &mut frame_size);
                 }
-                Tac::PARAM(_, op) => {
-                    let op2 = op.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = op
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+                Tac::UNEX(lv, _, op) => {
+                    reload(op, spilled, &mut slots, &mut frame_size);
+                    store = spill_def(lv, spilled, &mut slots, &mut frame_size);
                 }
                 Tac::LET(lv, op) => {
-                    let op2 = op.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = op
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
-                    let op2 = lv.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = lv
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+                    reload(op, spilled, &mut slots, &mut frame_size);
+                    store = spill_def(lv, spilled, &mut slots, &mut frame_size);
                 }
-                Tac::IFF(op, _label) => {
-                    let op2 = op.clone();
-                    if let Operand::REG(
-                        ref mut _virt,
-                        ref mut phys,
-                        ref mut _oind,
-                        ref mut _omember,
-                    ) = op
-                    {
-                        *phys = *reg_map.get(&op2.string()).unwrap();
-                    }
+                Tac::RET(op) | Tac::PARAM(_, op) | Tac::IFF(op, _) => {
+                    reload(op, spilled, &mut slots, &mut frame_size);
                 }
                 _ => (),
             }
+            tacs.push(t);
+            if let Some(store) = store {
+                tacs.push(store);
+            }
+            /* the frame is extended by the slots */
+            if let Some(idx) = prologue {
+                tacs[idx] = Tac::PROLOGUE(frame_size);
+            }
         }
         self.tacs = tacs;
     }
+    /* the registers read through an index or a member */
+    fn indexed_registers(&self) -> BTreeSet<String> {
+        let mut names: BTreeSet<String> = BTreeSet::new();
+        for t in self.tacs.iter() {
+            let ops: Vec<&Operand> = match t {
+                Tac::EX(lv, _, lop, rop) => vec![lv, lop, rop],
+                Tac::UNEX(lv, _, op) | Tac::LET(lv, op) => vec![lv, op],
+                Tac::RET(op) | Tac::PARAM(_, op) | Tac::IFF(op, _) => vec![op],
+                _ => Vec::new(),
+            };
+            for op in ops {
+                if let Operand::REG(_, _, Some(_), _) | Operand::REG(_, _, _, Some(_)) = op {
+                    names.insert(op.string());
+                }
+            }
+        }
+        names
+    }
     fn sort_active(&self, active_list: &mut Vec<(Operand, (usize, usize))>) {
         active_list.sort_by(|&(_, r1), &(_, r2)| r1.1.cmp(&r2.1));
     }
 }
+
+fn assign(op: &mut Operand, reg_map: &BTreeMap<String, usize>) {
+    let name: String = op.string();
+    if let Operand::REG(ref mut _virt, ref mut phys, ref mut _oind, ref mut _omember) = op {
+        if let Some(num) = reg_map.get(&name) {
+            *phys = *num;
+        }
+    }
+    /* the register indexing the operand */
+    if let Operand::REG(_, _, Some(index), _) | Operand::ID(_, _, Some(index), _, _) = op {
+        assign(index, reg_map);
+    }
+}
+
+/* the stack slot of the spilled value, it's allocated at the first appearance. */
+fn slot_of(name: String, slots: &mut BTreeMap<String, usize>, frame_size: &mut usize) -> Operand {
+    let offset: usize = *slots.entry(name.clone()).or_insert_with(|| {
+        *frame_size += 8;
+        *frame_size
+    });
+    Operand::ID(name, offset, None, None, (8, true))
+}
+
+/* a spilled value is read as the memory operand, also where it indexes another one. */
+fn reload(
+    op: &mut Operand,
+    spilled: &BTreeSet<String>,
+    slots: &mut BTreeMap<String, usize>,
+    frame_size: &mut usize,
+) {
+    if let Operand::REG(_, _, None, None) = op {
+        if spilled.contains(&op.string()) {
+            *op = slot_of(op.string(), slots, frame_size);
+        }
+    }
+    if let Operand::REG(_, _, Some(index), _) | Operand::ID(_, _, Some(index), _, _) = op {
+        reload(index, spilled, slots, frame_size);
+    }
+}
+
+/* a spilled value is computed in SPILL_REG, returns the store into its slot. */
+fn spill_def(
+    lv: &mut Operand,
+    spilled: &BTreeSet<String>,
+    slots: &mut BTreeMap<String, usize>,
+    frame_size: &mut usize,
+) -> Option<Tac> {
+    let name: String = lv.string();
+    if !spilled.contains(&name) {
+        return None;
+    }
+    if let Operand::REG(virt, phys, _, _) = lv {
+        *phys = SPILL_REG;
+        let computed: Operand = Operand::REG(*virt, SPILL_REG, None, None);
+        return Some(Tac::LET(slot_of(name, slots, frame_size), computed));
+    }
+    None
+}
@@ -1,33 +1,83 @@
-use crate::ce::types::Error;
+use crate::ce::types::Diagnostics;
 use crate::compile::frontend;
 use crate::compile::ir::tac::Tac;
-use frontend::parse::node::Func;
+use frontend::parse::node::{Func, Global};
 use frontend::sema::semantics::Type;
-use frontend::token::token::Token;
+use frontend::token::token::{Position, Token};
 
 use std::collections::BTreeMap;
 pub struct FrontManager {
     pub functions: Vec<Func>,
+    /* the functions of the imported modules, only their signatures are used */
+    pub imported: Vec<Func>,
+    pub globals: Vec<Global>,
+    pub strings: Vec<String>,
     pub stack_offset: usize,
+    pub stack_peak: usize,
     pub cur_env: Env,
+    pub cur_scopes: Vec<Env>,
     pub tacs: Vec<Tac>,
     pub virt: usize,
     pub label: usize,
+    pub diag: Diagnostics,
+    pub loops: Vec<Loop>,
+    pub warn_shadow: bool,
+    /* the function walked in semantics */
+    pub cur_func: String,
+    pub instances: Vec<Instance>,
 }
 
 impl FrontManager {
-    pub fn new(funcs: Vec<Func>) -> FrontManager {
+    pub fn new(funcs: Vec<Func>, globals: Vec<Global>) -> FrontManager {
         FrontManager {
             functions: funcs,
+            imported: Vec::new(),
+            globals,
+            strings: Vec::new(),
             stack_offset: 0,
+            stack_peak: 0,
             cur_env: Env::new(),
+            cur_scopes: Vec::new(),
             tacs: Vec::new(),
             virt: 0,
             label: 0,
+            diag: Diagnostics::new(),
+            loops: Vec::new(),
+            warn_shadow: false,
+            cur_func: String::new(),
+            instances: Vec::new(),
         }
     }
 }
 
+/* the enclosing loops, the innermost is the last. */
+pub struct Loop {
+    pub name: Option<String>,
+    pub continue_label: String,
+    pub break_label: String,
+}
+impl Loop {
+    pub fn new(name: Option<String>, continue_label: String, break_label: String) -> Self {
+        Self {
+            name,
+            continue_label,
+            break_label,
+        }
+    }
+}
+
+/* a call of a generic function, monomorphize lets it call the instance. */
+pub struct Instance {
+    pub caller: String,
+    pub pos: Position,
+    pub name: String,
+}
+impl Instance {
+    pub fn new(caller: String, pos: Position, name: String) -> Self {
+        Self { caller, pos, name }
+    }
+}
+
 #[derive(Clone)]
 pub struct Env {
     pub sym_table: BTreeMap<String, Symbol>,
@@ -42,6 +92,18 @@ impl Env {
             prev: None,
         }
     }
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        match self.sym_table.get(name) {
+            Some(s) => Some(s),
+            None => self.prev.as_ref().and_then(|env| env.lookup(name)),
+        }
+    }
+    pub fn lookup_type(&self, name: &str) -> Option<&Type> {
+        match self.type_table.get(name) {
+            Some(t) => Some(t),
+            None => self.prev.as_ref().and_then(|env| env.lookup_type(name)),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -49,6 +111,7 @@ pub struct Symbol {
     pub stack_offset: usize,
     pub ty: Result<Type, Token>,
     pub is_mutable: bool,
+    pub is_global: bool,
 }
 
 impl Symbol {
@@ -57,25 +120,31 @@ impl Symbol {
             stack_offset: offset,
             ty: res_ty,
             is_mutable: flg,
+            is_global: false,
         }
     }
     pub fn size(&self) -> usize {
         match &self.ty {
             Ok(ty) => ty.size(),
             Err(ty_t) => match &ty_t {
-                Token::I64 => 8,
-                Token::POINTER(_) => 8,
+                Token::I8
+                | Token::I16
+                | Token::I32
+                | Token::I64
+                | Token::U8
+                | Token::U16
+                | Token::U32
+                | Token::U64
+                | Token::BOOL => Type::from_token(ty_t.clone()).size(),
+                Token::POINTER(_) | Token::FUNCTYPE(_, _) => 8,
                 Token::ARRAY(type_t, array_size) => {
                     if let Token::INTEGER(num) = *array_size.clone() {
                         return Self::new(0, Err(*type_t.clone()), false).size() * num as usize;
                     }
-                    Error::TYPE.found(&"can't known size at compile time".to_string());
-                    0
-                }
-                _ => {
-                    Error::TYPE.found(&"can't known size at compile time".to_string());
                     0
                 }
+                /* the unsized type was already reported where it was written. */
+                _ => 0,
             },
         }
     }
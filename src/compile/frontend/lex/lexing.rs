@@ -1,32 +1,82 @@
-use crate::ce::types::Error;
-use crate::compile::frontend::token::token::Token;
+use crate::ce::types::{Diagnostics, Error};
+use crate::compile::frontend::token::token::{Position, Token};
 use std::collections::HashMap;
 
 type TokenLen = usize;
 
-pub fn lexing(mut input: String) -> Vec<Token> {
-    let mut tokens: Vec<Token> = Vec::with_capacity(2048);
+pub fn lexing(
+    file_name: String,
+    mut input: String,
+    diag: &mut Diagnostics,
+) -> Vec<(Token, Position)> {
+    let mut tokens: Vec<(Token, Position)> = Vec::with_capacity(2048);
 
     /* build all keywords they used in depth. */
     let keywords: HashMap<&str, (Token, usize)> = build_keywords();
 
+    /* keep the whole source to know what each token consumed. */
+    let source: String = input.clone();
+    let mut offset: usize = 0;
+    let mut line: usize = 1;
+    let mut column: usize = 1;
+
     /* append this_token to tokens while given tokens are valid. */
-    while let Some((t, idx)) = tokenize(&mut input, &keywords) {
+    loop {
+        let (t, idx): (Token, TokenLen) = match tokenize(&mut input, &keywords) {
+            Some(result) => result,
+            None => {
+                /* report an unexpected mark then skip it to find the other errors. */
+                if let Some(c) = input.chars().next() {
+                    let pos: Position = Position::new(file_name.to_string(), line, column, 1);
+                    let message: String = match c {
+                        '"' => "unterminated string-literal or unknown escape".to_string(),
+                        '\'' => "char-literal must be a single ascii character".to_string(),
+                        _ => format!("unexpected mark '{}'", c),
+                    };
+                    diag.error(Error::PARSE, &pos, &message);
+                    input.drain(..c.len_utf8());
+                    offset = source.len() - input.len();
+                    column += 1;
+                    continue;
+                }
+                break;
+            }
+        };
+
         /* next point. */
         input.drain(..idx);
 
+        /* tokenize() may drain a prefix by itself, so measure from the rest of input. */
+        let consumed: &str = &source[offset..source.len() - input.len()];
+        let pos: Position = Position::new(
+            file_name.to_string(),
+            line,
+            column,
+            consumed.chars().count(),
+        );
+        for c in consumed.chars() {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        offset = source.len() - input.len();
+
         if t.should_ignore() {
             continue;
         }
 
         /* if this_token is End-Of-File then we should exit from tokenize. */
         if let &Token::EOF = &t {
-            tokens.push(t);
-            break;
+            tokens.push((t, pos));
+            return tokens;
         }
-        tokens.push(t);
+        tokens.push((t, pos));
     }
 
+    tokens.push((Token::EOF, Position::new(file_name, line, column, 1)));
     tokens
 }
 
@@ -48,7 +98,7 @@ fn tokenize(
             Some((Token::INFORMATION(contents), length))
         }
         /* keyword and identifier */
-        c if c.is_alphabetic() => tokenize_keywords(input, keywords),
+        c if c.is_alphabetic() || c == '_' => tokenize_keywords(input, keywords),
 
         c if c == '0' => Some((Token::INTEGER(0), 1)),
 
@@ -61,6 +111,10 @@ fn tokenize(
             ))
         }
 
+        /* string-literal and char-literal */
+        '"' => tokenize_string(input),
+        '\'' => tokenize_char(input),
+
         /* ignore comment or Token::SLASH */
         '/' => {
             if input.as_bytes()[1] as char == '/' {
@@ -76,6 +130,12 @@ fn tokenize(
     }
 }
 fn tokenize_symbols(input: &String) -> Option<(Token, TokenLen)> {
+    /* '<<=' and '>>=' are the only symbols longer than 2 */
+    if let Some(multilength) = input.get(0..3) {
+        if let Some(t) = tokenize_multisymbols(&multilength.to_string()) {
+            return Some((t, 3));
+        }
+    }
     if input.len() >= 2 {
         /* check the symbol has multilength at read-offset */
         let multilength: String = std::str::from_utf8(&input.as_bytes()[0..2]).unwrap().into();
@@ -91,6 +151,10 @@ fn tokenize_symbols(input: &String) -> Option<(Token, TokenLen)> {
         '/' => Some((Token::SLASH, 1)),
         '%' => Some((Token::PERCENT, 1)),
         '&' => Some((Token::AMPERSAND, 1)),
+        '!' => Some((Token::BANG, 1)),
+        '|' => Some((Token::PIPE, 1)),
+        '^' => Some((Token::CARET, 1)),
+        '~' => Some((Token::TILDE, 1)),
         '(' => Some((Token::LPAREN, 1)),
         ')' => Some((Token::RPAREN, 1)),
         '{' => Some((Token::LBRACE, 1)),
@@ -109,12 +173,49 @@ fn tokenize_symbols(input: &String) -> Option<(Token, TokenLen)> {
         '\n' => Some((Token::LF, 1)),
         '\t' => Some((Token::BLANK, 1)),
         '\0' => Some((Token::EOF, 1)),
-        c => {
-            Error::PARSE.found(&format!("unexpected mark '{}'", c));
-            None
+        _ => None,
+    }
+}
+/* returns the escaped character and the length of the sequence. */
+fn escape_sequence(input: &str) -> Option<(char, TokenLen)> {
+    let mut chars = input.chars();
+    match chars.next()? {
+        '\\' => {
+            let c: char = match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '0' => '\0',
+                '\\' => '\\',
+                '"' => '"',
+                '\'' => '\'',
+                _ => return None,
+            };
+            Some((c, 2))
+        }
+        '\n' => None,
+        c => Some((c, c.len_utf8())),
+    }
+}
+fn tokenize_string(input: &str) -> Option<(Token, TokenLen)> {
+    let mut contents: String = String::new();
+    let mut length: TokenLen = 1;
+    loop {
+        if input[length..].starts_with('"') {
+            return Some((Token::STRLIT(contents), length + 1));
         }
+        let (c, len): (char, TokenLen) = escape_sequence(&input[length..])?;
+        contents.push(c);
+        length += len;
     }
 }
+fn tokenize_char(input: &str) -> Option<(Token, TokenLen)> {
+    let (c, len): (char, TokenLen) = escape_sequence(&input[1..])?;
+    if !c.is_ascii() || !input[1 + len..].starts_with('\'') {
+        return None;
+    }
+    Some((Token::CHARLIT(c as u8), len + 2))
+}
 fn tokenize_keywords(
     input: &String,
     keywords: &HashMap<&str, (Token, usize)>,
@@ -137,23 +238,44 @@ fn count_len(input: &String, f: fn(ch: &char) -> bool) -> TokenLen {
 fn tokenize_multisymbols(input: &String) -> Option<Token> {
     match input.as_str() {
         "<<" => Some(Token::LSHIFT),
+        ">>" => Some(Token::RSHIFT),
         "<=" => Some(Token::LTEQ),
         ">=" => Some(Token::GTEQ),
         "==" => Some(Token::EQ),
         "!=" => Some(Token::NTEQ),
         "::" => Some(Token::DOUBLECOLON),
+        "&&" => Some(Token::LOGAND),
+        "||" => Some(Token::LOGOR),
+        ".." => Some(Token::DOTDOT),
+        "=>" => Some(Token::FATARROW),
+        "+=" => Some(Token::ADDASSIGN),
+        "-=" => Some(Token::SUBASSIGN),
+        "*=" => Some(Token::MULASSIGN),
+        "/=" => Some(Token::DIVASSIGN),
+        "%=" => Some(Token::MODASSIGN),
+        "<<=" => Some(Token::LSHIFTASSIGN),
+        ">>=" => Some(Token::RSHIFTASSIGN),
+        "++" => Some(Token::INCREMENT),
+        "--" => Some(Token::DECREMENT),
         _ => None,
     }
 }
 
 fn build_keywords() -> HashMap<&'static str, (Token, usize)> {
-    let mut keywords: HashMap<&str, (Token, usize)> = HashMap::with_capacity(12);
+    let mut keywords: HashMap<&str, (Token, usize)> = HashMap::with_capacity(29);
     keywords.insert("return", (Token::RETURN, 6));
     keywords.insert("if", (Token::IF, 2));
     keywords.insert("else", (Token::ELSE, 4));
     keywords.insert("func", (Token::FUNC, 4));
     keywords.insert("let", (Token::LET, 3));
+    keywords.insert("i8", (Token::I8, 2));
+    keywords.insert("i16", (Token::I16, 3));
+    keywords.insert("i32", (Token::I32, 3));
     keywords.insert("i64", (Token::I64, 3));
+    keywords.insert("u8", (Token::U8, 2));
+    keywords.insert("u16", (Token::U16, 3));
+    keywords.insert("u32", (Token::U32, 3));
+    keywords.insert("u64", (Token::U64, 3));
     keywords.insert("Pointer", (Token::POINTER(Box::new(Token::EOF)), 7));
     keywords.insert("mut", (Token::MUT, 3));
     keywords.insert("goto", (Token::GOTO, 4));
@@ -161,9 +283,25 @@ fn build_keywords() -> HashMap<&'static str, (Token, usize)> {
         "Array",
         (Token::ARRAY(Box::new(Token::EOF), Box::new(Token::EOF)), 5),
     );
+    keywords.insert(
+        "Func",
+        (Token::FUNCTYPE(Vec::new(), Box::new(Token::EOF)), 4),
+    );
     keywords.insert("type", (Token::TYPE, 4));
     keywords.insert("struct", (Token::STRUCT, 6));
+    keywords.insert("import", (Token::IMPORT, 6));
+    keywords.insert("enum", (Token::ENUM, 4));
+    keywords.insert("match", (Token::MATCH, 5));
+    keywords.insert("impl", (Token::IMPL, 4));
     keywords.insert("condloop", (Token::CONDLOOP, 8));
+    keywords.insert("while", (Token::WHILE, 5));
+    keywords.insert("for", (Token::FOR, 3));
+    keywords.insert("in", (Token::IN, 2));
+    keywords.insert("break", (Token::BREAK, 5));
+    keywords.insert("continue", (Token::CONTINUE, 8));
     keywords.insert("compint", (Token::COMPINT, 7));
+    keywords.insert("bool", (Token::BOOL, 4));
+    keywords.insert("true", (Token::TRUE, 4));
+    keywords.insert("false", (Token::FALSE, 5));
     keywords
 }
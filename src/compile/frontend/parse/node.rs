@@ -1,6 +1,7 @@
 use crate::compile::frontend;
 use frontend::frontmanager::frontmanager::Env;
 use frontend::sema::semantics::Type;
+use frontend::token::token::{Position, Token};
 
 use std::collections::BTreeMap;
 
@@ -16,32 +17,55 @@ type Idx = Box<Node>;
 type Condition = Box<Node>;
 type Blk = Box<Node>;
 type Alter = Option<Box<Node>>;
+type Begin = Box<Node>;
+type End = Box<Node>;
+type LoopLabel = Option<String>;
+type Scope = usize;
 type Elements = Box<Vec<Node>>;
+type Arms = Box<Vec<MatchArm>>;
 #[derive(Clone)]
-pub enum Node {
+pub struct Node {
+    pub kind: NodeKind,
+    pub pos: Position,
+}
+#[derive(Clone)]
+pub enum NodeKind {
     /* statement */
     RETURN(Expr),
     LET(Name, Expr),
     ASSIGN(Name, Expr),
-    CONDLOOP(Condition, Blk),
+    /* 'x op= E', the operator is the token of 'op=' */
+    ASSIGNOP(Name, Token, Expr),
+    CONDLOOP(Condition, Blk, LoopLabel),
+    FOR(Name, Begin, End, Blk, LoopLabel),
+    BREAK(LoopLabel),
+    CONTINUE(LoopLabel),
     IF(Condition, Blk, Alter),
-    BLOCK(Elements),
+    BLOCK(Elements, Scope),
+    MATCH(Expr, Arms),
     LABEL(Name),
     GOTO(Name),
 
     /* factor */
     INTEGER(i128),
+    BOOLEAN(bool),
+    STRLIT(String),
     IDENT(Name),
     ARRAYLIT(Elements, Name),
     STRUCTLIT(Name, Box<BTreeMap<String, Node>>),
+    ENUMLIT(Name, Name, Elements, Name),
+    FUNCADDR(Name),
 
     /* unary-operation*/
     ADDRESS(Child),
     DEREFERENCE(Child),
     MINUS(Child),
+    NOT(Child),
+    BITNOT(Child),
     INDEX(Ary, Idx),
     MEMBER(Struct, Name),
     CALL(Name, Elements),
+    CALLPTR(Expr, Elements),
 
     /* binary-operation*/
     ADD(Child, Child),
@@ -57,63 +81,176 @@ pub enum Node {
     GTEQ(Child, Child),
     LSHIFT(Child, Child),
     RSHIFT(Child, Child),
+    BITAND(Child, Child),
+    BITOR(Child, Child),
+    BITXOR(Child, Child),
+    LOGAND(Child, Child),
+    LOGOR(Child, Child),
 
     /* etc */
     DEFARG(Name),
     INVALID,
 }
 impl Node {
+    pub fn new(kind: NodeKind, pos: Position) -> Self {
+        Self { kind, pos }
+    }
+    /* the value stored by 'x op= E', that is 'x op E' */
+    pub fn compound_value(ident: &str, op: &Token, expr: &Node, pos: &Position) -> Node {
+        let lhs: Box<Node> = Box::new(Node::new(NodeKind::IDENT(ident.to_string()), pos.clone()));
+        let rhs: Box<Node> = Box::new(expr.clone());
+        let kind: NodeKind = match op {
+            Token::ADDASSIGN => NodeKind::ADD(lhs, rhs),
+            Token::SUBASSIGN => NodeKind::SUB(lhs, rhs),
+            Token::MULASSIGN => NodeKind::MUL(lhs, rhs),
+            Token::DIVASSIGN => NodeKind::DIV(lhs, rhs),
+            Token::MODASSIGN => NodeKind::MOD(lhs, rhs),
+            Token::LSHIFTASSIGN => NodeKind::LSHIFT(lhs, rhs),
+            _ => NodeKind::RSHIFT(lhs, rhs),
+        };
+        Node::new(kind, pos.clone())
+    }
     pub fn name(&self) -> Option<String> {
-        if let Self::IDENT(name) = self {
+        if let NodeKind::IDENT(name) = &self.kind {
             return Some(name.to_string());
-        } else if let Self::DEFARG(name) = self {
+        } else if let NodeKind::DEFARG(name) = &self.kind {
             return Some(name.to_string());
         }
 
         // unary-operation
-        match self {
-            Self::DEREFERENCE(ch) => ch.name(),
-            Self::ADDRESS(ch) => ch.name(),
-            Self::MINUS(ch) => ch.name(),
+        match &self.kind {
+            NodeKind::DEREFERENCE(ch) => ch.name(),
+            NodeKind::ADDRESS(ch) => ch.name(),
+            NodeKind::MINUS(ch) => ch.name(),
+            NodeKind::NOT(ch) => ch.name(),
+            NodeKind::BITNOT(ch) => ch.name(),
             _ => None,
         }
     }
+    /* the nodes right under this one, to rewrite the tree in place */
+    pub fn children_mut(&mut self) -> Vec<&mut Node> {
+        match &mut self.kind {
+            NodeKind::RETURN(ch)
+            | NodeKind::LET(_, ch)
+            | NodeKind::ASSIGN(_, ch)
+            | NodeKind::ASSIGNOP(_, _, ch)
+            | NodeKind::ADDRESS(ch)
+            | NodeKind::DEREFERENCE(ch)
+            | NodeKind::MINUS(ch)
+            | NodeKind::NOT(ch)
+            | NodeKind::BITNOT(ch)
+            | NodeKind::MEMBER(ch, _) => vec![&mut **ch],
+            NodeKind::CONDLOOP(cond, blk, _) => vec![&mut **cond, &mut **blk],
+            NodeKind::FOR(_, begin, end, blk, _) => vec![&mut **begin, &mut **end, &mut **blk],
+            NodeKind::IF(cond, blk, alter) => {
+                let mut children: Vec<&mut Node> = vec![&mut **cond, &mut **blk];
+                if let Some(alt) = alter {
+                    children.push(&mut **alt);
+                }
+                children
+            }
+            NodeKind::BLOCK(elems, _)
+            | NodeKind::ARRAYLIT(elems, _)
+            | NodeKind::CALL(_, elems)
+            | NodeKind::ENUMLIT(_, _, elems, _) => elems.iter_mut().collect(),
+            NodeKind::CALLPTR(callee, args) => std::iter::once(&mut **callee)
+                .chain(args.iter_mut())
+                .collect(),
+            NodeKind::STRUCTLIT(_, members) => members.values_mut().collect(),
+            NodeKind::MATCH(expr, arms) => std::iter::once(&mut **expr)
+                .chain(arms.iter_mut().map(|arm| &mut arm.body))
+                .collect(),
+            NodeKind::INDEX(lch, rch)
+            | NodeKind::ADD(lch, rch)
+            | NodeKind::SUB(lch, rch)
+            | NodeKind::MUL(lch, rch)
+            | NodeKind::DIV(lch, rch)
+            | NodeKind::MOD(lch, rch)
+            | NodeKind::EQ(lch, rch)
+            | NodeKind::NTEQ(lch, rch)
+            | NodeKind::LT(lch, rch)
+            | NodeKind::GT(lch, rch)
+            | NodeKind::LTEQ(lch, rch)
+            | NodeKind::GTEQ(lch, rch)
+            | NodeKind::LSHIFT(lch, rch)
+            | NodeKind::RSHIFT(lch, rch)
+            | NodeKind::BITAND(lch, rch)
+            | NodeKind::BITOR(lch, rch)
+            | NodeKind::BITXOR(lch, rch)
+            | NodeKind::LOGAND(lch, rch)
+            | NodeKind::LOGOR(lch, rch) => vec![&mut **lch, &mut **rch],
+            _ => Vec::new(),
+        }
+    }
     pub fn string(&self) -> String {
-        match self {
-            Self::ADD(lch, rch) => format!("ADD<{},{}>", lch.string(), rch.string()),
-            Self::SUB(lch, rch) => format!("SUB<{},{}>", lch.string(), rch.string()),
-            Self::MUL(lch, rch) => format!("MUL<{},{}>", lch.string(), rch.string()),
-            Self::DIV(lch, rch) => format!("DIV<{},{}>", lch.string(), rch.string()),
-            Self::MOD(lch, rch) => format!("MOD<{},{}>", lch.string(), rch.string()),
-            Self::NTEQ(lch, rch) => format!("NTEQ<{},{}>", lch.string(), rch.string()),
-            Self::EQ(lch, rch) => format!("EQ<{},{}>", lch.string(), rch.string()),
-            Self::LT(lch, rch) => format!("LT<{},{}>", lch.string(), rch.string()),
-            Self::GT(lch, rch) => format!("GT<{},{}>", lch.string(), rch.string()),
-            Self::LTEQ(lch, rch) => format!("LTEQ<{},{}>", lch.string(), rch.string()),
-            Self::GTEQ(lch, rch) => format!("GTEQ<{},{}>", lch.string(), rch.string()),
-            Self::LSHIFT(lch, rch) => format!("LSHIFT<{},{}>", lch.string(), rch.string()),
-            Self::RSHIFT(lch, rch) => format!("RSHIFT<{},{}>", lch.string(), rch.string()),
-            Self::ADDRESS(ch) => format!("ADDRESS<{}>", ch.string()),
-            Self::DEREFERENCE(ch) => format!("DEREFERENCE<{}>", ch.string()),
-            Self::MINUS(ch) => format!("MINUS<{}>", ch.string()),
-            Self::INTEGER(val) => format!("INTEGER<{}>", val),
-            Self::IDENT(name) => format!("IDENT<{}>", name),
-            Self::INDEX(rec, ind) => format!("INDEX<{},{}>", rec.string(), ind.string()),
-            Self::MEMBER(ident, member) => format!("MEMBER<{}.{}>", ident.string(), member),
-            Self::RETURN(expr) => format!("RETURN({})", expr.string()),
-            Self::LET(ident, expr) => format!("LET<{}>({})", ident, expr.string()),
-            Self::ASSIGN(ident, expr) => format!("ASSIGN<{}>({})", ident, expr.string()),
-            Self::BLOCK(stmts) => format!("BLOCK<{} stmts>", stmts.len()),
-            Self::CALL(ident, _args) => format!("CALL<{}>", ident),
-            Self::ARRAYLIT(elems, _name) => format!("ARRAYLIT<{} elems>", elems.len()),
-            Self::STRUCTLIT(name, members) => {
+        match &self.kind {
+            NodeKind::ADD(lch, rch) => format!("ADD<{},{}>", lch.string(), rch.string()),
+            NodeKind::SUB(lch, rch) => format!("SUB<{},{}>", lch.string(), rch.string()),
+            NodeKind::MUL(lch, rch) => format!("MUL<{},{}>", lch.string(), rch.string()),
+            NodeKind::DIV(lch, rch) => format!("DIV<{},{}>", lch.string(), rch.string()),
+            NodeKind::MOD(lch, rch) => format!("MOD<{},{}>", lch.string(), rch.string()),
+            NodeKind::NTEQ(lch, rch) => format!("NTEQ<{},{}>", lch.string(), rch.string()),
+            NodeKind::EQ(lch, rch) => format!("EQ<{},{}>", lch.string(), rch.string()),
+            NodeKind::LT(lch, rch) => format!("LT<{},{}>", lch.string(), rch.string()),
+            NodeKind::GT(lch, rch) => format!("GT<{},{}>", lch.string(), rch.string()),
+            NodeKind::LTEQ(lch, rch) => format!("LTEQ<{},{}>", lch.string(), rch.string()),
+            NodeKind::GTEQ(lch, rch) => format!("GTEQ<{},{}>", lch.string(), rch.string()),
+            NodeKind::LSHIFT(lch, rch) => format!("LSHIFT<{},{}>", lch.string(), rch.string()),
+            NodeKind::RSHIFT(lch, rch) => format!("RSHIFT<{},{}>", lch.string(), rch.string()),
+            NodeKind::BITAND(lch, rch) => format!("BITAND<{},{}>", lch.string(), rch.string()),
+            NodeKind::BITOR(lch, rch) => format!("BITOR<{},{}>", lch.string(), rch.string()),
+            NodeKind::BITXOR(lch, rch) => format!("BITXOR<{},{}>", lch.string(), rch.string()),
+            NodeKind::LOGAND(lch, rch) => format!("LOGAND<{},{}>", lch.string(), rch.string()),
+            NodeKind::LOGOR(lch, rch) => format!("LOGOR<{},{}>", lch.string(), rch.string()),
+            NodeKind::ADDRESS(ch) => format!("ADDRESS<{}>", ch.string()),
+            NodeKind::DEREFERENCE(ch) => format!("DEREFERENCE<{}>", ch.string()),
+            NodeKind::MINUS(ch) => format!("MINUS<{}>", ch.string()),
+            NodeKind::NOT(ch) => format!("NOT<{}>", ch.string()),
+            NodeKind::BITNOT(ch) => format!("BITNOT<{}>", ch.string()),
+            NodeKind::INTEGER(val) => format!("INTEGER<{}>", val),
+            NodeKind::BOOLEAN(val) => format!("BOOLEAN<{}>", val),
+            NodeKind::STRLIT(contents) => format!("STRLIT<{:?}>", contents),
+            NodeKind::IDENT(name) => format!("IDENT<{}>", name),
+            NodeKind::INDEX(rec, ind) => format!("INDEX<{},{}>", rec.string(), ind.string()),
+            NodeKind::MEMBER(ident, member) => format!("MEMBER<{}.{}>", ident.string(), member),
+            NodeKind::RETURN(expr) => format!("RETURN({})", expr.string()),
+            NodeKind::LET(ident, expr) => format!("LET<{}>({})", ident, expr.string()),
+            NodeKind::ASSIGN(ident, expr) => format!("ASSIGN<{}>({})", ident, expr.string()),
+            NodeKind::ASSIGNOP(ident, op, expr) => {
+                format!("ASSIGNOP<{}, {}>({})", ident, op.string(), expr.string())
+            }
+            NodeKind::BLOCK(stmts, _scope) => format!("BLOCK<{} stmts>", stmts.len()),
+            NodeKind::CALL(ident, _args) => format!("CALL<{}>", ident),
+            NodeKind::CALLPTR(callee, _args) => format!("CALLPTR<{}>", callee.string()),
+            NodeKind::FUNCADDR(name) => format!("FUNCADDR<{}>", name),
+            NodeKind::ARRAYLIT(elems, _name) => format!("ARRAYLIT<{} elems>", elems.len()),
+            NodeKind::STRUCTLIT(name, members) => {
                 format!("STRUCTLIT<{},{} members>", name, members.len())
             }
-            Self::DEFARG(name) => format!("DEFARG<{}>", name),
-            Self::CONDLOOP(cond, stmts) => {
+            NodeKind::ENUMLIT(name, variant, args, _target) => {
+                format!("ENUMLIT<{}::{},{} args>", name, variant, args.len())
+            }
+            NodeKind::MATCH(expr, arms) => format!("MATCH<{},{} arms>", expr.string(), arms.len()),
+            NodeKind::DEFARG(name) => format!("DEFARG<{}>", name),
+            NodeKind::CONDLOOP(cond, stmts, _label) => {
                 format!("CONDLOOP<{},{}>", cond.string(), stmts.string())
             }
-            Self::IF(cond, stmts, alter) => match alter {
+            NodeKind::FOR(name, begin, end, stmts, _label) => format!(
+                "FOR<{} in {}..{},{}>",
+                name,
+                begin.string(),
+                end.string(),
+                stmts.string()
+            ),
+            NodeKind::BREAK(label) => match label {
+                Some(name) => format!("BREAK<{}>", name),
+                None => "BREAK".to_string(),
+            },
+            NodeKind::CONTINUE(label) => match label {
+                Some(name) => format!("CONTINUE<{}>", name),
+                None => "CONTINUE".to_string(),
+            },
+            NodeKind::IF(cond, stmts, alter) => match alter {
                 Some(alt) => format!(
                     "IF<{},{}> ELSE<{}>",
                     cond.string(),
@@ -123,8 +260,8 @@ impl Node {
                 None => format!("IF<{},{}>", cond.string(), stmts.string()),
             },
 
-            Self::LABEL(label) => format!("LABEL<{}>", label),
-            Self::GOTO(label) => format!("GOTO<{}>", label),
+            NodeKind::LABEL(label) => format!("LABEL<{}>", label),
+            NodeKind::GOTO(label) => format!("GOTO<{}>", label),
             _ => "INVALID".to_string(),
         }
     }
@@ -136,8 +273,46 @@ pub struct Func {
     pub stmts: Vec<Node>,
     pub args: Vec<Node>,
     pub env: Env,
+    /* the environments of the blocks, indexed by BLOCK's scope */
+    pub scopes: Vec<Env>,
     pub return_type: Type,
     pub document: Option<String>,
+    /* the type parameters of a generic function, it's instantiated per the type arguments */
+    pub type_params: Vec<String>,
+}
+
+/* a toplevel 'let', its initializer is folded into .data or .bss */
+#[derive(Clone)]
+pub struct Global {
+    pub name: String,
+    /* the name in the object, mangled with the module like 'math.counter' */
+    pub symbol: String,
+    pub init: Node,
+    pub ty: Type,
+    pub is_mutable: bool,
+}
+
+/* an arm of 'match', the bindings of the pattern are declared in its scope */
+#[derive(Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Node,
+    pub scope: Scope,
+    pub pos: Position,
+}
+#[derive(Clone)]
+pub enum Pattern {
+    /* '_' */
+    WILDCARD,
+    /* 'Enum::Variant(a, b)', '_' ignores the field */
+    VARIANT(Name, Name, Vec<Name>),
+}
+
+/* a toplevel 'import', the path of the module like ['std', 'io'] */
+#[derive(Clone)]
+pub struct Import {
+    pub path: Vec<String>,
+    pub pos: Position,
 }
 
 pub fn dump_ast(funcs: &Vec<Func>) {
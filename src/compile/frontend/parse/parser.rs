@@ -1,35 +1,55 @@
-use crate::ce::types::Error;
+use crate::ce::types::{Diagnostics, Error};
 use crate::compile::frontend;
 use frontend::frontmanager::frontmanager::{Env, Symbol};
-use frontend::parse::node::{Func, Node};
+use frontend::parse::node::{Func, Global, Import, MatchArm, Node, NodeKind, Pattern};
+use frontend::sema::builtin::BUILTINS;
 use frontend::sema::semantics::Type;
-use frontend::token::token::Token;
+use frontend::token::token::{Position, Token};
 
 use std::collections::BTreeMap;
-struct Parser {
-    tokens: Vec<Token>,
+struct Parser<'a> {
+    tokens: Vec<(Token, Position)>,
     funcs: Vec<Func>,
+    globals: Vec<Global>,
+    imports: Vec<Import>,
+    /* the prefix of the names this module defines, empty in the entry */
+    module: String,
     cur_env: Env,
+    scopes: Vec<Env>,
     cur: usize,
     next: usize,
     lit: usize,
     comp_table: BTreeMap<String, i128>,
+    diag: &'a mut Diagnostics,
+    recovering: bool,
+    no_structlit: bool,
 }
-pub fn parsing(tokens: Vec<Token>) -> Vec<Func> {
-    let mut parser: Parser = Parser::new(tokens);
+pub fn parsing(
+    tokens: Vec<(Token, Position)>,
+    module: &str,
+    diag: &mut Diagnostics,
+) -> (Vec<Func>, Vec<Global>, Vec<Import>) {
+    let mut parser: Parser = Parser::new(tokens, module, diag);
     parser.toplevel();
-    parser.funcs
+    (parser.funcs, parser.globals, parser.imports)
 }
-impl Parser {
-    fn new(tokens: Vec<Token>) -> Parser {
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<(Token, Position)>, module: &str, diag: &'a mut Diagnostics) -> Parser<'a> {
         Parser {
             tokens: tokens,
             funcs: Vec::with_capacity(100),
+            globals: Vec::new(),
+            imports: Vec::new(),
+            module: module.to_string(),
             cur_env: Env::new(),
+            scopes: Vec::new(),
             cur: 0,
             next: 1,
             lit: 0,
             comp_table: BTreeMap::new(),
+            diag,
+            recovering: false,
+            no_structlit: false,
         }
     }
     fn toplevel(&mut self) {
@@ -43,19 +63,38 @@ impl Parser {
                 &Token::TYPE => {
                     self.parse_alias(&mut global);
                 }
+                &Token::LET => {
+                    self.parse_global(&mut global);
+                }
                 &Token::FUNC => {
-                    self.parse_func(global.clone());
+                    self.parse_func(global.clone(), None);
                 }
                 Token::INFORMATION(contents) => {
                     self.next_token();
-                    self.parse_func(global.clone());
+                    self.parse_func(global.clone(), None);
                     let insert_number = self.funcs.len();
                     self.funcs[insert_number - 1].document = Some(contents.to_string());
                 }
                 &Token::STRUCT => {
-                    self.parse_struct();
+                    self.parse_struct(&mut global);
+                }
+                &Token::IMPORT => {
+                    self.parse_import();
+                }
+                &Token::ENUM => {
+                    self.parse_enum(&mut global);
+                }
+                &Token::IMPL => {
+                    self.parse_impl(global.clone());
+                }
+                &Token::EOF => break,
+                _ => {
+                    self.error(&format!("toplevel can't start with '{}'", t.string()));
                 }
-                _ => break,
+            }
+            /* skip to the next function or definition. */
+            if self.recovering {
+                self.synchronize(toplevel_boundaries(), false);
             }
         }
     }
@@ -66,13 +105,17 @@ impl Parser {
             &Token::LET => self.parse_let(),
             &Token::IDENT(_) => self.parse_assign(),
             &Token::LBRACE => self.parse_block(),
-            &Token::CONDLOOP => self.parse_condloop(),
+            &Token::CONDLOOP | &Token::WHILE => self.parse_condloop(),
+            &Token::FOR => self.parse_for(),
+            &Token::BREAK => self.parse_break(),
+            &Token::CONTINUE => self.parse_continue(),
             &Token::IF => self.parse_if(),
+            &Token::MATCH => self.parse_match(),
             &Token::COLON => self.parse_label(),
             &Token::GOTO => self.parse_goto(),
             _ => {
-                Error::PARSE.found(&format!("statement can't start with '{}'", t.string(),));
-                Node::INVALID
+                self.error(&format!("statement can't start with '{}'", t.string(),));
+                Node::new(NodeKind::INVALID, self.cur_pos())
             }
         }
     }
@@ -84,15 +127,29 @@ impl Parser {
         if let Token::INTEGER(val) = t {
             self.comp_table.insert(comp_name, val);
         } else {
-            Error::PARSE.found(&"compint statement's expression must be integer".to_string());
+            self.error("compint statement's expression must be integer");
         }
         self.next_token();
     }
-    fn parse_func(&mut self, global: Env) {
+    /* the methods in 'impl A' are named like 'A.get' */
+    fn parse_func(&mut self, global: Env, owner: Option<&str>) {
         self.cur_env = Env::new();
         self.cur_env.prev = Some(Box::new(global));
+        self.scopes = Vec::new();
         self.next_token();
+        let name_pos: Position = self.cur_pos();
         let func_name: String = self.consume_ident();
+        if owner.is_none() && BUILTINS.contains(&func_name.as_str()) {
+            self.diag.error(
+                Error::PARSE,
+                &name_pos,
+                &format!(
+                    "'{}' is a builtin function, it can't be redefined",
+                    func_name
+                ),
+            );
+        }
+        let type_params: Vec<String> = self.type_params();
         self.expect(&Token::LPAREN);
         let mut func_args: Vec<Node> = Vec::new();
         loop {
@@ -106,35 +163,108 @@ impl Parser {
             }
         }
         if !self.consume(&Token::DOUBLECOLON) {
-            Error::PARSE.found(
-                &"the function's return type of Depth must be declare explicit.".to_string(),
-            );
+            self.error("the function's return type of Depth must be declare explicit.");
         }
         let return_type_t = self.consume_typename();
-        let return_type: Type = if let Some(type_name) = return_type_t.name() {
+        let return_type: Type = if let Some(type_name) = return_type_t
+            .name()
+            .filter(|name| !type_params.contains(name))
+        {
             if let Some(global_t) = &self.cur_env.prev {
                 if let Some(alias_t) = global_t.type_table.get(&type_name) {
                     alias_t.clone()
                 } else {
-                    Error::TYPE.found(&format!("not found such an alias -> {}", type_name));
+                    let pos: Position = self.cur_pos();
+                    self.diag.error(
+                        Error::TYPE,
+                        &pos,
+                        &format!("not found such an alias -> {}", type_name),
+                    );
                     Type::UNKNOWN
                 }
             } else {
                 Type::UNKNOWN
             }
         } else {
-            Type::from_token(return_type_t)
+            Type::from_token_in(return_type_t, &type_params)
         };
+        if self.recovering {
+            self.synchronize(vec![Token::LBRACE], false);
+        }
         let func_stmts: Vec<Node> = self.compound_stmt();
+        let func_name: String = match owner {
+            Some(type_name) => format!("{}.{}", type_name, func_name),
+            None => func_name,
+        };
         self.funcs.push(Func {
-            name: func_name,
+            name: self.mangle(&func_name),
             args: func_args,
             stmts: func_stmts,
             return_type: return_type,
             document: None,
             env: self.cur_env.clone(),
+            scopes: self.scopes.clone(),
+            type_params,
         });
     }
+    /* impl A { func get(self : Pointer<A>) :: i64 { .. } } */
+    fn parse_impl(&mut self, global: Env) {
+        let pos: Position = self.cur_pos();
+        self.expect(&Token::IMPL);
+        let type_name: String = self.consume_ident();
+        if !matches!(global.type_table.get(&type_name), Some(Type::STRUCT(_, _))) {
+            self.diag.error(
+                Error::UNDEFINED,
+                &pos,
+                &format!("not found such a struct -> {}", type_name),
+            );
+        }
+        self.expect(&Token::LBRACE);
+        loop {
+            if self.recovering || self.consume(&Token::RBRACE) {
+                break;
+            }
+            let func_pos: Position = self.cur_pos();
+            match self.get_token() {
+                Token::FUNC => self.parse_func(global.clone(), Some(&type_name)),
+                Token::INFORMATION(contents) => {
+                    self.next_token();
+                    self.parse_func(global.clone(), Some(&type_name));
+                    let insert_number = self.funcs.len();
+                    self.funcs[insert_number - 1].document = Some(contents.to_string());
+                }
+                t => {
+                    self.error(&format!("impl block can't contain '{}'", t.string()));
+                    break;
+                }
+            }
+            self.check_receiver(&type_name, &func_pos);
+        }
+    }
+    /* the receiver is passed as the first argument of the method. */
+    fn check_receiver(&mut self, type_name: &str, pos: &Position) {
+        let method: &Func = match self.funcs.last() {
+            Some(f) => f,
+            None => return,
+        };
+        let receiver: Option<&Token> = match method.args.first().map(|arg| &arg.kind) {
+            Some(NodeKind::DEFARG(name)) if name == "self" => method
+                .env
+                .sym_table
+                .get(name)
+                .and_then(|s| s.ty.as_ref().err()),
+            _ => None,
+        };
+        let expected: Token = Token::POINTER(Box::new(Token::IDENT(type_name.to_string())));
+        if receiver != Some(&expected) {
+            let message: String = format!(
+                "the method '{}' must take 'self : Pointer<{}>' first",
+                method.name.replace('.', "::"),
+                type_name
+            );
+            self.diag.error(Error::TYPE, pos, &message);
+        }
+    }
     fn parse_alias(&mut self, global: &mut Env) {
         self.expect(&Token::TYPE);
         let alias_name: String = self.consume_ident();
@@ -145,13 +275,225 @@ impl Parser {
             Type::ALIAS(Box::new(Type::from_token(type_name))),
         );
     }
-    fn parse_struct(&mut self) {
+    fn parse_global(&mut self, global: &mut Env) {
+        self.expect(&Token::LET);
+        let mutable_flg: bool = self.consume(&Token::MUT);
+        let ident_name: String = self.consume_ident();
+        self.expect(&Token::COLON);
+        let type_name: Token = self.consume_typename();
+        self.expect(&Token::ASSIGN);
+        let init: Node = self.expr();
+        let ty: Type = Type::from_token(type_name);
+        let mut symbol: Symbol = Symbol::new(0, Ok(ty.clone()), mutable_flg);
+        symbol.is_global = true;
+        global.sym_table.insert(ident_name.clone(), symbol);
+        self.globals.push(Global {
+            symbol: self.mangle(&ident_name),
+            name: ident_name,
+            init,
+            ty,
+            is_mutable: mutable_flg,
+        });
+    }
+    fn parse_import(&mut self) {
+        let pos: Position = self.cur_pos();
+        self.expect(&Token::IMPORT);
+        let mut path: Vec<String> = vec![self.consume_ident()];
+        while self.consume(&Token::DOUBLECOLON) {
+            path.push(self.consume_ident());
+        }
+        let alias: &String = path.last().unwrap();
+        if let Some(other) = self.imports.iter().find(|i| i.path.last() == Some(alias)) {
+            if other.path != path {
+                let message: String = format!(
+                    "'{}' is imported as both {} and {}",
+                    alias,
+                    other.path.join("::"),
+                    path.join("::")
+                );
+                self.diag.error(Error::PARSE, &pos, &message);
+            }
+            return;
+        }
+        self.imports.push(Import { path, pos });
+    }
+    /* the names in a module are prefixed with the module's path, like 'math.square' */
+    fn mangle(&self, name: &str) -> String {
+        if self.module.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.module, name)
+        }
+    }
+    /* a module is referred by the last segment of its path or the whole path. */
+    fn resolve_module(&mut self, path: &[String], pos: &Position) -> String {
+        for import in self.imports.iter() {
+            if import.path == path || (path.len() == 1 && import.path.last() == path.last()) {
+                return import.path.join(".");
+            }
+        }
+        self.diag.error(
+            Error::UNDEFINED,
+            pos,
+            &format!("module '{}' is not imported", path.join("::")),
+        );
+        path.join(".")
+    }
+    fn call_args(&mut self) -> Vec<Node> {
+        self.expect(&Token::LPAREN);
+        let mut args: Vec<Node> = Vec::new();
+        loop {
+            if self.consume(&Token::RPAREN) {
+                break;
+            }
+            args.push(self.expr());
+            if !self.consume(&Token::COMMA) {
+                self.expect(&Token::RPAREN);
+                break;
+            }
+        }
+        args
+    }
+    fn parse_enum(&mut self, global: &mut Env) {
+        self.expect(&Token::ENUM);
+        let type_name: String = self.consume_ident();
+        self.expect(&Token::LBRACE);
+        let mut variants: Vec<(String, Vec<Type>)> = Vec::new();
+        loop {
+            if self.recovering || self.consume(&Token::RBRACE) {
+                break;
+            }
+            let pos: Position = self.cur_pos();
+            let variant_name: String = self.consume_ident();
+            let mut fields: Vec<Type> = Vec::new();
+            if self.consume(&Token::LPAREN) {
+                loop {
+                    let field_type: Type = Type::from_token(self.consume_typename());
+                    if let Type::UNKNOWN = field_type {
+                        let message: String =
+                            format!("the payload of '{}' must be a primitive type", variant_name);
+                        self.diag.error(Error::TYPE, &pos, &message);
+                    }
+                    fields.push(field_type);
+                    if !self.consume(&Token::COMMA) {
+                        self.expect(&Token::RPAREN);
+                        break;
+                    }
+                }
+            }
+            if variants.iter().any(|(name, _)| *name == variant_name) {
+                let message: String =
+                    format!("'{}' is defined twice in '{}'", variant_name, type_name);
+                self.diag.error(Error::TYPE, &pos, &message);
+            }
+            variants.push((variant_name, fields));
+            self.consume(&Token::COMMA);
+        }
+        global
+            .type_table
+            .insert(type_name.clone(), Type::enum_of(type_name, variants));
+    }
+    /* a variable declared as 'Func<..>' is called indirectly, so is the inferred one. */
+    fn is_func_value(&self, name: &str) -> bool {
+        match self.cur_env.lookup(name).map(|s| &s.ty) {
+            Some(Err(Token::FUNCTYPE(_, _))) | Some(Ok(Type::FUNC(_, _))) => true,
+            Some(Err(Token::EOF)) => true,
+            Some(Err(Token::IDENT(alias))) => matches!(
+                self.cur_env.lookup_type(alias),
+                Some(Type::ALIAS(alt)) if matches!(**alt, Type::FUNC(_, _))
+            ),
+            _ => false,
+        }
+    }
+    /* the struct of the variable and whether it's a pointer to it, if 'member' is its method. */
+    fn method_receiver(&self, name: &str, member: &str) -> Option<(String, bool)> {
+        let (type_name, is_pointer) = match self.cur_env.lookup(name).map(|s| &s.ty) {
+            Some(Err(Token::IDENT(type_name))) => (type_name, false),
+            Some(Err(Token::POINTER(inner))) => match &**inner {
+                Token::IDENT(type_name) => (type_name, true),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        match self.cur_env.lookup_type(type_name) {
+            Some(Type::STRUCT(members, _)) if !members.contains_key(member) => {
+                Some((type_name.to_string(), is_pointer))
+            }
+            _ => None,
+        }
+    }
+    fn is_enum(&self, name: &str) -> bool {
+        matches!(self.cur_env.lookup_type(name), Some(Type::ENUM(_, _, _)))
+    }
+    /* match E { Enum::Variant(a, b) => S  _ => S } */
+    fn parse_match(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
+        self.expect(&Token::MATCH);
+        /* 'match e {' must not take 'e {' as a struct literal. */
+        self.no_structlit = true;
+        let expr: Node = self.expr();
+        self.no_structlit = false;
+        self.expect(&Token::LBRACE);
+        let mut arms: Vec<MatchArm> = Vec::new();
+        loop {
+            if self.recovering || self.consume(&Token::RBRACE) {
+                break;
+            }
+            let arm_pos: Position = self.cur_pos();
+            let pattern: Pattern = self.parse_pattern();
+            self.expect(&Token::FATARROW);
+            let outer: Env = self.enter_scope();
+            if let Pattern::VARIANT(_, _, bindings) = &pattern {
+                /* the type is given by the variant in sema. */
+                for binding in bindings.iter().filter(|b| *b != "_") {
+                    self.cur_env
+                        .sym_table
+                        .insert(binding.clone(), Symbol::new(0, Err(Token::I64), false));
+                }
+            }
+            let body: Node = self.stmt();
+            let scope: usize = self.leave_scope(outer);
+            arms.push(MatchArm {
+                pattern,
+                body,
+                scope,
+                pos: arm_pos,
+            });
+            self.consume(&Token::COMMA);
+        }
+        Node::new(NodeKind::MATCH(Box::new(expr), Box::new(arms)), pos)
+    }
+    fn parse_pattern(&mut self) -> Pattern {
+        let enum_name: String = self.consume_ident();
+        if enum_name == "_" {
+            return Pattern::WILDCARD;
+        }
+        self.expect(&Token::DOUBLECOLON);
+        let variant: String = self.consume_ident();
+        let mut bindings: Vec<String> = Vec::new();
+        if self.consume(&Token::LPAREN) {
+            loop {
+                bindings.push(self.consume_ident());
+                if !self.consume(&Token::COMMA) {
+                    self.expect(&Token::RPAREN);
+                    break;
+                }
+            }
+        }
+        Pattern::VARIANT(enum_name, variant, bindings)
+    }
+    fn parse_struct(&mut self, global: &mut Env) {
         self.expect(&Token::STRUCT);
         let type_name: String = self.consume_ident();
+        let type_params: Vec<String> = self.type_params();
         self.expect(&Token::LBRACE);
         let mut members: BTreeMap<String, Symbol> = BTreeMap::new();
         loop {
-            if self.consume(&Token::RBRACE) {
+            if self.recovering || self.consume(&Token::RBRACE) {
+                break;
+            }
+            if self.check(&Token::EOF) {
+                self.expect(&Token::RBRACE);
                 break;
             }
             let member_name: String = self.consume_ident();
@@ -159,17 +501,50 @@ impl Parser {
             let member_type: Token = self.consume_typename();
             members.insert(member_name, Symbol::new(0, Err(member_type), false));
         }
+        /* a broken body is not registered so that it doesn't cascade. */
+        if self.recovering {
+            return;
+        }
+        /* a generic struct is sized when 'Pair<i64, bool>' instantiates it. */
+        if !type_params.is_empty() {
+            for s in members.values_mut() {
+                if let Err(member_type) = &s.ty {
+                    s.ty = Ok(Type::from_token_in(member_type.clone(), &type_params));
+                }
+            }
+            global
+                .type_table
+                .insert(type_name, Type::GENERIC(type_params, members));
+            return;
+        }
         let mut total_size: usize = 0;
         for (_name, s) in members.iter() {
             total_size += s.size();
         }
-        if let Some(ref mut global) = self.cur_env.prev {
-            global
-                .type_table
-                .insert(type_name, Type::STRUCT(members, total_size));
+        global
+            .type_table
+            .insert(type_name, Type::STRUCT(members, total_size));
+    }
+    /* '<T, U>' after the name of a generic function or struct */
+    fn type_params(&mut self) -> Vec<String> {
+        let mut params: Vec<String> = Vec::new();
+        if !self.consume(&Token::LT) {
+            return params;
+        }
+        loop {
+            if self.recovering {
+                break;
+            }
+            params.push(self.consume_ident());
+            if !self.consume(&Token::COMMA) {
+                self.expect_closing_angle();
+                break;
+            }
         }
+        params
     }
     fn define_arg(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         let mutable: bool = self.consume(&Token::MUT);
         let arg_name: String = self.consume_ident();
         self.consume(&Token::COLON);
@@ -177,55 +552,146 @@ impl Parser {
         self.cur_env
             .sym_table
             .insert(arg_name.clone(), Symbol::new(0, Err(type_name), mutable));
-        Node::DEFARG(arg_name)
+        Node::new(NodeKind::DEFARG(arg_name), pos)
     }
     fn parse_label(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         self.next_token();
         let label: String = self.consume_ident();
-        Node::LABEL(label)
+        /* a label right before a loop names the loop for break/continue. */
+        if self.check_vec(vec![Token::CONDLOOP, Token::WHILE, Token::FOR]) {
+            let mut loop_node: Node = self.stmt();
+            name_loop(&mut loop_node, label);
+            return loop_node;
+        }
+        Node::new(NodeKind::LABEL(label), pos)
     }
     fn parse_goto(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         self.next_token();
         if !self.consume(&Token::COLON) {
-            Error::PARSE.found(&"labelname must be started colon".to_string());
+            self.error("labelname must be started colon");
         }
         let label: String = self.consume_ident();
-        Node::GOTO(label)
+        Node::new(NodeKind::GOTO(label), pos)
     }
     fn parse_condloop(&mut self) -> Node {
-        self.expect(&Token::CONDLOOP);
+        let pos: Position = self.cur_pos();
+        if !self.consume(&Token::WHILE) {
+            self.expect(&Token::CONDLOOP);
+        }
         self.expect(&Token::LPAREN);
         let cond: Node = self.expr();
         self.expect(&Token::RPAREN);
         let stmt: Node = self.stmt();
-        Node::CONDLOOP(Box::new(cond), Box::new(stmt))
+        Node::new(
+            NodeKind::CONDLOOP(Box::new(cond), Box::new(stmt), None),
+            pos,
+        )
+    }
+    /* 'for i in ..' is wrapped by a block that owns 'i'. */
+    fn parse_for(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
+        let outer: Env = self.enter_scope();
+        self.expect(&Token::FOR);
+        let ident_name: String = self.consume_ident();
+        self.expect(&Token::IN);
+        /* 'for i in 0..n {' must not take 'n {' as a struct literal. */
+        self.no_structlit = true;
+        let begin: Node = self.expr();
+        self.expect(&Token::DOTDOT);
+        let end: Node = self.expr();
+        self.no_structlit = false;
+        self.cur_env
+            .sym_table
+            .insert(ident_name.clone(), Symbol::new(0, Err(Token::I64), false));
+        let stmt: Node = self.stmt();
+        let for_node: Node = Node::new(
+            NodeKind::FOR(
+                ident_name,
+                Box::new(begin),
+                Box::new(end),
+                Box::new(stmt),
+                None,
+            ),
+            pos.clone(),
+        );
+        let scope: usize = self.leave_scope(outer);
+        Node::new(NodeKind::BLOCK(Box::new(vec![for_node]), scope), pos)
+    }
+    fn parse_break(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
+        self.next_token();
+        let label: Option<String> = self.consume_loop_label();
+        Node::new(NodeKind::BREAK(label), pos)
+    }
+    fn parse_continue(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
+        self.next_token();
+        let label: Option<String> = self.consume_loop_label();
+        Node::new(NodeKind::CONTINUE(label), pos)
+    }
+    /* 'break :outer' names the loop with the label. */
+    fn consume_loop_label(&mut self) -> Option<String> {
+        if !self.consume(&Token::COLON) {
+            return None;
+        }
+        Some(self.consume_ident())
     }
     fn parse_block(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
+        let outer: Env = self.enter_scope();
         let stmts: Vec<Node> = self.compound_stmt();
-        Node::BLOCK(Box::new(stmts))
+        let scope: usize = self.leave_scope(outer);
+        Node::new(NodeKind::BLOCK(Box::new(stmts), scope), pos)
+    }
+    /* returns the enclosing environment to be restored by leave_scope(). */
+    fn enter_scope(&mut self) -> Env {
+        let mut env: Env = Env::new();
+        env.prev = Some(Box::new(self.cur_env.clone()));
+        std::mem::replace(&mut self.cur_env, env)
+    }
+    fn leave_scope(&mut self, outer: Env) -> usize {
+        let env: Env = std::mem::replace(&mut self.cur_env, outer);
+        self.scopes.push(env);
+        self.scopes.len() - 1
     }
     fn parse_if(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         self.expect(&Token::IF);
         self.expect(&Token::LPAREN);
         let cond: Node = self.expr();
         self.expect(&Token::RPAREN);
         let stmt: Node = self.stmt();
         if !self.consume(&Token::ELSE) {
-            return Node::IF(Box::new(cond), Box::new(stmt), None);
+            return Node::new(NodeKind::IF(Box::new(cond), Box::new(stmt), None), pos);
         }
-        Node::IF(Box::new(cond), Box::new(stmt), Some(Box::new(self.stmt())))
+        let alter: Node = self.stmt();
+        Node::new(
+            NodeKind::IF(Box::new(cond), Box::new(stmt), Some(Box::new(alter))),
+            pos,
+        )
     }
     fn parse_let(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         self.expect(&Token::LET);
         let mutable_flg: bool = self.consume(&Token::MUT);
         let ident_name: String = self.consume_ident();
-        self.expect(&Token::COLON);
-        let type_name: Token = self.consume_typename();
+        /* without the annotation, the type is inferred from the initializer. */
+        let mut type_name: Token = Token::EOF;
+        if self.consume(&Token::COLON) {
+            type_name = self.consume_typename();
+        }
         self.expect(&Token::ASSIGN);
         let mut expr: Node = self.expr();
-        if let Node::STRUCTLIT(ref mut name, ref mut _members) = expr {
+        if let Token::EOF = type_name {
+            type_name = self.infer_typename(&expr);
+        }
+        if let NodeKind::STRUCTLIT(ref mut name, ref mut _members) = expr.kind {
             *name = ident_name.clone();
-        } else if let Node::ARRAYLIT(ref mut _belems, ref mut name) = expr {
+        } else if let NodeKind::ENUMLIT(_, _, _, ref mut target) = expr.kind {
+            *target = ident_name.clone();
+        } else if let NodeKind::ARRAYLIT(ref mut _belems, ref mut name) = expr.kind {
             self.cur_env.sym_table.remove(name);
             *name = ident_name.clone();
         }
@@ -233,21 +699,88 @@ impl Parser {
             ident_name.clone(),
             Symbol::new(0, Err(type_name), mutable_flg),
         ) {}
-        Node::LET(ident_name, Box::new(expr))
+        Node::new(NodeKind::LET(ident_name, Box::new(expr)), pos)
+    }
+    /* the type which the parser can see from the initializer, sema infers the others. */
+    fn infer_typename(&self, expr: &Node) -> Token {
+        match &expr.kind {
+            NodeKind::STRUCTLIT(type_name, _) => match self.cur_env.lookup_type(type_name) {
+                Some(Type::STRUCT(_, _)) => Token::IDENT(type_name.to_string()),
+                _ => Token::EOF,
+            },
+            NodeKind::ENUMLIT(type_name, _, _, _) => Token::IDENT(type_name.to_string()),
+            NodeKind::IDENT(name) => match self.cur_env.lookup(name).map(|s| &s.ty) {
+                Some(Err(type_t)) => type_t.clone(),
+                _ => Token::EOF,
+            },
+            NodeKind::ADDRESS(child) => match self.infer_typename(child) {
+                Token::EOF => Token::EOF,
+                type_t => Token::POINTER(Box::new(type_t)),
+            },
+            _ => Token::EOF,
+        }
     }
     fn parse_return(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         self.expect(&Token::RETURN);
         let expr: Node = self.expr();
-        Node::RETURN(Box::new(expr))
+        Node::new(NodeKind::RETURN(Box::new(expr)), pos)
     }
     fn parse_assign(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         let ident_name: String = self.consume_ident();
-        self.expect(&Token::ASSIGN);
+        /* 'x++' and 'x--' are 'x += 1' and 'x -= 1' */
+        if self.check(&Token::INCREMENT) || self.check(&Token::DECREMENT) {
+            let op: Token = if self.consume(&Token::INCREMENT) {
+                Token::ADDASSIGN
+            } else {
+                self.expect(&Token::DECREMENT);
+                Token::SUBASSIGN
+            };
+            let one: Box<Node> = Box::new(Node::new(NodeKind::INTEGER(1), pos.clone()));
+            return Node::new(NodeKind::ASSIGNOP(ident_name, op, one), pos);
+        }
+        if !self.check_vec(compound_assigns()) {
+            self.expect(&Token::ASSIGN);
+            let mut expr: Node = self.expr();
+            if let NodeKind::ENUMLIT(_, _, _, ref mut target) = expr.kind {
+                *target = ident_name.clone();
+            }
+            return Node::new(NodeKind::ASSIGN(ident_name, Box::new(expr)), pos);
+        }
+        let op: Token = self.get_token();
+        self.next_token();
         let expr: Node = self.expr();
-        Node::ASSIGN(ident_name, Box::new(expr))
+        Node::new(NodeKind::ASSIGNOP(ident_name, op, Box::new(expr)), pos)
     }
     fn expr(&mut self) -> Node {
-        self.equal()
+        self.logor()
+    }
+    fn logor(&mut self) -> Node {
+        let mut lhs: Node = self.logand();
+        self.check_invalid(&lhs);
+        loop {
+            if !self.check(&Token::LOGOR) {
+                break;
+            }
+            let pos: Position = self.cur_pos();
+            self.next_token();
+            lhs = Node::new(NodeKind::LOGOR(Box::new(lhs), Box::new(self.logand())), pos);
+        }
+        lhs
+    }
+    fn logand(&mut self) -> Node {
+        let mut lhs: Node = self.equal();
+        self.check_invalid(&lhs);
+        loop {
+            if !self.check(&Token::LOGAND) {
+                break;
+            }
+            let pos: Position = self.cur_pos();
+            self.next_token();
+            lhs = Node::new(NodeKind::LOGAND(Box::new(lhs), Box::new(self.equal())), pos);
+        }
+        lhs
     }
     fn equal(&mut self) -> Node {
         let mut lhs: Node = self.relation();
@@ -257,33 +790,86 @@ impl Parser {
                 break;
             }
             let op: Token = self.get_token();
+            let pos: Position = self.cur_pos();
             self.next_token();
             if let &Token::EQ = &op {
-                lhs = Node::EQ(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(NodeKind::EQ(Box::new(lhs), Box::new(self.relation())), pos);
             } else if let &Token::NTEQ = &op {
-                lhs = Node::NTEQ(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(
+                    NodeKind::NTEQ(Box::new(lhs), Box::new(self.relation())),
+                    pos,
+                );
             }
         }
         lhs
     }
     fn relation(&mut self) -> Node {
-        let mut lhs: Node = self.shift();
+        let mut lhs: Node = self.bitor();
         self.check_invalid(&lhs);
         loop {
             if !self.check_vec(vec![Token::LT, Token::GT, Token::LTEQ, Token::GTEQ]) {
                 break;
             }
             let op: Token = self.get_token();
+            let pos: Position = self.cur_pos();
             self.next_token();
             if let &Token::LT = &op {
-                lhs = Node::LT(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(NodeKind::LT(Box::new(lhs), Box::new(self.relation())), pos);
             } else if let &Token::GT = &op {
-                lhs = Node::GT(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(NodeKind::GT(Box::new(lhs), Box::new(self.relation())), pos);
             } else if let &Token::LTEQ = &op {
-                lhs = Node::LTEQ(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(
+                    NodeKind::LTEQ(Box::new(lhs), Box::new(self.relation())),
+                    pos,
+                );
             } else if let &Token::GTEQ = &op {
-                lhs = Node::GTEQ(Box::new(lhs), Box::new(self.relation()));
+                lhs = Node::new(
+                    NodeKind::GTEQ(Box::new(lhs), Box::new(self.relation())),
+                    pos,
+                );
+            }
+        }
+        lhs
+    }
+    fn bitor(&mut self) -> Node {
+        let mut lhs: Node = self.bitxor();
+        self.check_invalid(&lhs);
+        loop {
+            if !self.check(&Token::PIPE) {
+                break;
+            }
+            let pos: Position = self.cur_pos();
+            self.next_token();
+            lhs = Node::new(NodeKind::BITOR(Box::new(lhs), Box::new(self.bitxor())), pos);
+        }
+        lhs
+    }
+    fn bitxor(&mut self) -> Node {
+        let mut lhs: Node = self.bitand();
+        self.check_invalid(&lhs);
+        loop {
+            if !self.check(&Token::CARET) {
+                break;
+            }
+            let pos: Position = self.cur_pos();
+            self.next_token();
+            lhs = Node::new(
+                NodeKind::BITXOR(Box::new(lhs), Box::new(self.bitand())),
+                pos,
+            );
+        }
+        lhs
+    }
+    fn bitand(&mut self) -> Node {
+        let mut lhs: Node = self.shift();
+        self.check_invalid(&lhs);
+        loop {
+            if !self.check(&Token::AMPERSAND) {
+                break;
             }
+            let pos: Position = self.cur_pos();
+            self.next_token();
+            lhs = Node::new(NodeKind::BITAND(Box::new(lhs), Box::new(self.shift())), pos);
         }
         lhs
     }
@@ -291,17 +877,13 @@ impl Parser {
         let mut lhs: Node = self.adsub();
         self.check_invalid(&lhs);
         loop {
+            let pos: Position = self.cur_pos();
             if self.check(&Token::LSHIFT) {
                 self.next_token();
-                lhs = Node::LSHIFT(Box::new(lhs), Box::new(self.adsub()));
-            } else if self.check(&Token::GT) {
-                if self.peek(&Token::GT) {
-                    self.next_token();
-                    self.next_token();
-                    lhs = Node::RSHIFT(Box::new(lhs), Box::new(self.adsub()));
-                } else {
-                    break;
-                }
+                lhs = Node::new(NodeKind::LSHIFT(Box::new(lhs), Box::new(self.adsub())), pos);
+            } else if self.check(&Token::RSHIFT) {
+                self.next_token();
+                lhs = Node::new(NodeKind::RSHIFT(Box::new(lhs), Box::new(self.adsub())), pos);
             } else {
                 break;
             }
@@ -316,11 +898,12 @@ impl Parser {
                 break;
             }
             let op: Token = self.get_token();
+            let pos: Position = self.cur_pos();
             self.next_token();
             if let Token::PLUS = op {
-                lhs = Node::ADD(Box::new(lhs), Box::new(self.muldiv()));
+                lhs = Node::new(NodeKind::ADD(Box::new(lhs), Box::new(self.muldiv())), pos);
             } else if let Token::MINUS = op {
-                lhs = Node::SUB(Box::new(lhs), Box::new(self.muldiv()));
+                lhs = Node::new(NodeKind::SUB(Box::new(lhs), Box::new(self.muldiv())), pos);
             }
         }
         lhs
@@ -333,31 +916,48 @@ impl Parser {
                 break;
             }
             let op: Token = self.get_token();
+            let pos: Position = self.cur_pos();
             self.next_token();
             if let Token::STAR = op {
-                lhs = Node::MUL(Box::new(lhs), Box::new(self.unary()));
+                lhs = Node::new(NodeKind::MUL(Box::new(lhs), Box::new(self.unary())), pos);
             } else if let Token::SLASH = op {
-                lhs = Node::DIV(Box::new(lhs), Box::new(self.unary()));
+                lhs = Node::new(NodeKind::DIV(Box::new(lhs), Box::new(self.unary())), pos);
             } else if let Token::PERCENT = op {
-                lhs = Node::MOD(Box::new(lhs), Box::new(self.unary()));
+                lhs = Node::new(NodeKind::MOD(Box::new(lhs), Box::new(self.unary())), pos);
             }
         }
         lhs
     }
     fn unary(&mut self) -> Node {
         let t: Token = self.get_token();
+        let pos: Position = self.cur_pos();
         match t {
             Token::AMPERSAND => {
                 self.next_token();
-                Node::ADDRESS(Box::new(self.unary()))
+                let child: Node = self.unary();
+                /* '&f' takes the address of the function unless 'f' is a variable. */
+                if let NodeKind::IDENT(name) = &child.kind {
+                    if self.cur_env.lookup(name).is_none() {
+                        return Node::new(NodeKind::FUNCADDR(self.mangle(name)), pos);
+                    }
+                }
+                Node::new(NodeKind::ADDRESS(Box::new(child)), pos)
             }
             Token::STAR => {
                 self.next_token();
-                Node::DEREFERENCE(Box::new(self.unary()))
+                Node::new(NodeKind::DEREFERENCE(Box::new(self.unary())), pos)
             }
             Token::MINUS => {
                 self.next_token();
-                Node::MINUS(Box::new(self.unary()))
+                Node::new(NodeKind::MINUS(Box::new(self.unary())), pos)
+            }
+            Token::BANG => {
+                self.next_token();
+                Node::new(NodeKind::NOT(Box::new(self.unary())), pos)
+            }
+            Token::TILDE => {
+                self.next_token();
+                Node::new(NodeKind::BITNOT(Box::new(self.unary())), pos)
             }
             _ => {
                 let n: Node = self.term();
@@ -372,18 +972,23 @@ impl Parser {
         let t: Token = self.get_token();
         match t {
             Token::LBRACKET => {
+                let pos: Position = self.cur_pos();
                 self.expect(&Token::LBRACKET);
                 let ind_n: Node = self.expr();
                 self.expect(&Token::RBRACKET);
-                self.postfix(Node::INDEX(Box::new(n), Box::new(ind_n)))
+                self.postfix(Node::new(
+                    NodeKind::INDEX(Box::new(n), Box::new(ind_n)),
+                    pos,
+                ))
             }
             _ => n,
         }
     }
     fn term(&mut self) -> Node {
+        let pos: Position = self.cur_pos();
         if let Some(val) = self.consume_compint() {
             self.next_token();
-            return Node::INTEGER(val);
+            return Node::new(NodeKind::INTEGER(val), pos);
         }
         let t: Token = self.get_token();
 
@@ -398,7 +1003,7 @@ impl Parser {
                 self.expect(&Token::LBRACKET);
                 let mut elems: Vec<Node> = Vec::new();
                 loop {
-                    if let &Token::RBRACKET = self.cur_token() {
+                    if self.consume(&Token::RBRACKET) {
                         break;
                     }
                     elems.push(self.expr());
@@ -413,11 +1018,31 @@ impl Parser {
                 );
                 let num = self.lit;
                 self.lit += 1;
-                Node::ARRAYLIT(Box::new(elems), format!("Array{}", num))
+                Node::new(
+                    NodeKind::ARRAYLIT(Box::new(elems), format!("Array{}", num)),
+                    pos,
+                )
             }
             Token::INTEGER(val) => {
                 self.next_token();
-                Node::INTEGER(val)
+                Node::new(NodeKind::INTEGER(val), pos)
+            }
+            /* a char-literal is the integer of its byte. */
+            Token::CHARLIT(c) => {
+                self.next_token();
+                Node::new(NodeKind::INTEGER(c as i128), pos)
+            }
+            Token::STRLIT(contents) => {
+                self.next_token();
+                Node::new(NodeKind::STRLIT(contents), pos)
+            }
+            Token::TRUE => {
+                self.next_token();
+                Node::new(NodeKind::BOOLEAN(true), pos)
+            }
+            Token::FALSE => {
+                self.next_token();
+                Node::new(NodeKind::BOOLEAN(false), pos)
             }
             Token::IDENT(name) => {
                 self.next_token();
@@ -426,24 +1051,76 @@ impl Parser {
                     Token::DOT => {
                         self.expect(&Token::DOT);
                         let member_name: String = self.consume_ident();
-                        Node::MEMBER(Box::new(Node::IDENT(name)), member_name)
+                        if let Token::LPAREN = self.get_token() {
+                            let mut args: Vec<Node> = self.call_args();
+                            /* 'a.get()' calls 'A.get(&a)' unless 'get' is a member of Func<..> */
+                            if let Some((type_name, is_pointer)) =
+                                self.method_receiver(&name, &member_name)
+                            {
+                                let ident: Node = Node::new(NodeKind::IDENT(name), pos.clone());
+                                let receiver: Node = if is_pointer {
+                                    ident
+                                } else {
+                                    Node::new(NodeKind::ADDRESS(Box::new(ident)), pos.clone())
+                                };
+                                args.insert(0, receiver);
+                                let method: String =
+                                    self.mangle(&format!("{}.{}", type_name, member_name));
+                                return Node::new(NodeKind::CALL(method, Box::new(args)), pos);
+                            }
+                            let ident: Node = Node::new(NodeKind::IDENT(name), pos.clone());
+                            let member: Node = Node::new(
+                                NodeKind::MEMBER(Box::new(ident), member_name),
+                                pos.clone(),
+                            );
+                            return Node::new(
+                                NodeKind::CALLPTR(Box::new(member), Box::new(args)),
+                                pos,
+                            );
+                        }
+                        let ident: Node = Node::new(NodeKind::IDENT(name), pos.clone());
+                        Node::new(NodeKind::MEMBER(Box::new(ident), member_name), pos)
+                    }
+                    Token::LPAREN if self.is_func_value(&name) => {
+                        let args: Vec<Node> = self.call_args();
+                        let callee: Node = Node::new(NodeKind::IDENT(name), pos.clone());
+                        Node::new(NodeKind::CALLPTR(Box::new(callee), Box::new(args)), pos)
                     }
                     Token::LPAREN => {
-                        self.expect(&Token::LPAREN);
+                        let args: Vec<Node> = self.call_args();
+                        let func_name: String = if BUILTINS.contains(&name.as_str()) {
+                            name
+                        } else {
+                            self.mangle(&name)
+                        };
+                        Node::new(NodeKind::CALL(func_name, Box::new(args)), pos)
+                    }
+                    Token::DOUBLECOLON if self.is_enum(&name) => {
+                        self.expect(&Token::DOUBLECOLON);
+                        let variant: String = self.consume_ident();
                         let mut args: Vec<Node> = Vec::new();
-                        loop {
-                            if self.consume(&Token::RPAREN) {
-                                break;
-                            }
-                            args.push(self.expr());
-                            if !self.consume(&Token::COMMA) {
-                                self.expect(&Token::RPAREN);
-                                break;
-                            }
+                        if let Token::LPAREN = self.get_token() {
+                            args = self.call_args();
                         }
-                        Node::CALL(name, Box::new(args))
+                        /* the variable it initializes is filled in by 'let' or assignment. */
+                        let kind = NodeKind::ENUMLIT(name, variant, Box::new(args), String::new());
+                        Node::new(kind, pos)
                     }
-                    Token::LBRACE => {
+                    Token::DOUBLECOLON => {
+                        /* 'module::func(args)' calls the function defined in the imported module. */
+                        let mut path: Vec<String> = vec![name];
+                        let mut func_name: String = String::new();
+                        while self.consume(&Token::DOUBLECOLON) {
+                            func_name = self.consume_ident();
+                            path.push(func_name.clone());
+                        }
+                        path.pop();
+                        let module: String = self.resolve_module(&path, &pos);
+                        let args: Vec<Node> = self.call_args();
+                        let symbol: String = format!("{}.{}", module, func_name);
+                        Node::new(NodeKind::CALL(symbol, Box::new(args)), pos)
+                    }
+                    Token::LBRACE if !self.no_structlit => {
                         self.expect(&Token::LBRACE);
                         let mut members: BTreeMap<String, Node> = BTreeMap::new();
                         loop {
@@ -459,14 +1136,14 @@ impl Parser {
                                 break;
                             }
                         }
-                        Node::STRUCTLIT(name, Box::new(members))
+                        Node::new(NodeKind::STRUCTLIT(name, Box::new(members)), pos)
                     }
-                    _ => Node::IDENT(name),
+                    _ => Node::new(NodeKind::IDENT(name), pos),
                 }
             }
             _ => {
-                Error::PARSE.found(&format!("term can't start with '{}'", t.string()));
-                Node::INVALID
+                self.error(&format!("term can't start with '{}'", t.string()));
+                Node::new(NodeKind::INVALID, pos)
             }
         }
     }
@@ -477,8 +1154,32 @@ impl Parser {
             if self.consume(&Token::RBRACE) {
                 break;
             }
+            /* the block isn't closed before the next definition. */
+            if self.check_vec(toplevel_boundaries()) || self.check(&Token::EOF) {
+                self.expect(&Token::RBRACE);
+                break;
+            }
             let st: Node = self.stmt();
             stmts.push(st);
+
+            /* skip to the next statement. */
+            if self.recovering {
+                let mut boundaries: Vec<Token> = vec![
+                    Token::LET,
+                    Token::RETURN,
+                    Token::IF,
+                    Token::CONDLOOP,
+                    Token::WHILE,
+                    Token::FOR,
+                    Token::BREAK,
+                    Token::CONTINUE,
+                    Token::GOTO,
+                    Token::LBRACE,
+                    Token::RBRACE,
+                ];
+                boundaries.append(&mut toplevel_boundaries());
+                self.synchronize(boundaries, true);
+            }
         }
         stmts
     }
@@ -488,12 +1189,23 @@ impl Parser {
             self.next_token();
             return;
         }
-        Error::PARSE.found(&format!(
+        self.error(&format!(
             "expected {} but got '{}'",
             t.string(),
             cur.string()
         ));
     }
+    fn expect_closing_angle(&mut self) {
+        /* 'Pointer<Pointer<i64>>' ends with '>>', so leave the other '>' to the outer type. */
+        if self.check(&Token::RSHIFT) {
+            let pos: &mut Position = &mut self.tokens[self.cur].1;
+            pos.column += 1;
+            pos.length = 1;
+            self.tokens[self.cur].0 = Token::GT;
+            return;
+        }
+        self.expect(&Token::GT);
+    }
     fn consume(&mut self, t: &Token) -> bool {
         let cur: &Token = self.cur_token();
         if t == cur {
@@ -506,33 +1218,80 @@ impl Parser {
     fn consume_typename(&mut self) -> Token {
         let t: Token = self.get_token();
         match t {
-            Token::I64 => {
+            Token::I8
+            | Token::I16
+            | Token::I32
+            | Token::I64
+            | Token::U8
+            | Token::U16
+            | Token::U32
+            | Token::U64
+            | Token::BOOL => {
                 self.next_token();
-                Token::I64
+                t
             }
             Token::IDENT(name) => {
                 self.next_token();
+                /* Pair<i64, bool> */
+                if self.consume(&Token::LT) {
+                    let mut args: Vec<Token> = Vec::new();
+                    loop {
+                        if self.recovering {
+                            break;
+                        }
+                        args.push(self.consume_typename());
+                        if !self.consume(&Token::COMMA) {
+                            self.expect_closing_angle();
+                            break;
+                        }
+                    }
+                    return Token::GENERIC(name, args);
+                }
                 Token::IDENT(name.to_string())
             }
             Token::POINTER(_ptr_to) => {
                 self.next_token();
                 self.expect(&Token::LT);
                 let inner: Token = self.consume_typename();
-                self.expect(&Token::GT);
+                self.expect_closing_angle();
                 Token::POINTER(Box::new(inner))
             }
+            Token::FUNCTYPE(_args, _ret) => {
+                /* Func<(i64, u8) :: i64> */
+                self.next_token();
+                self.expect(&Token::LT);
+                self.expect(&Token::LPAREN);
+                let mut args: Vec<Token> = Vec::new();
+                loop {
+                    if self.recovering || self.consume(&Token::RPAREN) {
+                        break;
+                    }
+                    args.push(self.consume_typename());
+                    if !self.consume(&Token::COMMA) {
+                        self.expect(&Token::RPAREN);
+                        break;
+                    }
+                }
+                self.expect(&Token::DOUBLECOLON);
+                let ret: Token = self.consume_typename();
+                self.expect_closing_angle();
+                Token::FUNCTYPE(args, Box::new(ret))
+            }
             Token::ARRAY(_type_name, _ary_size) => {
                 self.next_token();
                 self.expect(&Token::LT);
                 let elem_type: Token = self.consume_typename();
                 self.expect(&Token::COMMA);
                 let ary_size: Token = self.get_token();
+                if !matches!(ary_size, Token::INTEGER(_)) {
+                    self.error("array size must be known at compile time");
+                }
                 self.next_token();
-                self.expect(&Token::GT);
+                self.expect_closing_angle();
                 Token::ARRAY(Box::new(elem_type), Box::new(ary_size))
             }
             _ => {
-                Error::PARSE.found(&format!("got {} it's not typename ", t.string()));
+                self.error(&format!("got {} it's not typename ", t.string()));
                 Token::EOF
             }
         }
@@ -554,14 +1313,43 @@ impl Parser {
             self.next_token();
             name.to_string()
         } else {
-            Error::PARSE.found(&format!("expected identifier but got '{}'", t.string()));
+            self.error(&format!("expected identifier but got '{}'", t.string()));
             String::new()
         }
     }
     fn check_invalid(&mut self, n: &Node) {
-        if let &Node::INVALID = n {
-            Error::PARSE.found(&"got INVALID Node".to_string());
+        if let NodeKind::INVALID = n.kind {
+            self.error("got INVALID Node");
+        }
+    }
+    fn error(&mut self, message: &str) {
+        /* suppress the cascade until the parser synchronizes. */
+        if self.recovering {
+            return;
+        }
+        self.recovering = true;
+        let pos: Position = self.cur_pos();
+        self.diag.error(Error::PARSE, &pos, message);
+    }
+    fn synchronize(&mut self, boundaries: Vec<Token>, stop_at_assign: bool) {
+        loop {
+            if self.check_vec(boundaries.clone()) || self.check(&Token::EOF) {
+                break;
+            }
+            /* an assignment statement starts with 'ident =' or 'ident op='. */
+            if let Token::IDENT(_) = self.cur_token() {
+                if stop_at_assign
+                    && (self.peek(&Token::ASSIGN)
+                        || self.peek(&Token::INCREMENT)
+                        || self.peek(&Token::DECREMENT)
+                        || compound_assigns().contains(self.peek_token()))
+                {
+                    break;
+                }
+            }
+            self.next_token();
         }
+        self.recovering = false;
     }
     fn check_vec(&self, tks: Vec<Token>) -> bool {
         for t in tks.iter() {
@@ -575,13 +1363,22 @@ impl Parser {
         if self.cur >= self.tokens.len() {
             return Token::EOF;
         }
-        self.tokens[self.cur].clone()
+        self.tokens[self.cur].0.clone()
     }
     fn cur_token(&self) -> &Token {
         if self.cur >= self.tokens.len() {
             return &Token::EOF;
         }
-        &self.tokens[self.cur]
+        &self.tokens[self.cur].0
+    }
+    fn cur_pos(&self) -> Position {
+        if self.cur >= self.tokens.len() {
+            return match self.tokens.last() {
+                Some((_, pos)) => pos.clone(),
+                None => Position::default(),
+            };
+        }
+        self.tokens[self.cur].1.clone()
     }
     fn next_token(&mut self) {
         self.cur += 1;
@@ -603,6 +1400,41 @@ impl Parser {
         if self.next >= self.tokens.len() {
             return &Token::EOF;
         }
-        &self.tokens[self.next]
+        &self.tokens[self.next].0
+    }
+}
+fn name_loop(n: &mut Node, label: String) {
+    match n.kind {
+        NodeKind::CONDLOOP(_, _, ref mut loop_label)
+        | NodeKind::FOR(_, _, _, _, ref mut loop_label) => *loop_label = Some(label),
+        /* for-in is wrapped by the block of its variable. */
+        NodeKind::BLOCK(ref mut stmts, _) => {
+            if let Some(st) = stmts.first_mut() {
+                name_loop(st, label);
+            }
+        }
+        _ => (),
     }
 }
+fn compound_assigns() -> Vec<Token> {
+    vec![
+        Token::ADDASSIGN,
+        Token::SUBASSIGN,
+        Token::MULASSIGN,
+        Token::DIVASSIGN,
+        Token::MODASSIGN,
+        Token::LSHIFTASSIGN,
+        Token::RSHIFTASSIGN,
+    ]
+}
+fn toplevel_boundaries() -> Vec<Token> {
+    vec![
+        Token::FUNC,
+        Token::STRUCT,
+        Token::TYPE,
+        Token::COMPINT,
+        Token::IMPORT,
+        Token::ENUM,
+        Token::IMPL,
+    ]
+}
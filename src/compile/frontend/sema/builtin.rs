@@ -0,0 +1,83 @@
+use crate::ce::types::Error;
+use crate::compile::frontend;
+use frontend::frontmanager::frontmanager::FrontManager;
+use frontend::parse::node::Node;
+use frontend::sema::semantics::Type;
+use frontend::token::token::Position;
+
+/* the functions the runtime provides, they aren't mangled and can't be redefined. */
+pub const BUILTINS: [&str; 3] = ["alloc", "free", "syscall"];
+/* the number and the 6 arguments of the system call */
+pub const SYSCALL_ARGS: usize = 7;
+
+impl FrontManager {
+    /* 'alloc(size)' returns the address of 'size' bytes on the heap, 'free(ptr)' releases it. */
+    pub fn walk_builtin_call(&mut self, func_name: &str, args: &[Node], pos: &Position) -> Type {
+        if func_name == "syscall" {
+            return self.walk_syscall(args, pos);
+        }
+        let (expected, ret): (Type, Type) = match func_name {
+            "alloc" => (
+                Type::INTEGER(false, 64),
+                Type::POINTER(Box::new(Type::INTEGER(false, 8))),
+            ),
+            _ => (
+                /* any pointer is released */
+                Type::POINTER(Box::new(Type::UNKNOWN)),
+                Type::INTEGER(true, 64),
+            ),
+        };
+        if args.len() != 1 {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!("'{}' takes 1 arguments but {} given", func_name, args.len()),
+            );
+            return ret;
+        }
+        let arg_type: Type = self.walk(args[0].clone());
+        if arg_type != Type::UNKNOWN
+            && std::mem::discriminant(&expected) != std::mem::discriminant(&arg_type)
+        {
+            self.diag.error(
+                Error::TYPE,
+                &args[0].pos,
+                &format!(
+                    "expected {} in the argument but got {}",
+                    expected.string().replace("<UNKNOWN>", ""),
+                    arg_type.string()
+                ),
+            );
+        }
+        ret
+    }
+    /* 'syscall(n, a1, .., a6)' takes the integers or the pointers, returns rax as it is. */
+    fn walk_syscall(&mut self, args: &[Node], pos: &Position) -> Type {
+        if args.is_empty() || args.len() > SYSCALL_ARGS {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!(
+                    "'syscall' takes 1 to {} arguments but {} given",
+                    SYSCALL_ARGS,
+                    args.len()
+                ),
+            );
+        }
+        for arg in args.iter() {
+            let arg_type: Type = self.walk(arg.clone());
+            match arg_type {
+                Type::INTEGER(_, _) | Type::POINTER(_) | Type::UNKNOWN => (),
+                _ => self.diag.error(
+                    Error::TYPE,
+                    &arg.pos,
+                    &format!(
+                        "expected an integer or a pointer in the argument but got {}",
+                        arg_type.string()
+                    ),
+                ),
+            }
+        }
+        Type::INTEGER(true, 64)
+    }
+}
@@ -0,0 +1,251 @@
+use crate::ce::types::Error;
+use crate::compile::frontend;
+use frontend::frontmanager::frontmanager::{Env, FrontManager, Instance};
+use frontend::parse::node::{Func, Node, NodeKind};
+use frontend::sema::semantics::Type;
+use frontend::token::token::{Position, Token};
+
+use std::collections::BTreeMap;
+
+impl FrontManager {
+    /* the calls of the generic functions refer their instances, then the templates are dropped. */
+    pub fn monomorphize(&mut self) {
+        let instances: Vec<Instance> = std::mem::take(&mut self.instances);
+        for f in self.functions.iter_mut() {
+            for st in f.stmts.iter_mut() {
+                resolve_instances(st, &f.name, &instances);
+            }
+        }
+        self.functions.retain(|f| f.type_params.is_empty());
+    }
+    pub fn find_template(&self, func_name: &str) -> Option<Func> {
+        self.functions
+            .iter()
+            .chain(self.imported.iter())
+            .find(|f| f.name == func_name && !f.type_params.is_empty())
+            .cloned()
+    }
+    /* the type arguments are inferred from the arguments, the function is instantiated once per them. */
+    pub fn walk_generic_call(&mut self, template: Func, args: &[Node], pos: &Position) -> Type {
+        let display_name: String = template.name.replace('.', "::");
+        let params: Vec<Type> = match Type::signature(&template) {
+            Type::FUNC(params, _) => params,
+            _ => Vec::new(),
+        };
+        if params.len() != args.len() {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!(
+                    "'{}' takes {} arguments but {} given",
+                    display_name,
+                    params.len(),
+                    args.len()
+                ),
+            );
+            return Type::UNKNOWN;
+        }
+        let mut bindings: BTreeMap<String, Type> = BTreeMap::new();
+        for (param, arg) in params.iter().zip(args.iter()) {
+            let arg_type: Type = self.walk(arg.clone());
+            if let Err(name) = infer(param, &arg_type, &mut bindings) {
+                self.diag.error(
+                    Error::TYPE,
+                    &arg.pos,
+                    &format!(
+                        "'{}' of '{}' is {} but got {}",
+                        name,
+                        display_name,
+                        bindings[&name].string(),
+                        arg_type.string()
+                    ),
+                );
+            }
+        }
+        let mut type_args: Vec<String> = Vec::new();
+        for type_param in template.type_params.iter() {
+            match bindings.get(type_param) {
+                Some(ty) => type_args.push(ty.mangle()),
+                None => {
+                    self.diag.error(
+                        Error::TYPE,
+                        pos,
+                        &format!(
+                            "can't infer the type parameter '{}' of '{}'",
+                            type_param, display_name
+                        ),
+                    );
+                    return Type::UNKNOWN;
+                }
+            }
+        }
+
+        /* 'max' called with i64 is instantiated as 'max.i64' */
+        let name: String = format!("{}.{}", template.name, type_args.join("."));
+        if !self.functions.iter().any(|f| f.name == name) {
+            match instantiate(&template, &name, &bindings) {
+                Some(instance) => self.functions.push(instance),
+                None => {
+                    self.diag.error(
+                        Error::TYPE,
+                        pos,
+                        &format!(
+                            "'{}' can't be instantiated with {}",
+                            display_name,
+                            type_args.join(", ")
+                        ),
+                    );
+                    return Type::UNKNOWN;
+                }
+            }
+        }
+        self.instances
+            .push(Instance::new(self.cur_func.clone(), pos.clone(), name));
+        template.return_type.substitute(&bindings)
+    }
+    /* the members of the struct in the annotation, 'Pair<i64, bool>' instantiates the generic one. */
+    pub fn resolve_struct(
+        &mut self,
+        type_t: &Token,
+        pos: &Position,
+    ) -> Option<(String, BTreeMap<String, Type>)> {
+        let (type_name, args) = match type_t {
+            Token::IDENT(name) => (name.to_string(), Vec::new()),
+            Token::GENERIC(name, args) => (name.to_string(), args.clone()),
+            _ => return None,
+        };
+        match self.cur_env.lookup_type(&type_name).cloned() {
+            Some(Type::STRUCT(members, _)) => {
+                if !args.is_empty() {
+                    self.diag.error(
+                        Error::TYPE,
+                        pos,
+                        &format!("'{}' takes no type arguments", type_name),
+                    );
+                    return None;
+                }
+                let members: BTreeMap<String, Type> = members
+                    .into_iter()
+                    .map(|(member, s)| (member, s.ty.unwrap_or_else(Type::from_token)))
+                    .collect();
+                Some((type_name, members))
+            }
+            Some(Type::GENERIC(params, members)) => {
+                if params.len() != args.len() {
+                    self.diag.error(
+                        Error::TYPE,
+                        pos,
+                        &format!(
+                            "'{}' takes {} type arguments but {} given",
+                            type_name,
+                            params.len(),
+                            args.len()
+                        ),
+                    );
+                    return None;
+                }
+                let bindings: BTreeMap<String, Type> = params
+                    .into_iter()
+                    .zip(args.into_iter().map(Type::from_token))
+                    .collect();
+                let members: BTreeMap<String, Type> = members
+                    .into_iter()
+                    .map(|(member, s)| {
+                        let ty: Type = s.ty.map_or(Type::UNKNOWN, |ty| ty.substitute(&bindings));
+                        (member, ty)
+                    })
+                    .collect();
+                Some((type_name, members))
+            }
+            _ => None,
+        }
+    }
+}
+
+/* bind the type parameters in 'param' to the parts of 'arg', returns the one bound to another type. */
+fn infer(param: &Type, arg: &Type, bindings: &mut BTreeMap<String, Type>) -> Result<(), String> {
+    match (param, arg) {
+        (_, Type::UNKNOWN) => Ok(()),
+        (Type::PARAM(name), _) => match bindings.get(name) {
+            /* integers are converted implicitly, so the first one decides the width. */
+            Some(Type::INTEGER(_, _)) if matches!(arg, Type::INTEGER(_, _)) => Ok(()),
+            Some(bound) if bound != arg => Err(name.to_string()),
+            Some(_) => Ok(()),
+            None => {
+                bindings.insert(name.to_string(), arg.clone());
+                Ok(())
+            }
+        },
+        (Type::POINTER(param_inner), Type::POINTER(arg_inner))
+        | (Type::ARRAY(param_inner, _), Type::ARRAY(arg_inner, _)) => {
+            infer(param_inner, arg_inner, bindings)
+        }
+        (Type::FUNC(param_args, param_ret), Type::FUNC(arg_args, arg_ret)) => {
+            for (p, a) in param_args.iter().zip(arg_args.iter()) {
+                infer(p, a, bindings)?;
+            }
+            infer(param_ret, arg_ret, bindings)
+        }
+        _ => Ok(()),
+    }
+}
+
+/* copy the template with the type parameters substituted, sema walks it as a usual function. */
+fn instantiate(template: &Func, name: &str, bindings: &BTreeMap<String, Type>) -> Option<Func> {
+    let args: BTreeMap<String, Token> = bindings
+        .iter()
+        .map(|(param, ty)| ty.token().map(|t| (param.to_string(), t)))
+        .collect::<Option<BTreeMap<String, Token>>>()?;
+    let mut instance: Func = template.clone();
+    instance.name = name.to_string();
+    instance.type_params = Vec::new();
+    instance.return_type = template.return_type.substitute(bindings);
+    substitute_env(&mut instance.env, &args);
+    for scope in instance.scopes.iter_mut() {
+        substitute_env(scope, &args);
+    }
+    Some(instance)
+}
+
+fn substitute_env(env: &mut Env, args: &BTreeMap<String, Token>) {
+    for s in env.sym_table.values_mut() {
+        if let Err(type_t) = &s.ty {
+            s.ty = Err(substitute_token(type_t, args));
+        }
+    }
+}
+
+fn substitute_token(type_t: &Token, args: &BTreeMap<String, Token>) -> Token {
+    match type_t {
+        Token::IDENT(name) => args.get(name).cloned().unwrap_or_else(|| type_t.clone()),
+        Token::POINTER(inner) => Token::POINTER(Box::new(substitute_token(inner, args))),
+        Token::ARRAY(elem, size) => {
+            Token::ARRAY(Box::new(substitute_token(elem, args)), size.clone())
+        }
+        Token::FUNCTYPE(params, ret) => Token::FUNCTYPE(
+            params.iter().map(|p| substitute_token(p, args)).collect(),
+            Box::new(substitute_token(ret, args)),
+        ),
+        Token::GENERIC(name, params) => Token::GENERIC(
+            name.to_string(),
+            params.iter().map(|p| substitute_token(p, args)).collect(),
+        ),
+        _ => type_t.clone(),
+    }
+}
+
+/* the calls in 'caller' which sema resolved to the instances */
+fn resolve_instances(n: &mut Node, caller: &str, instances: &[Instance]) {
+    let pos: Position = n.pos.clone();
+    if let NodeKind::CALL(name, _) = &mut n.kind {
+        if let Some(instance) = instances
+            .iter()
+            .find(|i| i.caller == caller && i.pos == pos)
+        {
+            *name = instance.name.clone();
+        }
+    }
+    for ch in n.children_mut() {
+        resolve_instances(ch, caller, instances);
+    }
+}
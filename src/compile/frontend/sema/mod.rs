@@ -1,2 +1,4 @@
+pub mod builtin;
+pub mod generics;
 pub mod optimize;
 pub mod semantics;
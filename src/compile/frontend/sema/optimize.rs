@@ -1,8 +1,20 @@
+use crate::ce::types::Error;
 use crate::compile::frontend;
 use frontend::frontmanager::frontmanager::FrontManager;
-use frontend::parse::node::{Func, Node};
+use frontend::parse::node::{Func, Node, NodeKind};
 impl FrontManager {
     pub fn constant_folding(&mut self) {
+        /* the initial value of a global is written into the object file. */
+        for (idx, g) in self.globals.clone().iter().enumerate() {
+            match self.fold_expr(g.init.clone()) {
+                Some(folded) => self.globals[idx].init = folded,
+                None => self.diag.error(
+                    Error::TYPE,
+                    &g.init.pos,
+                    &format!("global '{}' must be initialized with a constant", g.name),
+                ),
+            }
+        }
         let func_num: usize = self.functions.len();
         let mut idx: usize = 0;
         loop {
@@ -19,114 +31,186 @@ impl FrontManager {
         }
     }
     fn fold_stmt(&mut self, n: &Node, func_idx: usize, i: usize) {
-        match n {
-            Node::LET(ident_name, bexpr) => {
+        match &n.kind {
+            NodeKind::LET(ident_name, bexpr) => {
                 let onode: Option<Node> = self.fold_expr(*bexpr.clone());
-                if let Some(Node::INTEGER(val)) = onode {
-                    self.functions[func_idx].stmts[i] =
-                        Node::LET(ident_name.clone(), Box::new(Node::INTEGER(val)));
+                if let Some(folded) = onode {
+                    self.functions[func_idx].stmts[i] = Node::new(
+                        NodeKind::LET(ident_name.clone(), Box::new(folded)),
+                        n.pos.clone(),
+                    );
                 }
             }
-            Node::ASSIGN(ident, bexpr) => {
+            NodeKind::ASSIGN(ident, bexpr) => {
                 let onode: Option<Node> = self.fold_expr(*bexpr.clone());
-                if let Some(Node::INTEGER(val)) = onode {
-                    self.functions[func_idx].stmts[i] =
-                        Node::ASSIGN(ident.clone(), Box::new(Node::INTEGER(val)));
+                if let Some(folded) = onode {
+                    self.functions[func_idx].stmts[i] = Node::new(
+                        NodeKind::ASSIGN(ident.clone(), Box::new(folded)),
+                        n.pos.clone(),
+                    );
                 }
             }
-            Node::RETURN(bexpr) => {
+            NodeKind::ASSIGNOP(ident, op, bexpr) => {
                 let onode: Option<Node> = self.fold_expr(*bexpr.clone());
-                if let Some(Node::INTEGER(val)) = onode {
-                    self.functions[func_idx].stmts[i] = Node::RETURN(Box::new(Node::INTEGER(val)));
+                if let Some(folded) = onode {
+                    self.functions[func_idx].stmts[i] = Node::new(
+                        NodeKind::ASSIGNOP(ident.clone(), op.clone(), Box::new(folded)),
+                        n.pos.clone(),
+                    );
+                }
+            }
+            NodeKind::RETURN(bexpr) => {
+                let onode: Option<Node> = self.fold_expr(*bexpr.clone());
+                if let Some(folded) = onode {
+                    self.functions[func_idx].stmts[i] =
+                        Node::new(NodeKind::RETURN(Box::new(folded)), n.pos.clone());
                 }
             }
             _ => (),
         }
     }
     fn fold_expr(&mut self, n: Node) -> Option<Node> {
-        match n {
-            Node::INTEGER(val) => Some(Node::INTEGER(val)),
-            Node::ADD(lch, rch) => {
+        let pos = n.pos;
+        match n.kind {
+            NodeKind::INTEGER(val) => Some(Node::new(NodeKind::INTEGER(val), pos)),
+            NodeKind::ADD(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval + rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval + rval), pos));
                 }
                 None
             }
-            Node::SUB(lch, rch) => {
+            NodeKind::SUB(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval - rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval - rval), pos));
                 }
                 None
             }
-            Node::MUL(lch, rch) => {
+            NodeKind::MUL(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval * rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval * rval), pos));
                 }
                 None
             }
-            Node::DIV(lch, rch) => {
+            NodeKind::DIV(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval / rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval / rval), pos));
                 }
                 None
             }
-            Node::MOD(lch, rch) => {
+            NodeKind::MOD(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval % rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval % rval), pos));
                 }
                 None
             }
-            Node::EQ(lch, rch) => {
+            NodeKind::EQ(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval == rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval == rval), pos));
                 }
                 None
             }
-            Node::NTEQ(lch, rch) => {
+            NodeKind::NTEQ(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval != rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval != rval), pos));
                 }
                 None
             }
-            Node::LT(lch, rch) => {
+            NodeKind::LT(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval < rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval < rval), pos));
                 }
                 None
             }
-            Node::GT(lch, rch) => {
+            NodeKind::GT(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval > rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval > rval), pos));
                 }
                 None
             }
-            Node::LTEQ(lch, rch) => {
+            NodeKind::LTEQ(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval <= rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval <= rval), pos));
                 }
                 None
             }
-            Node::GTEQ(lch, rch) => {
+            NodeKind::GTEQ(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval >= rval) as i128));
+                    return Some(Node::new(NodeKind::BOOLEAN(lval >= rval), pos));
                 }
                 None
             }
-            Node::LSHIFT(lch, rch) => {
+            NodeKind::LSHIFT(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER((lval << rval) as i128));
+                    return Some(Node::new(NodeKind::INTEGER((lval << rval) as i128), pos));
                 }
                 None
             }
-            Node::RSHIFT(lch, rch) => {
+            NodeKind::RSHIFT(lch, rch) => {
                 if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
-                    return Some(Node::INTEGER(lval >> rval));
+                    return Some(Node::new(NodeKind::INTEGER(lval >> rval), pos));
+                }
+                None
+            }
+            NodeKind::BOOLEAN(val) => Some(Node::new(NodeKind::BOOLEAN(val), pos)),
+            NodeKind::LOGAND(lch, rch) => {
+                if let Some((lval, rval)) = self.check_valid_bool(*lch, *rch) {
+                    return Some(Node::new(NodeKind::BOOLEAN(lval && rval), pos));
+                }
+                None
+            }
+            NodeKind::LOGOR(lch, rch) => {
+                if let Some((lval, rval)) = self.check_valid_bool(*lch, *rch) {
+                    return Some(Node::new(NodeKind::BOOLEAN(lval || rval), pos));
                 }
                 None
             }
-            Node::MINUS(lch) => {
-                if let Some(Node::INTEGER(lval)) = self.fold_expr(*lch) {
-                    return Some(Node::INTEGER(-lval));
+            NodeKind::NOT(lch) => {
+                if let Some(Node {
+                    kind: NodeKind::BOOLEAN(lval),
+                    ..
+                }) = self.fold_expr(*lch)
+                {
+                    Some(Node::new(NodeKind::BOOLEAN(!lval), pos))
+                } else {
+                    None
+                }
+            }
+            NodeKind::BITAND(lch, rch) => {
+                if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
+                    return Some(Node::new(NodeKind::INTEGER(lval & rval), pos));
+                }
+                None
+            }
+            NodeKind::BITOR(lch, rch) => {
+                if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
+                    return Some(Node::new(NodeKind::INTEGER(lval | rval), pos));
+                }
+                None
+            }
+            NodeKind::BITXOR(lch, rch) => {
+                if let Some((lval, rval)) = self.check_valid(*lch, *rch) {
+                    return Some(Node::new(NodeKind::INTEGER(lval ^ rval), pos));
+                }
+                None
+            }
+            NodeKind::BITNOT(lch) => {
+                if let Some(Node {
+                    kind: NodeKind::INTEGER(lval),
+                    ..
+                }) = self.fold_expr(*lch)
+                {
+                    Some(Node::new(NodeKind::INTEGER(!lval), pos))
+                } else {
+                    None
+                }
+            }
+            NodeKind::MINUS(lch) => {
+                if let Some(Node {
+                    kind: NodeKind::INTEGER(lval),
+                    ..
+                }) = self.fold_expr(*lch)
+                {
+                    return Some(Node::new(NodeKind::INTEGER(-lval), pos));
                 } else {
                     return None;
                 }
@@ -135,8 +219,16 @@ impl FrontManager {
         }
     }
     fn check_valid(&mut self, lhs: Node, rhs: Node) -> Option<(i128, i128)> {
-        if let Some(Node::INTEGER(lval)) = self.fold_expr(lhs) {
-            if let Some(Node::INTEGER(rval)) = self.fold_expr(rhs) {
+        if let Some(Node {
+            kind: NodeKind::INTEGER(lval),
+            ..
+        }) = self.fold_expr(lhs)
+        {
+            if let Some(Node {
+                kind: NodeKind::INTEGER(rval),
+                ..
+            }) = self.fold_expr(rhs)
+            {
                 return Some((lval, rval));
             } else {
                 return None;
@@ -145,4 +237,19 @@ impl FrontManager {
             return None;
         }
     }
+    fn check_valid_bool(&mut self, lhs: Node, rhs: Node) -> Option<(bool, bool)> {
+        match (self.fold_expr(lhs), self.fold_expr(rhs)) {
+            (
+                Some(Node {
+                    kind: NodeKind::BOOLEAN(lval),
+                    ..
+                }),
+                Some(Node {
+                    kind: NodeKind::BOOLEAN(rval),
+                    ..
+                }),
+            ) => Some((lval, rval)),
+            _ => None,
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use crate::ce::types::Error;
 use crate::compile::frontend;
-use frontend::frontmanager::frontmanager::{Env, FrontManager, Symbol};
-use frontend::parse::node::{Func, Node};
-use frontend::token::token::Token;
+use frontend::frontmanager::frontmanager::{Env, FrontManager, Loop, Symbol};
+use frontend::parse::node::{Func, MatchArm, Node, NodeKind, Pattern};
+use frontend::sema::builtin::BUILTINS;
+use frontend::token::token::{Position, Token};
 
 use std::collections::BTreeMap;
 
@@ -11,81 +12,277 @@ type TotalSize = usize;
 type PointerTo = Box<Type>;
 type Elem = Box<Type>;
 type Alias = Box<Type>;
+type Signed = bool;
+type BitSize = usize;
+type Variants = Vec<(String, Vec<Type>)>;
+type Params = Vec<Type>;
+type Return = Box<Type>;
 #[derive(Clone, Eq, PartialEq)]
 pub enum Type {
-    INTEGER, // future INTEGER(,bitsize)
+    INTEGER(Signed, BitSize),
+    BOOLEAN,
     POINTER(PointerTo),
     ARRAY(Elem, ArySize),
     UNKNOWN, //DEFTYPE(name,size)
     ALIAS(Alias),
     STRUCT(BTreeMap<String, Symbol>, TotalSize),
+    /* a tagged union, the 8 bytes tag is followed by the payload of the largest variant */
+    ENUM(String, Variants, TotalSize),
+    /* the address of a function, it's called indirectly */
+    FUNC(Params, Return),
+    /* a type parameter, it's substituted when the generic one is instantiated */
+    PARAM(String),
+    /* a struct with type parameters, the members refer them as PARAM */
+    GENERIC(Vec<String>, BTreeMap<String, Symbol>),
 }
 
 impl Type {
     pub fn string(&self) -> String {
         match self {
-            Self::INTEGER => "INT-TYPE".to_string(),
+            Self::INTEGER(signed, bitsize) => {
+                format!("INT-TYPE<{}{}>", if *signed { "i" } else { "u" }, bitsize)
+            }
+            Self::BOOLEAN => "BOOLEAN".to_string(),
             Self::POINTER(inner) => format!("POINTER<{}>", inner.string()),
             Self::ARRAY(elem, len) => format!("ARRAY<{},{}>", elem.string(), len),
             Self::ALIAS(alt) => format!("ALIAS<{}>", alt.string()),
             Self::STRUCT(_members, size) => format!("STRUCT<{}>", size),
+            Self::ENUM(name, _variants, size) => format!("ENUM<{},{}>", name, size),
+            Self::FUNC(params, ret) => format!(
+                "FUNC<({})::{}>",
+                params
+                    .iter()
+                    .map(|p| p.string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                ret.string()
+            ),
+            Self::PARAM(name) => format!("PARAM<{}>", name),
+            Self::GENERIC(params, _members) => format!("GENERIC<{}>", params.join(",")),
             Self::UNKNOWN => "UNKNOWN".to_string(),
         }
     }
+    /* the value as the integer of this width holds it, like 300 -> 44 for u8 */
+    pub fn wrap(&self, value: i128) -> i128 {
+        match self {
+            Self::INTEGER(signed, bitsize) if *bitsize < 64 => {
+                let shift: u32 = 128 - *bitsize as u32;
+                if *signed {
+                    (value << shift) >> shift
+                } else {
+                    value & ((1 << bitsize) - 1)
+                }
+            }
+            _ => value,
+        }
+    }
     pub fn size(&self) -> usize {
         match self {
-            Self::INTEGER => 8,
+            Self::INTEGER(_, bitsize) => bitsize / 8,
+            Self::BOOLEAN => 1,
             Self::POINTER(_innter) => 8,
             Self::ARRAY(elem, len) => elem.size() * len,
             Self::ALIAS(alt) => alt.size(),
             Self::STRUCT(_, size) => *size,
-            Self::UNKNOWN => {
-                Error::TYPE.found(&"can't known size at compile time".to_string());
-                0
+            Self::ENUM(_, _, size) => *size,
+            Self::FUNC(_, _) => 8,
+            /* the cause of the unknown type is reported where it is found. */
+            Self::PARAM(_) | Self::GENERIC(_, _) | Self::UNKNOWN => 0,
+        }
+    }
+    pub fn enum_of(name: String, variants: Variants) -> Self {
+        let payload: usize = variants
+            .iter()
+            .map(|(_, fields)| fields.iter().map(|f| f.size()).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        Self::ENUM(name, variants, 8 + payload)
+    }
+    /* the tag and the field types of the variant */
+    pub fn variant(&self, name: &str) -> Option<(usize, &Vec<Type>)> {
+        if let Self::ENUM(_, variants, _) = self {
+            return variants
+                .iter()
+                .position(|(n, _)| n == name)
+                .map(|tag| (tag, &variants[tag].1));
+        }
+        None
+    }
+    /* the offsets of the fields from the start of the payload */
+    pub fn payload_offsets(fields: &[Type]) -> Vec<usize> {
+        let mut offset: usize = 0;
+        let mut offsets: Vec<usize> = Vec::new();
+        for f in fields.iter() {
+            offsets.push(offset);
+            offset += f.size();
+        }
+        offsets
+    }
+    /* the offset of the member from the bottom of the struct, the members are laid out in order. */
+    pub fn member_offset(&self, member: &str) -> Option<(usize, Type)> {
+        if let Self::STRUCT(members, _) = self {
+            let mut offset: usize = 0;
+            for (name, s) in members.iter() {
+                let ty: Type = s.ty.clone().unwrap_or(Type::UNKNOWN);
+                if name == member {
+                    return Some((offset, ty));
+                }
+                offset += ty.size();
+            }
+        }
+        None
+    }
+    /* the type of the function's address, built from its arguments and return type. */
+    pub fn signature(f: &Func) -> Self {
+        let mut params: Vec<Type> = Vec::new();
+        for arg in f.args.iter() {
+            if let NodeKind::DEFARG(name) = &arg.kind {
+                let param: Type = match f.env.sym_table.get(name).map(|s| s.ty.clone()) {
+                    Some(Ok(ty)) => ty,
+                    Some(Err(type_t)) => Type::from_token_in(type_t, &f.type_params),
+                    None => Type::UNKNOWN,
+                };
+                params.push(param);
             }
         }
+        Self::FUNC(params, Box::new(f.return_type.clone()))
     }
     pub fn from_token(type_t: Token) -> Self {
+        Self::from_token_in(type_t, &[])
+    }
+    /* the names in 'params' are the type parameters of the generic function or struct. */
+    pub fn from_token_in(type_t: Token, params: &[String]) -> Self {
         match type_t {
-            Token::I64 => Type::INTEGER,
+            Token::IDENT(name) if params.contains(&name) => Type::PARAM(name),
+            Token::I8 => Type::INTEGER(true, 8),
+            Token::I16 => Type::INTEGER(true, 16),
+            Token::I32 => Type::INTEGER(true, 32),
+            Token::I64 => Type::INTEGER(true, 64),
+            Token::U8 => Type::INTEGER(false, 8),
+            Token::U16 => Type::INTEGER(false, 16),
+            Token::U32 => Type::INTEGER(false, 32),
+            Token::U64 => Type::INTEGER(false, 64),
+            Token::BOOL => Type::BOOLEAN,
             Token::POINTER(inner) => {
-                let inner_type: Type = Self::from_token(*inner.clone());
+                let inner_type: Type = Self::from_token_in(*inner.clone(), params);
                 Self::POINTER(Box::new(inner_type))
             }
+            Token::FUNCTYPE(args, ret) => Self::FUNC(
+                args.into_iter()
+                    .map(|arg| Self::from_token_in(arg, params))
+                    .collect(),
+                Box::new(Self::from_token_in(*ret, params)),
+            ),
             Token::ARRAY(elem, size) => {
-                let elem_type: Type = Self::from_token(*elem.clone());
+                let elem_type: Type = Self::from_token_in(*elem.clone(), params);
                 if let Token::INTEGER(ary_size) = *size.clone() {
                     return Self::ARRAY(Box::new(elem_type), ary_size as usize);
                 }
-                Error::TYPE.found(&"array size is known at compile time.".to_string());
                 Self::UNKNOWN
             }
             _ => Type::UNKNOWN,
         }
     }
+    /* replace the type parameters with the type arguments */
+    pub fn substitute(&self, args: &BTreeMap<String, Type>) -> Self {
+        match self {
+            Self::PARAM(name) => args.get(name).cloned().unwrap_or(Self::UNKNOWN),
+            Self::POINTER(inner) => Self::POINTER(Box::new(inner.substitute(args))),
+            Self::ARRAY(elem, len) => Self::ARRAY(Box::new(elem.substitute(args)), *len),
+            Self::FUNC(params, ret) => Self::FUNC(
+                params.iter().map(|p| p.substitute(args)).collect(),
+                Box::new(ret.substitute(args)),
+            ),
+            _ => self.clone(),
+        }
+    }
+    /* the type written in the source, a type argument is substituted with this */
+    pub fn token(&self) -> Option<Token> {
+        match self {
+            Self::INTEGER(true, 8) => Some(Token::I8),
+            Self::INTEGER(true, 16) => Some(Token::I16),
+            Self::INTEGER(true, 32) => Some(Token::I32),
+            Self::INTEGER(true, 64) => Some(Token::I64),
+            Self::INTEGER(false, 8) => Some(Token::U8),
+            Self::INTEGER(false, 16) => Some(Token::U16),
+            Self::INTEGER(false, 32) => Some(Token::U32),
+            Self::INTEGER(false, 64) => Some(Token::U64),
+            Self::BOOLEAN => Some(Token::BOOL),
+            Self::POINTER(inner) => Some(Token::POINTER(Box::new(inner.token()?))),
+            Self::ARRAY(elem, len) => Some(Token::ARRAY(
+                Box::new(elem.token()?),
+                Box::new(Token::INTEGER(*len as i128)),
+            )),
+            Self::FUNC(params, ret) => Some(Token::FUNCTYPE(
+                params
+                    .iter()
+                    .map(|p| p.token())
+                    .collect::<Option<Vec<Token>>>()?,
+                Box::new(ret.token()?),
+            )),
+            Self::ALIAS(alt) => alt.token(),
+            _ => None,
+        }
+    }
+    /* the part of an instance's name, like 'i64' in 'max.i64' */
+    pub fn mangle(&self) -> String {
+        match self {
+            Self::INTEGER(signed, bitsize) => {
+                format!("{}{}", if *signed { "i" } else { "u" }, bitsize)
+            }
+            Self::BOOLEAN => "bool".to_string(),
+            Self::POINTER(inner) => format!("ptr_{}", inner.mangle()),
+            Self::ARRAY(elem, len) => format!("ary{}_{}", len, elem.mangle()),
+            Self::FUNC(params, ret) => format!(
+                "func_{}_{}",
+                params
+                    .iter()
+                    .map(|p| p.mangle())
+                    .collect::<Vec<String>>()
+                    .join("_"),
+                ret.mangle()
+            ),
+            Self::ALIAS(alt) => alt.mangle(),
+            _ => "unknown".to_string(),
+        }
+    }
 }
 
 impl FrontManager {
     pub fn semantics(&mut self) {
-        let func_num: usize = self.functions.len();
+        self.check_globals();
         let mut idx: usize = 0;
         loop {
-            if idx == func_num {
+            /* the instances of the generic functions are appended while walking. */
+            if idx == self.functions.len() {
                 break;
             }
             let f: Func = self.functions[idx].clone();
+            if !f.type_params.is_empty() {
+                idx += 1;
+                continue;
+            }
+            if f.name == "main" {
+                self.check_main(&f);
+            }
+            self.cur_func = f.name.clone();
             self.cur_env = f.env.clone();
+            self.cur_scopes = f.scopes.clone();
             for arg in f.args {
-                if let Node::DEFARG(name) = arg {
+                if let NodeKind::DEFARG(name) = arg.kind {
                     if let Some(ref mut s) = self.cur_env.sym_table.get_mut(&name) {
                         let res_ty = s.ty.clone();
                         if let Err(type_t) = res_ty {
-                            s.ty = Ok(Type::from_token(type_t));
+                            s.ty = Ok(Self::arg_type(&f.env, type_t));
                         }
                         self.stack_offset += s.size();
                         s.stack_offset = self.stack_offset;
                     } else {
-                        Error::UNDEFINED.found(&format!("{} is not defined", name));
+                        self.diag.error(
+                            Error::UNDEFINED,
+                            &arg.pos,
+                            &format!("{} is not defined", name),
+                        );
                     }
                 }
             }
@@ -93,71 +290,271 @@ impl FrontManager {
                 self.walk(n);
             }
             self.functions[idx].env = self.cur_env.clone();
+            self.functions[idx].scopes = self.cur_scopes.clone();
             idx += 1;
         }
+        /* the frame must hold the deepest scope. */
+        if self.stack_offset < self.stack_peak {
+            self.stack_offset = self.stack_peak;
+        }
+    }
+    /* a global takes a quad word in .data or .bss, the narrower integers are kept wrapped in it */
+    fn check_globals(&mut self) {
+        for g in self.globals.clone().iter() {
+            let init_type: Type = self.walk(g.init.clone());
+            match (&g.ty, &init_type) {
+                (Type::INTEGER(_, _), Type::INTEGER(_, _)) | (Type::BOOLEAN, Type::BOOLEAN) => (),
+                (Type::INTEGER(_, _), _) | (Type::BOOLEAN, _) => {
+                    self.diag.error(
+                        Error::TYPE,
+                        &g.init.pos,
+                        &format!(
+                            "can't initialize global '{}' of {} with {}",
+                            g.name,
+                            g.ty.string(),
+                            init_type.string()
+                        ),
+                    );
+                }
+                _ => {
+                    self.diag.error(
+                        Error::TYPE,
+                        &g.init.pos,
+                        &format!(
+                            "global '{}' must be an integer or bool but got {}",
+                            g.name,
+                            g.ty.string()
+                        ),
+                    );
+                }
+            }
+        }
     }
-    fn walk(&mut self, n: Node) -> Type {
-        match n {
-            Node::LET(ident_name, bexpr) => {
+    pub fn walk(&mut self, n: Node) -> Type {
+        match n.kind {
+            NodeKind::LET(ident_name, bexpr) => {
+                let inferred: bool = matches!(
+                    self.get_symbol(&ident_name).map(|s| s.ty),
+                    Some(Err(Token::EOF))
+                );
+                let errors: usize = self.diag.errors;
                 let expr_type: Type = self.walk(*bexpr.clone());
+                /* the initializer reported its error already if it has. */
+                if inferred && errors == self.diag.errors {
+                    self.check_inferred(&ident_name, &n.pos, &expr_type);
+                }
+                self.check_enum_init(&ident_name, &bexpr, &expr_type);
+                self.check_func_init(&ident_name, &bexpr.pos, &expr_type);
+                let declared_members: BTreeMap<String, Type> =
+                    self.check_struct_init(&ident_name, &bexpr, &expr_type);
+                if self.warn_shadow {
+                    /* the outer one is declared already if sema has resolved its type. */
+                    if let Some(outer) = &self.cur_env.prev {
+                        if outer.lookup(&ident_name).is_some_and(|s| s.ty.is_ok()) {
+                            self.diag.warning(
+                                &n.pos,
+                                &format!(
+                                    "'{}' shadows a variable in an enclosing scope",
+                                    ident_name
+                                ),
+                            );
+                        }
+                    }
+                }
                 if let Some(ref mut s) = self.cur_env.sym_table.get_mut(&ident_name) {
                     if let Type::ARRAY(_, _) = expr_type {
                         s.ty = Ok(expr_type.clone());
-                    } else if let Type::STRUCT(ref mut member_map, ref mut _totalsize) =
-                        expr_type.clone()
-                    {
-                        s.ty = Ok(expr_type.clone());
-                        self.stack_offset += s.size();
+                    } else if let Type::STRUCT(ref mut member_map, _) = expr_type.clone() {
+                        /* the first member is at the bottom, so '&a' points it. */
                         let mut totalsize: usize = 0;
-                        for (_member_name, member_s) in member_map.iter_mut() {
-                            member_s.stack_offset = self.stack_offset - totalsize;
+                        for (member_name, member_s) in member_map.iter_mut() {
+                            if let (Some(Type::INTEGER(signed, bitsize)), Ok(Type::INTEGER(_, _))) =
+                                (declared_members.get(member_name), &member_s.ty)
+                            {
+                                member_s.ty = Ok(Type::INTEGER(*signed, *bitsize));
+                            }
                             totalsize += member_s.size();
                         }
+                        self.stack_offset += totalsize;
+                        let mut offset: usize = 0;
+                        for member_s in member_map.values_mut() {
+                            member_s.stack_offset = self.stack_offset - offset;
+                            offset += member_s.size();
+                        }
+                        s.ty = Ok(Type::STRUCT(member_map.clone(), totalsize));
                     } else {
-                        s.ty = Ok(expr_type.clone());
+                        /* integers are converted implicitly, so the annotation decides the width.
+                        the pointer from 'alloc' points whatever the annotation says. */
+                        let declared_type: Type = match &s.ty {
+                            Ok(ty) => ty.clone(),
+                            Err(type_t) => Type::from_token(type_t.clone()),
+                        };
+                        if let (Type::INTEGER(_, _), Type::INTEGER(_, _))
+                        | (Type::POINTER(_), Type::POINTER(_)) = (&declared_type, &expr_type)
+                        {
+                            s.ty = Ok(declared_type);
+                        } else {
+                            s.ty = Ok(expr_type.clone());
+                        }
                         self.stack_offset += s.size();
                     }
                     s.stack_offset = self.stack_offset;
                 }
                 expr_type
             }
-            Node::ASSIGN(ident, bexpr) => {
+            NodeKind::ASSIGN(ident, bexpr) => {
                 let expr_type: Type = self.walk(*bexpr.clone());
+                self.check_enum_init(&ident, &bexpr, &expr_type);
+                self.check_func_init(&ident, &bexpr.pos, &expr_type);
+                if let Some(s) = self.get_symbol(&ident) {
+                    if !s.is_mutable {
+                        self.diag.error(
+                            Error::TYPE,
+                            &n.pos,
+                            &format!(
+                                "can't assign {} into '{}' it's not mutable",
+                                expr_type.string(),
+                                ident
+                            ),
+                        );
+                    }
+                } else {
+                    self.diag.error(
+                        Error::UNDEFINED,
+                        &n.pos,
+                        &format!("{} is not defined", ident),
+                    );
+                }
+                expr_type
+            }
+            NodeKind::ASSIGNOP(ident, op, bexpr) => {
+                /* reading the target reports it if it's not defined */
+                let value: Node = Node::compound_value(&ident, &op, &bexpr, &n.pos);
+                let expr_type: Type = self.walk(value);
                 if let Some(s) = self.get_symbol(&ident) {
                     if !s.is_mutable {
-                        Error::TYPE.found(&format!(
-                            "can't assign {} into '{}' it's not mutable",
-                            expr_type.string(),
-                            ident
-                        ));
+                        self.diag.error(
+                            Error::TYPE,
+                            &n.pos,
+                            &format!(
+                                "can't assign {} into '{}' it's not mutable",
+                                expr_type.string(),
+                                ident
+                            ),
+                        );
                     }
                 }
                 expr_type
             }
-            Node::RETURN(bexpr) => {
+            NodeKind::RETURN(bexpr) => {
                 let expr_type: Type = self.walk(*bexpr.clone());
                 expr_type
             }
-            Node::INDEX(rec, ind) => {
+            NodeKind::IF(bcond, bblock, balter) => {
+                self.walk(*bcond);
+                self.walk(*bblock);
+                if let Some(alter) = balter {
+                    self.walk(*alter);
+                }
+                Type::UNKNOWN
+            }
+            NodeKind::BLOCK(stmts, scope) => {
+                /* the block sees the enclosing scopes as resolved so far. */
+                let mut env: Env = self.cur_scopes[scope].clone();
+                env.prev = Some(Box::new(self.cur_env.clone()));
+                let outer: Env = std::mem::replace(&mut self.cur_env, env);
+                let stack_offset: usize = self.stack_offset;
+                for st in stmts.iter() {
+                    self.walk(st.clone());
+                }
+                /* the next disjoint scope reuses the slots. */
+                if self.stack_peak < self.stack_offset {
+                    self.stack_peak = self.stack_offset;
+                }
+                self.stack_offset = stack_offset;
+                self.cur_scopes[scope] = std::mem::replace(&mut self.cur_env, outer);
+                Type::UNKNOWN
+            }
+            NodeKind::MATCH(bexpr, arms) => {
+                self.walk_match(*bexpr, *arms, &n.pos);
+                Type::UNKNOWN
+            }
+            NodeKind::CONDLOOP(bcond, bblock, label) => {
+                self.walk(*bcond);
+                self.loops
+                    .push(Loop::new(label, String::new(), String::new()));
+                self.walk(*bblock);
+                self.loops.pop();
+                Type::UNKNOWN
+            }
+            NodeKind::FOR(ident_name, bbegin, bend, bblock, label) => {
+                for bound in [*bbegin, *bend].iter() {
+                    let bound_type: Type = self.walk(bound.clone());
+                    if let Type::INTEGER(_, _) = bound_type {
+                    } else {
+                        self.diag.error(
+                            Error::TYPE,
+                            &bound.pos,
+                            &format!(
+                                "range bound must be integer but got {}",
+                                bound_type.string()
+                            ),
+                        );
+                    }
+                }
+                if let Some(ref mut s) = self.cur_env.sym_table.get_mut(&ident_name) {
+                    s.ty = Ok(Type::INTEGER(true, 64));
+                    self.stack_offset += s.size();
+                    s.stack_offset = self.stack_offset;
+                }
+                self.loops
+                    .push(Loop::new(label, String::new(), String::new()));
+                self.walk(*bblock);
+                self.loops.pop();
+                Type::UNKNOWN
+            }
+            NodeKind::BREAK(label) => {
+                self.check_loop_jump("break", &label, &n.pos);
+                Type::UNKNOWN
+            }
+            NodeKind::CONTINUE(label) => {
+                self.check_loop_jump("continue", &label, &n.pos);
+                Type::UNKNOWN
+            }
+            NodeKind::INDEX(rec, ind) => {
                 let array_type: Type = self.walk(*rec.clone());
                 let index_type: Type = self.walk(*ind.clone());
-                if let Type::INTEGER = &index_type {
+                if let Type::INTEGER(_, _) = &index_type {
                 } else {
-                    Error::TYPE.found(&format!(
-                        "must be integer-type in index but got {}",
-                        index_type.string()
-                    ));
+                    self.diag.error(
+                        Error::TYPE,
+                        &n.pos,
+                        &format!(
+                            "must be integer-type in index but got {}",
+                            index_type.string()
+                        ),
+                    );
                 }
 
                 if let Type::ARRAY(elem_type, _) = array_type {
                     *elem_type.clone()
+                } else if let Type::POINTER(inner) = array_type {
+                    *inner
                 } else {
-                    Error::TYPE.found(&format!("can't indexing {} it's not array ", rec.string()));
+                    self.diag.error(
+                        Error::TYPE,
+                        &n.pos,
+                        &format!("can't indexing {} it's not array or pointer ", rec.string()),
+                    );
                     Type::UNKNOWN
                 }
             }
-            Node::MEMBER(ident, member) => {
-                let struct_type: Type = self.walk(*ident.clone());
+            NodeKind::MEMBER(ident, member) => {
+                /* the member is read through the pointer, like 'self.x' in a method */
+                let struct_type: Type = match self.walk(*ident.clone()) {
+                    Type::POINTER(inner) => *inner,
+                    ty => ty,
+                };
                 if let Type::STRUCT(map, _) = struct_type {
                     if let Some(member_s) = map.get(&member) {
                         if let Ok(member_type) = &member_s.ty {
@@ -167,27 +564,37 @@ impl FrontManager {
                 }
                 Type::UNKNOWN
             }
-            Node::ADDRESS(lch) => {
+            NodeKind::ADDRESS(lch) => {
                 let ident_node = *lch.clone();
-                if let Node::IDENT(_) = &ident_node {
+                if let NodeKind::IDENT(name) = &ident_node.kind {
+                    if self.get_symbol(name).is_some_and(|s| s.is_global) {
+                        self.diag.error(
+                            Error::TYPE,
+                            &n.pos,
+                            &format!("can't address global '{}'", name),
+                        );
+                        return Type::UNKNOWN;
+                    }
                     Type::POINTER(Box::new(self.walk(ident_node)))
                 } else {
-                    Error::TYPE.found(&format!("can't address without ident"));
+                    self.diag
+                        .error(Error::TYPE, &n.pos, &format!("can't address without ident"));
                     Type::UNKNOWN
                 }
             }
-            Node::DEREFERENCE(lch) => {
+            NodeKind::DEREFERENCE(lch) => {
                 let lch_type: Type = self.walk(*lch.clone());
                 if let Type::POINTER(inner) = &lch_type {
                     return *inner.clone();
                 }
-                Error::TYPE.found(&format!(
-                    "can't dereference {} it's not pointer ",
-                    lch_type.string(),
-                ));
+                self.diag.error(
+                    Error::TYPE,
+                    &n.pos,
+                    &format!("can't dereference {} it's not pointer ", lch_type.string(),),
+                );
                 Type::UNKNOWN
             }
-            Node::IDENT(name) => {
+            NodeKind::IDENT(name) => {
                 if let Some(s) = self.get_symbol(&name) {
                     if let Ok(ty) = s.ty {
                         if let Type::ALIAS(alt) = ty {
@@ -201,11 +608,18 @@ impl FrontManager {
                         Type::UNKNOWN
                     }
                 } else {
+                    self.diag.error(
+                        Error::UNDEFINED,
+                        &n.pos,
+                        &format!("{} is not defined", name),
+                    );
                     Type::UNKNOWN
                 }
             }
-            Node::INTEGER(_val) => Type::INTEGER,
-            Node::ARRAYLIT(elems, name) => {
+            NodeKind::INTEGER(_val) => Type::INTEGER(true, 64),
+            NodeKind::BOOLEAN(_val) => Type::BOOLEAN,
+            NodeKind::STRLIT(_contents) => Type::POINTER(Box::new(Type::INTEGER(false, 8))),
+            NodeKind::ARRAYLIT(elems, name) => {
                 let mut elem_type: Type = Type::UNKNOWN;
                 let length: usize = elems.len();
                 for elem in elems.iter() {
@@ -214,59 +628,130 @@ impl FrontManager {
                         elem_type = cur_type;
                     } else {
                         if elem_type != cur_type {
-                            Error::TYPE.found(&format!(
-                                "type difference between {} - {} in arraylit",
-                                elem_type.string(),
-                                cur_type.string()
-                            ));
+                            self.diag.error(
+                                Error::TYPE,
+                                &n.pos,
+                                &format!(
+                                    "type difference between {} - {} in arraylit",
+                                    elem_type.string(),
+                                    cur_type.string()
+                                ),
+                            );
                         }
                         elem_type = cur_type;
                     }
                 }
                 if let Some(ref mut array) = self.cur_env.sym_table.get_mut(&name) {
+                    if let Err(Token::ARRAY(elem_t, _)) = &array.ty {
+                        let declared_type: Type = Type::from_token(*elem_t.clone());
+                        if let (Type::INTEGER(_, _), Type::INTEGER(_, _)) =
+                            (&declared_type, &elem_type)
+                        {
+                            elem_type = declared_type;
+                        }
+                    }
                     self.stack_offset += elem_type.size() * length;
                     array.stack_offset = self.stack_offset;
                     array.ty = Ok(Type::ARRAY(Box::new(elem_type.clone()), length));
                 }
                 Type::ARRAY(Box::new(elem_type), length)
             }
-            Node::ADD(lch, rch)
-            | Node::SUB(lch, rch)
-            | Node::MUL(lch, rch)
-            | Node::DIV(lch, rch)
-            | Node::MOD(lch, rch)
-            | Node::EQ(lch, rch)
-            | Node::NTEQ(lch, rch)
-            | Node::LT(lch, rch)
-            | Node::GT(lch, rch)
-            | Node::LTEQ(lch, rch)
-            | Node::GTEQ(lch, rch)
-            | Node::LSHIFT(lch, rch)
-            | Node::RSHIFT(lch, rch) => {
+            /* a pointer moves by the integer, the distance of two pointers is an integer. */
+            NodeKind::ADD(lch, rch) => match (self.walk(*lch), self.walk(*rch)) {
+                (Type::INTEGER(_, _), rch_type @ Type::POINTER(_)) => rch_type,
+                (lch_type, _) => lch_type,
+            },
+            NodeKind::SUB(lch, rch) => match (self.walk(*lch), self.walk(*rch)) {
+                (Type::POINTER(_), Type::POINTER(_)) => Type::INTEGER(true, 64),
+                (lch_type, _) => lch_type,
+            },
+            NodeKind::MUL(lch, rch)
+            | NodeKind::DIV(lch, rch)
+            | NodeKind::MOD(lch, rch)
+            | NodeKind::LSHIFT(lch, rch)
+            | NodeKind::RSHIFT(lch, rch)
+            | NodeKind::BITAND(lch, rch)
+            | NodeKind::BITOR(lch, rch)
+            | NodeKind::BITXOR(lch, rch) => {
                 let lch_type: Type = self.walk(*lch.clone());
                 let rch_type: Type = self.walk(*rch.clone());
                 if lch_type != lch_type {
-                    Error::TYPE.found(&format!(
-                        "type difference between {} - {} ",
-                        lch_type.string(),
-                        rch_type.string()
-                    ));
+                    self.diag.error(
+                        Error::TYPE,
+                        &n.pos,
+                        &format!(
+                            "type difference between {} - {} ",
+                            lch_type.string(),
+                            rch_type.string()
+                        ),
+                    );
                     return Type::UNKNOWN;
                 }
                 lch_type
             }
-            Node::MINUS(lch) => {
+            NodeKind::EQ(lch, rch)
+            | NodeKind::NTEQ(lch, rch)
+            | NodeKind::LT(lch, rch)
+            | NodeKind::GT(lch, rch)
+            | NodeKind::LTEQ(lch, rch)
+            | NodeKind::GTEQ(lch, rch) => {
+                self.walk(*lch.clone());
+                self.walk(*rch.clone());
+                Type::BOOLEAN
+            }
+            NodeKind::LOGAND(lch, rch) | NodeKind::LOGOR(lch, rch) => {
+                for ch in [lch, rch].iter() {
+                    let ch_type: Type = self.walk(*ch.clone());
+                    if ch_type != Type::BOOLEAN {
+                        self.diag.error(
+                            Error::TYPE,
+                            &ch.pos,
+                            &format!(
+                                "logical operand must be boolean but got {}",
+                                ch_type.string()
+                            ),
+                        );
+                    }
+                }
+                Type::BOOLEAN
+            }
+            NodeKind::NOT(lch) => {
+                let lch_type: Type = self.walk(*lch.clone());
+                if let Type::BOOLEAN = &lch_type {
+                    return lch_type;
+                }
+                self.diag.error(
+                    Error::TYPE,
+                    &n.pos,
+                    &format!("can't invert {} it's not boolean ", lch_type.string(),),
+                );
+                Type::UNKNOWN
+            }
+            NodeKind::BITNOT(lch) => {
+                let lch_type: Type = self.walk(*lch.clone());
+                if let Type::INTEGER(_, _) = &lch_type {
+                    return lch_type;
+                }
+                self.diag.error(
+                    Error::TYPE,
+                    &n.pos,
+                    &format!("can't complement {} it's not integer ", lch_type.string(),),
+                );
+                Type::UNKNOWN
+            }
+            NodeKind::MINUS(lch) => {
                 let lch_type: Type = self.walk(*lch.clone());
-                if let Type::INTEGER = &lch_type {
-                    return Type::INTEGER;
+                if let Type::INTEGER(_, _) = &lch_type {
+                    return lch_type;
                 }
-                Error::TYPE.found(&format!(
-                    "can't negative {} it's not integer ",
-                    lch_type.string(),
-                ));
+                self.diag.error(
+                    Error::TYPE,
+                    &n.pos,
+                    &format!("can't negative {} it's not integer ", lch_type.string(),),
+                );
                 Type::UNKNOWN
             }
-            Node::STRUCTLIT(_type_name, members) => {
+            NodeKind::STRUCTLIT(_type_name, members) => {
                 let mut total_size: usize = self.stack_offset;
                 let mut map: BTreeMap<String, Symbol> = BTreeMap::new();
                 for (member_name, member_expr) in members.iter() {
@@ -279,30 +764,491 @@ impl FrontManager {
                 }
                 Type::STRUCT(map, total_size)
             }
-            Node::CALL(func_name, _) => {
-                for f in self.functions.iter() {
-                    if f.name == func_name {
-                        return f.return_type.clone();
+            NodeKind::ENUMLIT(enum_name, variant, args, _target) => {
+                let enum_type: Type = match self.cur_env.lookup_type(&enum_name) {
+                    Some(ty) => ty.clone(),
+                    None => return Type::UNKNOWN,
+                };
+                let fields: Vec<Type> = match enum_type.variant(&variant) {
+                    Some((_, fields)) => fields.clone(),
+                    None => {
+                        self.diag.error(
+                            Error::UNDEFINED,
+                            &n.pos,
+                            &format!("no variant '{}' in enum '{}'", variant, enum_name),
+                        );
+                        return Type::UNKNOWN;
                     }
+                };
+                if fields.len() != args.len() {
+                    self.diag.error(
+                        Error::TYPE,
+                        &n.pos,
+                        &format!(
+                            "'{}::{}' takes {} fields but {} given",
+                            enum_name,
+                            variant,
+                            fields.len(),
+                            args.len()
+                        ),
+                    );
+                }
+                for (field, arg) in fields.iter().zip(args.iter()) {
+                    let arg_type: Type = self.walk(arg.clone());
+                    if std::mem::discriminant(field) != std::mem::discriminant(&arg_type) {
+                        self.diag.error(
+                            Error::TYPE,
+                            &arg.pos,
+                            &format!(
+                                "the field of '{}::{}' is {} but got {}",
+                                enum_name,
+                                variant,
+                                field.string(),
+                                arg_type.string()
+                            ),
+                        );
+                    }
+                }
+                enum_type
+            }
+            NodeKind::FUNCADDR(func_name) if self.find_template(&func_name).is_some() => {
+                self.diag.error(
+                    Error::TYPE,
+                    &n.pos,
+                    &format!(
+                        "can't take the address of the generic function '{}'",
+                        func_name.replace('.', "::")
+                    ),
+                );
+                Type::UNKNOWN
+            }
+            NodeKind::FUNCADDR(func_name) => match self.signature_of(&func_name) {
+                Some(func_type) => func_type,
+                None => {
+                    self.diag.error(
+                        Error::UNDEFINED,
+                        &n.pos,
+                        &format!(
+                            "not found such a function -> {}",
+                            func_name.replace('.', "::")
+                        ),
+                    );
+                    Type::UNKNOWN
+                }
+            },
+            NodeKind::CALLPTR(callee, args) => {
+                let callee_type: Type = self.walk(*callee.clone());
+                let (params, ret) = match callee_type {
+                    Type::FUNC(params, ret) => (params, ret),
+                    _ => {
+                        self.diag.error(
+                            Error::TYPE,
+                            &n.pos,
+                            &format!("can't call {} it's not function", callee_type.string()),
+                        );
+                        return Type::UNKNOWN;
+                    }
+                };
+                if params.len() != args.len() {
+                    let callee_name: String = match &callee.kind {
+                        NodeKind::IDENT(name) | NodeKind::MEMBER(_, name) => name.to_string(),
+                        _ => callee.string(),
+                    };
+                    self.diag.error(
+                        Error::TYPE,
+                        &n.pos,
+                        &format!(
+                            "'{}' takes {} arguments but {} given",
+                            callee_name,
+                            params.len(),
+                            args.len()
+                        ),
+                    );
+                }
+                for (param, arg) in params.iter().zip(args.iter()) {
+                    let arg_type: Type = self.walk(arg.clone());
+                    if std::mem::discriminant(param) != std::mem::discriminant(&arg_type) {
+                        self.diag.error(
+                            Error::TYPE,
+                            &arg.pos,
+                            &format!(
+                                "expected {} in the argument but got {}",
+                                param.string(),
+                                arg_type.string()
+                            ),
+                        );
+                    }
+                }
+                *ret
+            }
+            NodeKind::CALL(func_name, args) => {
+                if let Some(template) = self.find_template(&func_name) {
+                    return self.walk_generic_call(template, &args, &n.pos);
+                }
+                if let Some(Type::FUNC(params, ret)) = self.signature_of(&func_name) {
+                    self.check_pointer_args(&params, &args);
+                    return *ret;
+                }
+                if BUILTINS.contains(&func_name.as_str()) {
+                    return self.walk_builtin_call(&func_name, &args, &n.pos);
+                }
+                /* only the names in a module are mangled, so it must be defined there. */
+                if func_name.contains('.') {
+                    self.diag.error(
+                        Error::UNDEFINED,
+                        &n.pos,
+                        &format!(
+                            "not found such a function -> {}",
+                            func_name.replace('.', "::")
+                        ),
+                    );
                 }
                 Type::UNKNOWN
             }
             _ => Type::UNKNOWN,
         }
     }
-    pub fn get_symbol(&self, name: &String) -> Option<Symbol> {
-        let mut env: Env = self.cur_env.clone();
-        loop {
-            if let None = env.prev {
-                if let Some(s) = env.sym_table.get(name) {
-                    return Some(s.clone());
+    /* 'Pointer<A>' refers the struct declared as 'A', so the members are read through it. */
+    fn arg_type(env: &Env, type_t: Token) -> Type {
+        /* an enum parameter is matched on, so its variants are needed */
+        if let Token::IDENT(type_name) = &type_t {
+            if let Some(enum_type @ Type::ENUM(_, _, _)) = env.lookup_type(type_name) {
+                return enum_type.clone();
+            }
+        }
+        if let Token::POINTER(inner) = &type_t {
+            if let Token::IDENT(type_name) = &**inner {
+                if let Some(Type::STRUCT(members, size)) = env.lookup_type(type_name) {
+                    let mut members: BTreeMap<String, Symbol> = members.clone();
+                    for member_s in members.values_mut() {
+                        if let Err(member_t) = &member_s.ty {
+                            member_s.ty = Ok(Type::from_token(member_t.clone()));
+                        }
+                    }
+                    return Type::POINTER(Box::new(Type::STRUCT(members, *size)));
                 }
-                return None;
             }
-            if let Some(s) = env.sym_table.get(name) {
-                return Some(s.clone());
+        }
+        Type::from_token(type_t)
+    }
+    fn signature_of(&self, func_name: &str) -> Option<Type> {
+        self.functions
+            .iter()
+            .chain(self.imported.iter())
+            .find(|f| f.name == func_name)
+            .map(Type::signature)
+    }
+    /* the pointer in the argument must point what the parameter does. */
+    fn check_pointer_args(&mut self, params: &[Type], args: &[Node]) {
+        for (param, arg) in params.iter().zip(args.iter()) {
+            let arg_type: Type = self.walk(arg.clone());
+            if let (Type::POINTER(expected), Type::POINTER(got)) = (param, &arg_type) {
+                if scalar(expected) && scalar(got) && expected != got {
+                    self.diag.error(
+                        Error::TYPE,
+                        &arg.pos,
+                        &format!(
+                            "expected {} in the argument but got {}",
+                            param.string(),
+                            arg_type.string()
+                        ),
+                    );
+                }
             }
-            env = *env.prev.unwrap().clone();
         }
     }
+    /* the startup routine passes 'argc' and 'argv' to main, it may ignore them. */
+    fn check_main(&mut self, f: &Func) {
+        let params: Vec<Type> = match Type::signature(f) {
+            Type::FUNC(params, _) => params,
+            _ => return,
+        };
+        let argv: Type = Type::POINTER(Box::new(Type::POINTER(Box::new(Type::INTEGER(false, 8)))));
+        if params.is_empty() || params == [Type::INTEGER(true, 64), argv] {
+            return;
+        }
+        self.diag.error(
+            Error::TYPE,
+            &f.args[0].pos,
+            "'main' must take no arguments or 'argc : i64, argv : Pointer<Pointer<u8>>'",
+        );
+    }
+    /* the variable without the annotation takes the type of the initializer. */
+    fn check_inferred(&mut self, ident_name: &str, pos: &Position, expr_type: &Type) {
+        let ambiguous: bool = match expr_type {
+            Type::UNKNOWN => true,
+            Type::ARRAY(elem, _) => **elem == Type::UNKNOWN,
+            _ => false,
+        };
+        if ambiguous {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!(
+                    "can't infer the type of '{}' from {}, it needs the annotation",
+                    ident_name,
+                    expr_type.string()
+                ),
+            );
+        }
+    }
+    /* a function value is stored only into the variable of the same signature. */
+    fn check_func_init(&mut self, ident_name: &str, pos: &Position, expr_type: &Type) {
+        if let Type::FUNC(_, _) = expr_type {
+        } else {
+            return;
+        }
+        let declared: Type = match self.get_symbol(ident_name).map(|s| s.ty) {
+            Some(Ok(ty)) => ty,
+            Some(Err(Token::IDENT(alias))) => match self.cur_env.lookup_type(&alias) {
+                Some(Type::ALIAS(alt)) => *alt.clone(),
+                _ => Type::UNKNOWN,
+            },
+            Some(Err(Token::EOF)) | None => return,
+            Some(Err(type_t)) => Type::from_token(type_t),
+        };
+        if declared != *expr_type {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!("can't assign {} into '{}'", expr_type.string(), ident_name),
+            );
+        }
+    }
+    /* a struct literal is checked with the struct the variable is declared as, returns its members. */
+    fn check_struct_init(
+        &mut self,
+        ident_name: &str,
+        expr: &Node,
+        expr_type: &Type,
+    ) -> BTreeMap<String, Type> {
+        let members = match (&expr.kind, expr_type) {
+            (NodeKind::STRUCTLIT(_, _), Type::STRUCT(members, _)) => members,
+            _ => return BTreeMap::new(),
+        };
+        let declared_t: Token = match self.get_symbol(ident_name).map(|s| s.ty) {
+            Some(Err(type_t)) => type_t,
+            _ => return BTreeMap::new(),
+        };
+        let (type_name, declared) = match self.resolve_struct(&declared_t, &expr.pos) {
+            Some(resolved) => resolved,
+            None => return BTreeMap::new(),
+        };
+        for member_name in declared.keys() {
+            if !members.contains_key(member_name) {
+                self.diag.error(
+                    Error::TYPE,
+                    &expr.pos,
+                    &format!(
+                        "the member '{}' of '{}' is not initialized",
+                        member_name, type_name
+                    ),
+                );
+            }
+        }
+        for (member_name, member_s) in members.iter() {
+            let member_type: Type = member_s.ty.clone().unwrap_or(Type::UNKNOWN);
+            match declared.get(member_name) {
+                Some(Type::UNKNOWN) => (),
+                Some(declared_type) => {
+                    if std::mem::discriminant(declared_type) != std::mem::discriminant(&member_type)
+                    {
+                        self.diag.error(
+                            Error::TYPE,
+                            &expr.pos,
+                            &format!(
+                                "the member '{}' of '{}' is {} but got {}",
+                                member_name,
+                                type_name,
+                                declared_type.string(),
+                                member_type.string()
+                            ),
+                        );
+                    }
+                }
+                None => {
+                    self.diag.error(
+                        Error::UNDEFINED,
+                        &expr.pos,
+                        &format!("no member '{}' in '{}'", member_name, type_name),
+                    );
+                }
+            }
+        }
+        declared
+    }
+    /* an enum variable is built only by a variant of its enum, it can't be copied. */
+    fn check_enum_init(&mut self, ident_name: &str, expr: &Node, expr_type: &Type) {
+        let enum_name: &String = match expr_type {
+            Type::ENUM(name, _, _) => name,
+            _ => return,
+        };
+        if let NodeKind::ENUMLIT(_, _, _, _) = expr.kind {
+        } else {
+            self.diag.error(
+                Error::TYPE,
+                &expr.pos,
+                &format!(
+                    "'{}' must be initialized with a variant of '{}'",
+                    ident_name, enum_name
+                ),
+            );
+            return;
+        }
+        /* 'let' declares the name of the enum, an assignment has resolved it */
+        let declared: Option<String> = match self.get_symbol(ident_name).map(|s| s.ty) {
+            Some(Ok(Type::ENUM(name, _, _))) => Some(name),
+            Some(Err(Token::IDENT(name))) => Some(name),
+            Some(_) => Some(String::new()),
+            None => None,
+        };
+        if let Some(name) = declared {
+            if name != *enum_name {
+                self.diag.error(
+                    Error::TYPE,
+                    &expr.pos,
+                    &format!("can't assign {} into '{}'", expr_type.string(), ident_name),
+                );
+            }
+        }
+    }
+    /* the arms must cover every variant unless '_' is given. */
+    fn walk_match(&mut self, expr: Node, arms: Vec<MatchArm>, pos: &Position) {
+        let expr_type: Type = self.walk(expr.clone());
+        let (enum_name, variants) = match &expr_type {
+            Type::ENUM(name, variants, _) => (name.to_string(), variants.clone()),
+            _ => {
+                self.diag.error(
+                    Error::TYPE,
+                    pos,
+                    &format!("can't match {} it's not enum", expr_type.string()),
+                );
+                return;
+            }
+        };
+        if let NodeKind::IDENT(_) = expr.kind {
+        } else {
+            self.diag.error(
+                Error::TYPE,
+                &expr.pos,
+                "the operand of match must be a variable",
+            );
+        }
+        let mut covered: Vec<String> = Vec::new();
+        let mut wildcard: bool = false;
+        for arm in arms.iter() {
+            let mut fields: Vec<Type> = Vec::new();
+            match &arm.pattern {
+                Pattern::WILDCARD => {
+                    if wildcard {
+                        self.diag.warning(&arm.pos, "unreachable match arm");
+                    }
+                    wildcard = true;
+                }
+                Pattern::VARIANT(name, variant, bindings) => {
+                    if wildcard || covered.contains(variant) {
+                        self.diag.warning(&arm.pos, "unreachable match arm");
+                    }
+                    if *name != enum_name {
+                        self.diag.error(
+                            Error::TYPE,
+                            &arm.pos,
+                            &format!(
+                                "expected a variant of '{}' but got '{}::{}'",
+                                enum_name, name, variant
+                            ),
+                        );
+                    } else if let Some((_, types)) = expr_type.variant(variant) {
+                        if types.len() != bindings.len() {
+                            self.diag.error(
+                                Error::TYPE,
+                                &arm.pos,
+                                &format!(
+                                    "'{}::{}' has {} fields but {} bindings",
+                                    name,
+                                    variant,
+                                    types.len(),
+                                    bindings.len()
+                                ),
+                            );
+                        }
+                        fields = types.clone();
+                        covered.push(variant.to_string());
+                    } else {
+                        self.diag.error(
+                            Error::UNDEFINED,
+                            &arm.pos,
+                            &format!("no variant '{}' in enum '{}'", variant, enum_name),
+                        );
+                    }
+                }
+            }
+            self.walk_arm(arm, &fields);
+        }
+        let missing: Vec<String> = variants
+            .iter()
+            .filter(|(variant, _)| !covered.contains(variant))
+            .map(|(variant, _)| format!("'{}::{}'", enum_name, variant))
+            .collect();
+        if !wildcard && !missing.is_empty() {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!("non-exhaustive match, {} not covered", missing.join(", ")),
+            );
+        }
+    }
+    /* the arm's scope is walked like a block, its bindings get the types of the fields. */
+    fn walk_arm(&mut self, arm: &MatchArm, fields: &[Type]) {
+        let mut env: Env = self.cur_scopes[arm.scope].clone();
+        env.prev = Some(Box::new(self.cur_env.clone()));
+        let outer: Env = std::mem::replace(&mut self.cur_env, env);
+        let stack_offset: usize = self.stack_offset;
+        if let Pattern::VARIANT(_, _, bindings) = &arm.pattern {
+            for (binding, field) in bindings.iter().zip(fields.iter()) {
+                if let Some(ref mut s) = self.cur_env.sym_table.get_mut(binding) {
+                    s.ty = Ok(field.clone());
+                    self.stack_offset += s.size();
+                    s.stack_offset = self.stack_offset;
+                }
+            }
+        }
+        self.walk(arm.body.clone());
+        if self.stack_peak < self.stack_offset {
+            self.stack_peak = self.stack_offset;
+        }
+        self.stack_offset = stack_offset;
+        self.cur_scopes[arm.scope] = std::mem::replace(&mut self.cur_env, outer);
+    }
+    fn check_loop_jump(&mut self, keyword: &str, label: &Option<String>, pos: &Position) {
+        if self.loops.is_empty() {
+            self.diag.error(
+                Error::TYPE,
+                pos,
+                &format!("'{}' outside of a loop", keyword),
+            );
+            return;
+        }
+        if let Some(name) = label {
+            if !self.loops.iter().any(|l| l.name.as_ref() == Some(name)) {
+                self.diag.error(
+                    Error::UNDEFINED,
+                    pos,
+                    &format!("no enclosing loop labeled ':{}'", name),
+                );
+            }
+        }
+    }
+    pub fn get_symbol(&self, name: &str) -> Option<Symbol> {
+        self.cur_env.lookup(name).cloned()
+    }
+}
+
+/* the types compared as they are, the members of a struct have their own offsets. */
+fn scalar(ty: &Type) -> bool {
+    match ty {
+        Type::INTEGER(_, _) | Type::BOOLEAN => true,
+        Type::POINTER(inner) => scalar(inner),
+        _ => false,
+    }
 }
@@ -1,3 +1,24 @@
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct Position {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+impl Position {
+    pub fn new(file: String, line: usize, column: usize, length: usize) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            length,
+        }
+    }
+    pub fn string(&self) -> String {
+        format!("{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
 #[derive(Eq, PartialEq, Clone)]
 pub enum Token {
     /* symbol */
@@ -14,6 +35,7 @@ pub enum Token {
     LBRACKET,
     RBRACKET,
     LSHIFT,
+    RSHIFT,
     LT,
     GT,
     LTEQ,
@@ -25,7 +47,24 @@ pub enum Token {
     DOUBLECOLON,
     COMMA,
     DOT,
+    DOTDOT,
+    FATARROW,
     AMPERSAND,
+    LOGAND,
+    LOGOR,
+    BANG,
+    PIPE,
+    CARET,
+    TILDE,
+    ADDASSIGN,
+    SUBASSIGN,
+    MULASSIGN,
+    DIVASSIGN,
+    MODASSIGN,
+    LSHIFTASSIGN,
+    RSHIFTASSIGN,
+    INCREMENT,
+    DECREMENT,
 
     /* keyword */
     FUNC,
@@ -34,18 +73,42 @@ pub enum Token {
     IF,
     ELSE,
     CONDLOOP,
+    WHILE,
+    FOR,
+    IN,
+    BREAK,
+    CONTINUE,
     LET,
     MUT,
     GOTO,
     STRUCT,
+    IMPORT,
+    ENUM,
+    MATCH,
+    IMPL,
+    I8,
+    I16,
+    I32,
     I64,
+    U8,
+    U16,
+    U32,
+    U64,
     COMPINT,
+    BOOL,
+    TRUE,
+    FALSE,
     POINTER(Box<Token>),
     ARRAY(Box<Token>, Box<Token>),
+    FUNCTYPE(Vec<Token>, Box<Token>),
+    /* 'Pair<i64, bool>', the instance of a generic struct */
+    GENERIC(String, Vec<Token>),
     INFORMATION(String),
 
     /* etc */
     INTEGER(i128),
+    STRLIT(String),
+    CHARLIT(u8),
     IDENT(String),
     EOF,
     BLANK,
@@ -63,6 +126,8 @@ impl Token {
     pub fn string(&self) -> String {
         match self {
             Token::INTEGER(int) => format!("INTEGER<{}>", int),
+            Token::STRLIT(contents) => format!("STRING<{:?}>", contents),
+            Token::CHARLIT(c) => format!("CHAR<{:?}>", *c as char),
             Token::PLUS => "PLUS".to_string(),
             Token::MINUS => "MINUS".to_string(),
             Token::STAR => "STAR".to_string(),
@@ -76,6 +141,7 @@ impl Token {
             Token::LBRACKET => "LBRACKET".to_string(),
             Token::RBRACKET => "RBRACKET".to_string(),
             Token::LSHIFT => "LSHIFT".to_string(),
+            Token::RSHIFT => "RSHIFT".to_string(),
             Token::LT => "LESSTHAN".to_string(),
             Token::GT => "GREATERTHAN".to_string(),
             Token::LTEQ => "LESSTHANEQUAL".to_string(),
@@ -87,7 +153,24 @@ impl Token {
             Token::DOUBLECOLON => "DOUBLECOLON".to_string(),
             Token::COMMA => "COMMA".to_string(),
             Token::DOT => "DOT".to_string(),
+            Token::DOTDOT => "DOTDOT".to_string(),
+            Token::FATARROW => "FATARROW".to_string(),
             Token::AMPERSAND => "AMPERSAND".to_string(),
+            Token::LOGAND => "LOGICALAND".to_string(),
+            Token::LOGOR => "LOGICALOR".to_string(),
+            Token::BANG => "BANG".to_string(),
+            Token::PIPE => "PIPE".to_string(),
+            Token::CARET => "CARET".to_string(),
+            Token::TILDE => "TILDE".to_string(),
+            Token::ADDASSIGN => "ADDASSIGN".to_string(),
+            Token::SUBASSIGN => "SUBASSIGN".to_string(),
+            Token::MULASSIGN => "MULASSIGN".to_string(),
+            Token::DIVASSIGN => "DIVASSIGN".to_string(),
+            Token::MODASSIGN => "MODASSIGN".to_string(),
+            Token::LSHIFTASSIGN => "LSHIFTASSIGN".to_string(),
+            Token::RSHIFTASSIGN => "RSHIFTASSIGN".to_string(),
+            Token::INCREMENT => "INCREMENT".to_string(),
+            Token::DECREMENT => "DECREMENT".to_string(),
             Token::RETURN => "RETURN".to_string(),
             Token::EOF => "EOF".to_string(),
             Token::FUNC => "FUNCTION".to_string(),
@@ -96,12 +179,47 @@ impl Token {
             Token::IF => "IF".to_string(),
             Token::ELSE => "ELSE".to_string(),
             Token::CONDLOOP => "CONDLOOP".to_string(),
+            Token::WHILE => "WHILE".to_string(),
+            Token::FOR => "FOR".to_string(),
+            Token::IN => "IN".to_string(),
+            Token::BREAK => "BREAK".to_string(),
+            Token::CONTINUE => "CONTINUE".to_string(),
             Token::LET => "LET".to_string(),
             Token::MUT => "MUTABLE".to_string(),
             Token::STRUCT => "STRUCT".to_string(),
+            Token::IMPORT => "IMPORT".to_string(),
+            Token::ENUM => "ENUM".to_string(),
+            Token::MATCH => "MATCH".to_string(),
+            Token::IMPL => "IMPL".to_string(),
+            Token::I8 => "i8".to_string(),
+            Token::I16 => "i16".to_string(),
+            Token::I32 => "i32".to_string(),
             Token::I64 => "i64".to_string(),
+            Token::U8 => "u8".to_string(),
+            Token::U16 => "u16".to_string(),
+            Token::U32 => "u32".to_string(),
+            Token::U64 => "u64".to_string(),
             Token::COMPINT => "compint".to_string(),
+            Token::BOOL => "bool".to_string(),
+            Token::TRUE => "TRUE".to_string(),
+            Token::FALSE => "FALSE".to_string(),
             Token::POINTER(ptr_to) => format!("POINTER<{}>", ptr_to.string()),
+            Token::FUNCTYPE(args, ret) => format!(
+                "FUNCTYPE<({})::{}>",
+                args.iter()
+                    .map(|a| a.string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                ret.string()
+            ),
+            Token::GENERIC(name, args) => format!(
+                "GENERIC<{},({})>",
+                name,
+                args.iter()
+                    .map(|a| a.string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
             Token::ARRAY(elem_type, ary_size) => {
                 format!("ARRAY<{},{}>", elem_type.string(), ary_size.string())
             }
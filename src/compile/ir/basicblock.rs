@@ -2,7 +2,7 @@ use crate::compile::ir::instruction::Instruction;
 
 #[derive(Clone)]
 pub struct BasicBlock {
-    entry: String,
+    pub entry: String,
     pub insts: Vec<Instruction>,
     // prev: &mut BasicBlock
     // next: &mut BasicBlock
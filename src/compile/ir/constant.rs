@@ -5,6 +5,8 @@ use crate::compile::ir::llvm_value::LLVMValue;
 #[derive(Clone)]
 pub enum Constant {
     Array(String, LLVMType, Vec<(LLVMType, LLVMValue)>),
+    Global(String, LLVMType, LLVMValue, bool),
+    Str(String, Vec<u8>),
 }
 
 impl Constant {
@@ -26,6 +28,33 @@ impl Constant {
                     name, ty, constant_string, alignment
                 );
             }
+            Self::Str(name, bytes) => {
+                let mut contents = String::new();
+                for b in bytes.iter() {
+                    if (b.is_ascii_graphic() || *b == b' ') && *b != b'"' && *b != b'\\' {
+                        contents.push(*b as char);
+                    } else {
+                        let _ = contents.write_fmt(format_args!("\\{:02X}", b));
+                    }
+                }
+                println!(
+                    "\n{} = private unnamed_addr constant [{} x i8] c\"{}\", align 1",
+                    name,
+                    bytes.len(),
+                    contents
+                );
+            }
+            Self::Global(name, ty, value, is_mutable) => {
+                let linkage = if *is_mutable { "global" } else { "constant" };
+                println!(
+                    "@{} = {} {} {}, align {}",
+                    name,
+                    linkage,
+                    ty,
+                    value,
+                    ty.alignment()
+                );
+            }
         }
     }
 }
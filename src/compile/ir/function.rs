@@ -1,8 +1,9 @@
 use crate::ce::types::Error;
 use crate::compile::frontend;
 use crate::compile::ir;
-use frontend::frontmanager::frontmanager::Symbol;
-use frontend::parse::node::{Func, Node};
+use frontend::frontmanager::frontmanager::{Env, Symbol};
+use frontend::parse::node::{Func, MatchArm, Node, NodeKind, Pattern};
+use frontend::sema::builtin::SYSCALL_ARGS;
 use frontend::sema::semantics::Type;
 use ir::basicblock::BasicBlock;
 use ir::constant::Constant;
@@ -27,10 +28,23 @@ pub struct Function {
     pub insert_point: usize,
     pub label: usize,
     pub env: BTreeMap<String, LLVMSymbol>,
+    pub globals: BTreeMap<String, LLVMSymbol>,
+    /* the mangled names of the globals, like 'math.counter' */
+    pub global_symbols: BTreeMap<String, String>,
+    pub signatures: BTreeMap<String, Type>,
     pub constants: Vec<Constant>,
     pub const_label: usize,
     pub declares: HashSet<Intrinsic>,
     pub jump_labels: BTreeMap<String, (InstructionLabel, BasicBlockLabel)>, // BTreeMap<String,(writeToInstructionIndex,writeToBasicBlockTo)>
+    loops: Vec<LoopSites>,
+    scope: Env,
+}
+
+/* break/continue leave a NOP, which is patched once the loop's blocks are numbered. */
+struct LoopSites {
+    name: Option<String>,
+    breaks: Vec<(InstructionLabel, BasicBlockLabel)>,
+    continues: Vec<(InstructionLabel, BasicBlockLabel)>,
 }
 
 impl Function {
@@ -43,7 +57,12 @@ impl Function {
             args: Vec::new(),
             label: len,
             env: BTreeMap::new(),
+            globals: BTreeMap::new(),
+            global_symbols: BTreeMap::new(),
+            signatures: BTreeMap::new(),
             jump_labels: BTreeMap::new(),
+            loops: Vec::new(),
+            scope: Env::new(),
             constants: Vec::new(),
             declares: HashSet::new(),
             const_label: 0,
@@ -79,27 +98,36 @@ impl Function {
     pub fn add_inst(&mut self, inst: Inst) {
         match inst {
             Inst::Store(_, _, _, _) => (),
-            Inst::Memcpy64(_, _, _, _) => (),
+            Inst::StoreGlobal(_, _, _, _) => (),
+            Inst::Memcpy64(_, _, _, _, _) => (),
             _ => self.label += 1,
         }
         self.blocks[self.insert_point].insts.push(inst);
     }
     pub fn build_function(&mut self, f: &Func) {
+        self.scope = f.env.clone();
         for arg in f.args.iter() {
-            if let Node::DEFARG(name) = arg {
+            if let NodeKind::DEFARG(name) = &arg.kind {
                 if let Some(ref mut s) = f.env.sym_table.get(name) {
                     if let Ok(ty) = s.ty.clone() {
                         let llvm_type = self.get_llvmtype_from_type(&ty);
                         let alignment = llvm_type.alignment();
                         self.args.push(llvm_type.clone());
                         self.add_inst(Inst::Alloca(self.label, llvm_type.clone(), alignment));
-                        let llvm_symbol = LLVMSymbol::new(self.label - 1, llvm_type.clone());
+                        let llvm_symbol =
+                            LLVMSymbol::new(self.label - 1, llvm_type.clone(), self.is_signed(&ty));
                         self.env.insert(name.to_string(), llvm_symbol);
                     }
                 }
             }
         }
-        for (i, (_key, value)) in self.env.clone().iter().enumerate() {
+        /* %0, %1, ... are the arguments in the order they're declared. */
+        let params: Vec<LLVMSymbol> = f
+            .args
+            .iter()
+            .filter_map(|arg| arg.name().and_then(|name| self.env.get(&name).cloned()))
+            .collect();
+        for (i, value) in params.iter().enumerate() {
             let alignment = value.ty.alignment();
             self.add_inst(Inst::Store(
                 value.ty.clone(),
@@ -124,32 +152,68 @@ impl Function {
     }
     fn build_stmt(&mut self, option_f: Option<&Func>, stmt: Node) {
         if let Some(f) = option_f {
-            match stmt {
-                Node::RETURN(bexpr) => self.build_return(*bexpr.clone()),
-                Node::LET(ident_name, bexpr) => {
-                    if let Some(v) = f.env.sym_table.get(&ident_name) {
-                        self.build_let(ident_name.to_string(), v, *bexpr.clone())
+            match stmt.kind {
+                NodeKind::RETURN(bexpr) => self.build_return(*bexpr.clone()),
+                NodeKind::LET(ident_name, bexpr) => {
+                    if let Some(v) = self.scope.sym_table.get(&ident_name).cloned() {
+                        self.build_let(ident_name.to_string(), &v, *bexpr.clone())
                     } else {
                         Error::LLVM.found(&format!("{} is not defined", &ident_name));
                     }
                 }
-                Node::ASSIGN(ident_name, bexpr) => self.build_assign(ident_name, *bexpr.clone()),
-                Node::CONDLOOP(bcond_expr, bblock) => {
-                    self.build_condloop(f, *bcond_expr, *bblock);
+                NodeKind::ASSIGN(ident_name, bexpr) => {
+                    self.build_assign(ident_name, *bexpr.clone())
+                }
+                NodeKind::ASSIGNOP(ident_name, op, bexpr) => {
+                    let value: Node = Node::compound_value(&ident_name, &op, &bexpr, &stmt.pos);
+                    self.build_assign(ident_name, value)
+                }
+                NodeKind::CONDLOOP(bcond_expr, bblock, name) => {
+                    self.build_condloop(f, *bcond_expr, *bblock, None, name);
                 }
-                Node::IF(bcond_expr, bblock, opt_balter) => {
+                NodeKind::FOR(ident_name, bbegin, bend, bblock, name) => {
+                    /* i = begin; condloop (i < end) { block; i = i + 1 } */
+                    if let Some(v) = self.scope.sym_table.get(&ident_name).cloned() {
+                        self.build_let(ident_name.to_string(), &v, *bbegin)
+                    } else {
+                        Error::LLVM.found(&format!("{} is not defined", &ident_name));
+                    }
+                    let ident = Box::new(Node::new(
+                        NodeKind::IDENT(ident_name.to_string()),
+                        stmt.pos.clone(),
+                    ));
+                    let cond = Node::new(NodeKind::LT(ident.clone(), bend), stmt.pos.clone());
+                    let one = Box::new(Node::new(NodeKind::INTEGER(1), stmt.pos.clone()));
+                    let step = Node::new(
+                        NodeKind::ASSIGN(
+                            ident_name,
+                            Box::new(Node::new(NodeKind::ADD(ident, one), stmt.pos.clone())),
+                        ),
+                        stmt.pos.clone(),
+                    );
+                    self.build_condloop(f, cond, *bblock, Some(step), name);
+                }
+                NodeKind::BREAK(name) => self.build_loop_jump(name, true),
+                NodeKind::CONTINUE(name) => self.build_loop_jump(name, false),
+                NodeKind::IF(bcond_expr, bblock, opt_balter) => {
                     if let Some(balter) = opt_balter {
                         self.build_ifelse(f, *bcond_expr, *bblock, *balter);
                     } else {
                         self.build_if(f, *bcond_expr, *bblock);
                     }
                 }
-                Node::BLOCK(bstmts) => {
+                NodeKind::BLOCK(bstmts, scope) => {
+                    /* the names declared in the block go out of scope after it. */
+                    let outer_env = self.env.clone();
+                    let outer_scope = std::mem::replace(&mut self.scope, f.scopes[scope].clone());
                     for bst in bstmts.iter() {
                         self.build_stmt(Some(f), bst.clone());
                     }
+                    self.scope = outer_scope;
+                    self.env = outer_env;
                 }
-                Node::LABEL(name) => {
+                NodeKind::MATCH(bexpr, arms) => self.build_match(f, *bexpr, *arms),
+                NodeKind::LABEL(name) => {
                     if let Some((inst_label, block_label)) = self.jump_labels.clone().get(&name) {
                         self.blocks[*block_label].insts[*inst_label] =
                             Inst::UnconditionalBranch(self.label);
@@ -167,7 +231,7 @@ impl Function {
                     self.insert_point += 1;
                     self.label += 1;
                 }
-                Node::GOTO(name) => {
+                NodeKind::GOTO(name) => {
                     if let Some((_inst_label, block_label)) = self.jump_labels.clone().get(&name) {
                         self.add_inst(Inst::UnconditionalBranch(*block_label));
                         return;
@@ -186,7 +250,8 @@ impl Function {
         }
     }
     fn build_ifelse(&mut self, f: &Func, cond_node: Node, block: Node, alter: Node) {
-        let (cond_value, _) = self.build_expr(cond_node);
+        let (cond_value, cond_type) = self.build_expr(cond_node);
+        let cond_value = self.build_condition(cond_value, cond_type);
         let conditional_branch_index = self.insert_point;
 
         let true_label = self.label;
@@ -225,7 +290,8 @@ impl Function {
         self.insert_point += 1;
     }
     fn build_if(&mut self, f: &Func, cond_node: Node, block: Node) {
-        let (cond_value, _) = self.build_expr(cond_node);
+        let (cond_value, cond_type) = self.build_expr(cond_node);
+        let cond_value = self.build_condition(cond_value, cond_type);
         let insert_point_after_generate_all = self.insert_point;
 
         let true_label = self.label;
@@ -250,7 +316,14 @@ impl Function {
                 false_label,
             ));
     }
-    fn build_condloop(&mut self, f: &Func, cond_node: Node, block: Node) {
+    fn build_condloop(
+        &mut self,
+        f: &Func,
+        cond_node: Node,
+        block: Node,
+        step: Option<Node>,
+        name: Option<String>,
+    ) {
         let cond_label = self.label;
         self.add_inst(Inst::UnconditionalBranch(cond_label));
 
@@ -259,7 +332,8 @@ impl Function {
 
         let cond_block = BasicBlock::new(format!("{}", cond_label));
         self.blocks.push(cond_block);
-        let (cond_value, _) = self.build_expr(cond_node);
+        let (cond_value, cond_type) = self.build_expr(cond_node);
+        let cond_value = self.build_condition(cond_value, cond_type);
 
         let true_label = self.label;
 
@@ -268,9 +342,33 @@ impl Function {
         self.insert_point += 1;
         self.label += 1;
 
+        self.loops.push(LoopSites {
+            name,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        });
         self.build_stmt(Some(f), block);
+        let sites: LoopSites = self.loops.pop().unwrap();
+
+        /* continue goes to the step if the loop has it */
+        let mut continue_label = cond_label;
+        if let Some(step_stmt) = step {
+            continue_label = self.label;
+            self.add_inst(Inst::UnconditionalBranch(continue_label));
+            let step_block = BasicBlock::new(format!("{}", continue_label));
+            self.blocks.push(step_block);
+            self.insert_point += 1;
+            self.build_stmt(Some(f), step_stmt);
+        }
         let false_label = self.label;
         self.add_inst(Inst::UnconditionalBranch(cond_label));
+        for (inst_label, block_label) in sites.breaks.iter() {
+            self.blocks[*block_label].insts[*inst_label] = Inst::UnconditionalBranch(false_label);
+        }
+        for (inst_label, block_label) in sites.continues.iter() {
+            self.blocks[*block_label].insts[*inst_label] =
+                Inst::UnconditionalBranch(continue_label);
+        }
 
         self.blocks[insert_point_after_generate_all]
             .insts
@@ -284,15 +382,39 @@ impl Function {
         self.blocks.push(breaked_block);
         self.insert_point += 1;
     }
+    fn build_loop_jump(&mut self, name: Option<String>, is_break: bool) {
+        let site = (
+            self.blocks[self.insert_point].insts.len(),
+            self.insert_point,
+        );
+        /* sema has already checked that the loop exists. */
+        let sites: &mut LoopSites = self
+            .loops
+            .iter_mut()
+            .rev()
+            .find(|l| name.is_none() || l.name == name)
+            .unwrap();
+        if is_break {
+            sites.breaks.push(site);
+        } else {
+            sites.continues.push(site);
+        }
+        /* the code after the jump goes to a new (unreachable) block. */
+        let label = self.label;
+        self.add_inst(Inst::NOP);
+        let another_block = BasicBlock::new(format!("{}", label));
+        self.blocks.push(another_block);
+        self.insert_point += 1;
+    }
     fn build_let(&mut self, ident_name: String, symbol: &Symbol, mut expr: Node) {
         if let Ok(ty) = &symbol.ty {
             let llvm_type = self.get_llvmtype_from_type(ty);
             let alignment = llvm_type.alignment();
             let label = self.label;
-            let llvm_symbol = LLVMSymbol::new(label, llvm_type.clone());
+            let llvm_symbol = LLVMSymbol::new(label, llvm_type.clone(), self.is_signed(ty));
             self.env.insert(ident_name, llvm_symbol);
             self.add_inst(Inst::Alloca(label, llvm_type.clone(), alignment));
-            if let Node::ARRAYLIT(ref mut elements, ref mut name) = expr {
+            if let NodeKind::ARRAYLIT(ref mut elements, ref mut name) = expr.kind {
                 *name = format!("{}", self.const_label);
                 self.const_label += 1;
                 self.add_inst(Inst::BitCast(
@@ -311,12 +433,16 @@ impl Function {
                         LLVMType::I8,
                     ),
                     total_size,
+                    alignment,
                     false,
                 ));
                 self.declares.insert(Intrinsic::Memcpy);
                 self.add_constant_array((*elements).to_vec(), llvm_type, name.to_string());
+            } else if let NodeKind::ENUMLIT(enum_name, variant, args, _) = expr.kind {
+                self.build_enumlit(label, llvm_type, &enum_name, &variant, &args);
             } else {
-                let (llvm_value, _) = self.build_expr(expr);
+                let (llvm_value, value_type) = self.build_expr(expr);
+                let llvm_value = self.narrow(llvm_value, value_type, &llvm_type);
                 self.add_inst(Inst::Store(llvm_type, llvm_value, label, alignment));
             }
         }
@@ -327,8 +453,23 @@ impl Function {
             .ty
             .clone();
         let symbol_label = self.get_symbol_if_defined(&ident_name.to_string()).label;
-        let (llvm_value, _llvm_type) = self.build_expr(expr.clone());
+        if let NodeKind::ENUMLIT(enum_name, variant, args, _) = expr.kind {
+            self.build_enumlit(symbol_label, symbol_type, &enum_name, &variant, &args);
+            return;
+        }
+        let (llvm_value, llvm_type) = self.build_expr(expr.clone());
+        let llvm_value = self.narrow(llvm_value, llvm_type, &symbol_type);
         let alignment = symbol_type.alignment();
+        if self.is_global(&ident_name) {
+            let symbol: String = self.global_symbols[&ident_name].to_string();
+            self.add_inst(Inst::StoreGlobal(
+                symbol_type,
+                llvm_value,
+                symbol,
+                alignment,
+            ));
+            return;
+        }
         self.add_inst(Inst::Store(
             symbol_type,
             llvm_value,
@@ -336,14 +477,247 @@ impl Function {
             alignment,
         ));
     }
+    /* an enum is an array of i64, the tag is stored at [0] and the fields follow it. */
+    fn build_enumlit(
+        &mut self,
+        dst: usize,
+        ty: LLVMType,
+        enum_name: &str,
+        variant: &str,
+        args: &[Node],
+    ) {
+        let tag: usize = match self.scope.lookup_type(enum_name) {
+            Some(enum_type) => enum_type.variant(variant).map_or(0, |(tag, _)| tag),
+            None => 0,
+        };
+        let mut values: Vec<LLVMValue> = vec![LLVMValue::INTEGER(tag as i128)];
+        for arg in args.iter() {
+            let (arg_value, arg_type) = self.build_expr(arg.clone());
+            let arg_value = if let LLVMType::I1 = arg_type {
+                let label = self.label;
+                self.add_inst(Inst::Zext(label, arg_type, arg_value, LLVMType::I64));
+                LLVMValue::VREG(label)
+            } else {
+                arg_value
+            };
+            values.push(arg_value);
+        }
+        for (idx, value) in values.into_iter().enumerate() {
+            let slot = self.build_enum_slot(dst, ty.clone(), idx);
+            self.add_inst(Inst::Store(LLVMType::I64, value, slot, 8));
+        }
+    }
+    fn build_enum_slot(&mut self, src: usize, ty: LLVMType, idx: usize) -> usize {
+        let label = self.label;
+        self.add_inst(Inst::GetElementPtrInbounds(
+            label,
+            ty,
+            LLVMValue::VREG(src),
+            LLVMType::I64,
+            LLVMValue::INTEGER(idx as i128),
+        ));
+        label
+    }
+    /* each arm gets its own block, a switch on the tag jumps into them. */
+    fn build_match(&mut self, f: &Func, expr: Node, arms: Vec<MatchArm>) {
+        let name: String = match expr.kind {
+            NodeKind::IDENT(name) => name,
+            _ => return,
+        };
+        let enum_type: Type = match self.scope.lookup(&name).map(|s| s.ty.clone()) {
+            Some(Ok(ty)) => ty,
+            _ => return,
+        };
+        let src: usize = self.get_symbol_if_defined(&name).label;
+        let ty: LLVMType = self.get_symbol_if_defined(&name).ty.clone();
+        let slot = self.build_enum_slot(src, ty.clone(), 0);
+        let tag_label = self.label;
+        self.add_inst(Inst::Load(
+            tag_label,
+            LLVMType::I64,
+            LLVMValue::VREG(slot),
+            8,
+        ));
+        let switch_index = self.insert_point;
+
+        let mut default_label: Option<usize> = None;
+        let mut cases: Vec<(i128, usize)> = Vec::new();
+        let mut arm_ends: Vec<usize> = Vec::new();
+        for arm in arms.iter() {
+            let arm_label = self.label;
+            let arm_block = BasicBlock::new(format!("{}", arm_label));
+            self.blocks.push(arm_block);
+            self.insert_point += 1;
+            self.label += 1;
+
+            let outer_env = self.env.clone();
+            let outer_scope = std::mem::replace(&mut self.scope, f.scopes[arm.scope].clone());
+            match &arm.pattern {
+                Pattern::WILDCARD => {
+                    if default_label.is_none() {
+                        default_label = Some(arm_label);
+                    }
+                }
+                Pattern::VARIANT(_, variant, bindings) => {
+                    if let Some((tag, _)) = enum_type.variant(variant) {
+                        if !cases.iter().any(|(v, _)| *v == tag as i128) {
+                            cases.push((tag as i128, arm_label));
+                        }
+                    }
+                    for (k, binding) in bindings.iter().enumerate() {
+                        self.build_binding(binding, src, ty.clone(), k + 1);
+                    }
+                }
+            }
+            self.build_stmt(Some(f), arm.body.clone());
+            self.scope = outer_scope;
+            self.env = outer_env;
+            arm_ends.push(self.insert_point);
+        }
+
+        let end_label = self.label;
+        self.blocks[switch_index].insts.push(Inst::Switch(
+            LLVMType::I64,
+            LLVMValue::VREG(tag_label),
+            default_label.unwrap_or(end_label),
+            cases,
+        ));
+        for idx in arm_ends.iter() {
+            self.blocks[*idx]
+                .insts
+                .push(Inst::UnconditionalBranch(end_label));
+        }
+        let end_block = BasicBlock::new(format!("{}", end_label));
+        self.blocks.push(end_block);
+        self.insert_point += 1;
+        self.label += 1;
+    }
+    /* copy the field into the binding, which is declared in the arm's scope. */
+    fn build_binding(&mut self, binding: &str, src: usize, ty: LLVMType, idx: usize) {
+        let field_type: Type = match self.scope.sym_table.get(binding).map(|s| s.ty.clone()) {
+            Some(Ok(field_type)) => field_type,
+            _ => return,
+        };
+        let slot = self.build_enum_slot(src, ty, idx);
+        let value_label = self.label;
+        self.add_inst(Inst::Load(
+            value_label,
+            LLVMType::I64,
+            LLVMValue::VREG(slot),
+            8,
+        ));
+        let llvm_type = self.get_llvmtype_from_type(&field_type);
+        let alignment = llvm_type.alignment();
+        let value = match llvm_type {
+            LLVMType::I64 => LLVMValue::VREG(value_label),
+            _ => {
+                let label = self.label;
+                self.add_inst(Inst::Trunc(
+                    label,
+                    LLVMType::I64,
+                    LLVMValue::VREG(value_label),
+                    llvm_type.clone(),
+                ));
+                LLVMValue::VREG(label)
+            }
+        };
+        let label = self.label;
+        self.add_inst(Inst::Alloca(label, llvm_type.clone(), alignment));
+        self.add_inst(Inst::Store(llvm_type.clone(), value, label, alignment));
+        let signed = self.is_signed(&field_type);
+        self.env.insert(
+            binding.to_string(),
+            LLVMSymbol::new(label, llvm_type, signed),
+        );
+    }
     fn build_return(&mut self, expr: Node) {
         let (llvm_value, llvm_type) = self.build_expr(expr);
+        if let LLVMType::I1 = llvm_type {
+            let label = self.label;
+            self.add_inst(Inst::Zext(label, llvm_type, llvm_value, LLVMType::I64));
+            self.add_inst(Inst::RetTy(LLVMType::I64, LLVMValue::VREG(label)));
+            return;
+        }
         self.add_inst(Inst::RetTy(llvm_type, llvm_value));
     }
+    /* the right operand gets its own block, and a phi merges both paths. */
+    fn build_logical(&mut self, lop: Node, rop: Node, is_and: bool) -> (LLVMValue, LLVMType) {
+        let (lop_value, lop_type) = self.build_expr(lop);
+        let lop_value = self.build_condition(lop_value, lop_type);
+        let lop_block_index = self.insert_point;
+        let lop_block = self.blocks[lop_block_index].entry.to_string();
+
+        let rop_label = self.label;
+        let rop_block = BasicBlock::new(format!("{}", rop_label));
+        self.blocks.push(rop_block);
+        self.insert_point += 1;
+        self.label += 1;
+
+        let (rop_value, rop_type) = self.build_expr(rop);
+        let rop_value = self.build_condition(rop_value, rop_type);
+        let rop_end_block = self.blocks[self.insert_point].entry.to_string();
+
+        let merged_label = self.label;
+        self.add_inst(Inst::UnconditionalBranch(merged_label));
+        let merged_block = BasicBlock::new(format!("{}", merged_label));
+        self.blocks.push(merged_block);
+        self.insert_point += 1;
+
+        let (true_label, false_label) = if is_and {
+            (rop_label, merged_label)
+        } else {
+            (merged_label, rop_label)
+        };
+        self.blocks[lop_block_index]
+            .insts
+            .push(Inst::ConditionalBranch(
+                LLVMType::I1,
+                lop_value,
+                true_label,
+                false_label,
+            ));
+
+        let label = self.label;
+        self.add_inst(Inst::Phi(
+            label,
+            LLVMType::I1,
+            vec![
+                (LLVMValue::INTEGER(!is_and as i128), lop_block),
+                (rop_value, rop_end_block),
+            ],
+        ));
+        (LLVMValue::VREG(label), LLVMType::I1)
+    }
+    /* conditions are i1, so compare the other integers with zero. */
+    fn build_condition(&mut self, value: LLVMValue, ty: LLVMType) -> LLVMValue {
+        if let LLVMType::I1 = ty {
+            return value;
+        }
+        let label = self.label;
+        self.add_inst(Inst::Icmp(
+            label,
+            CompareMode::NOTEQUAL,
+            ty,
+            value,
+            LLVMValue::INTEGER(0),
+        ));
+        LLVMValue::VREG(label)
+    }
     fn build_expr(&mut self, expr: Node) -> (LLVMValue, LLVMType) {
-        match expr {
-            Node::INTEGER(value) => (LLVMValue::INTEGER(value), LLVMType::I64),
-            Node::IDENT(name) => {
+        match expr.kind {
+            NodeKind::INTEGER(value) => (LLVMValue::INTEGER(value), LLVMType::I64),
+            NodeKind::BOOLEAN(value) => (LLVMValue::INTEGER(value as i128), LLVMType::I1),
+            NodeKind::STRLIT(contents) => self.build_string(contents),
+            NodeKind::NOT(bchild) => {
+                let (child, child_type) = self.build_expr(*bchild);
+                let child = self.build_condition(child, child_type);
+                let label = self.label;
+                self.add_inst(Inst::Xor(label, LLVMType::I1, child, LLVMValue::INTEGER(1)));
+                (LLVMValue::VREG(label), LLVMType::I1)
+            }
+            NodeKind::LOGAND(blop, brop) => self.build_logical(*blop, *brop, true),
+            NodeKind::LOGOR(blop, brop) => self.build_logical(*blop, *brop, false),
+            NodeKind::IDENT(name) => {
                 let label = self.label;
                 let llvm_symbol = self.get_symbol_if_defined(&name);
                 let llvm_type = llvm_symbol.ty.clone();
@@ -351,24 +725,156 @@ impl Function {
                     return (LLVMValue::VREG(llvm_symbol.label), llvm_type);
                 }
                 let alignment = llvm_type.alignment();
-                let llvm_value = LLVMValue::VREG(llvm_symbol.label);
+                let signed = llvm_symbol.signed;
+                let llvm_value = if self.is_global(&name) {
+                    LLVMValue::Const(format!("@{}", self.global_symbols[&name]))
+                } else {
+                    LLVMValue::VREG(self.get_symbol_if_defined(&name).label)
+                };
                 self.add_inst(Inst::Load(label, llvm_type.clone(), llvm_value, alignment));
-                (LLVMValue::VREG(label), llvm_type)
+                self.widen(LLVMValue::VREG(label), llvm_type, signed)
             }
-            Node::CALL(name, elements) => {
+            NodeKind::FUNCADDR(name) => {
+                let func_type: Type = self.signatures.get(&name).cloned().unwrap_or(Type::UNKNOWN);
+                let llvm_type = self.get_llvmtype_from_type(&func_type);
+                (LLVMValue::Const(format!("@{}", name)), llvm_type)
+            }
+            NodeKind::CALLPTR(bcallee, elements) => {
+                let (callee_value, callee_type) = self.build_expr(*bcallee);
+                let params: Vec<LLVMType> = match &callee_type {
+                    LLVMType::FUNC(params) => params.clone(),
+                    _ => Vec::new(),
+                };
+                let mut args: Vec<(LLVMValue, LLVMType)> = Vec::new();
+                for (elem, param) in elements.iter().zip(params.iter()) {
+                    let (elem_value, elem_type) = self.build_expr(elem.clone());
+                    let elem_value = self.narrow(elem_value, elem_type, param);
+                    args.push((elem_value, param.clone()));
+                }
+                let label = self.label;
+                self.add_inst(Inst::CallPtr(label, callee_value, args));
+                (LLVMValue::VREG(label), LLVMType::I64)
+            }
+            NodeKind::CALL(name, elements) if name == "syscall" => {
+                let mut args: Vec<(LLVMValue, LLVMType)> = Vec::new();
+                for elem in elements.iter() {
+                    let (elem_value, elem_type) = self.build_expr(elem.clone());
+                    if let LLVMType::POINTER(_) = elem_type {
+                        let label = self.label;
+                        self.add_inst(Inst::PtrToInt(label, elem_type, elem_value, LLVMType::I64));
+                        args.push((LLVMValue::VREG(label), LLVMType::I64));
+                    } else {
+                        args.push((elem_value, elem_type));
+                    }
+                }
+                args.resize(SYSCALL_ARGS, (LLVMValue::INTEGER(0), LLVMType::I64));
+                self.declares.insert(Intrinsic::Syscall);
+                let label = self.label;
+                self.add_inst(Inst::Call(
+                    label,
+                    LLVMType::I64,
+                    "depth.syscall".to_string(),
+                    args,
+                ));
+                (LLVMValue::VREG(label), LLVMType::I64)
+            }
+            NodeKind::CALL(name, elements) if name == "alloc" || name == "free" => {
+                /* the runtime takes and returns the addresses as i64 */
                 let mut args: Vec<(LLVMValue, LLVMType)> = Vec::new();
                 for elem in elements.iter() {
                     let (elem_value, elem_type) = self.build_expr(elem.clone());
-                    args.push((elem_value, elem_type));
+                    args.push(self.pointer_to_int(elem_value, elem_type));
+                }
+                if name == "alloc" {
+                    self.declares.insert(Intrinsic::Alloc);
+                    /* alloc maps its heap through the syscall intrinsic */
+                    self.declares.insert(Intrinsic::Syscall);
+                } else {
+                    self.declares.insert(Intrinsic::Free);
+                }
+                let label = self.label;
+                self.add_inst(Inst::Call(label, LLVMType::I64, name.to_string(), args));
+                if name != "alloc" {
+                    return (LLVMValue::VREG(label), LLVMType::I64);
+                }
+                let ptr_type: LLVMType = LLVMType::POINTER(Box::new(LLVMType::I8));
+                let ptr_label = self.label;
+                self.add_inst(Inst::IntToPtr(
+                    ptr_label,
+                    LLVMType::I64,
+                    LLVMValue::VREG(label),
+                    ptr_type.clone(),
+                ));
+                (LLVMValue::VREG(ptr_label), ptr_type)
+            }
+            NodeKind::CALL(name, elements) => {
+                let func_type: Type = self.signatures.get(&name).cloned().unwrap_or(Type::UNKNOWN);
+                let params: Vec<LLVMType> = match self.get_llvmtype_from_type(&func_type) {
+                    LLVMType::FUNC(params) => params,
+                    _ => Vec::new(),
+                };
+                let mut args: Vec<(LLVMValue, LLVMType)> = Vec::new();
+                for (idx, elem) in elements.iter().enumerate() {
+                    let (elem_value, elem_type) = self.build_expr(elem.clone());
+                    /* the narrower parameters take the truncated arguments */
+                    match params.get(idx) {
+                        Some(param @ (LLVMType::I8 | LLVMType::I16 | LLVMType::I32)) => {
+                            let elem_value = self.narrow(elem_value, elem_type, param);
+                            args.push((elem_value, param.clone()));
+                        }
+                        /* an enum is passed by value, so it's loaded from its variable */
+                        Some(param @ LLVMType::ARRAY(_, _)) => {
+                            let label = self.label;
+                            let alignment = param.alignment();
+                            self.add_inst(Inst::Load(label, param.clone(), elem_value, alignment));
+                            args.push((LLVMValue::VREG(label), param.clone()));
+                        }
+                        _ => args.push((elem_value, elem_type)),
+                    }
                 }
                 let label = self.label;
                 self.add_inst(Inst::Call(label, LLVMType::I64, name, args)); // TODO: func_type
+                                                                             /* 'bool' is returned as i64 like the other values */
+                if let Type::FUNC(_, ret) = &func_type {
+                    if let Type::BOOLEAN = **ret {
+                        let trunc_label = self.label;
+                        self.add_inst(Inst::Trunc(
+                            trunc_label,
+                            LLVMType::I64,
+                            LLVMValue::VREG(label),
+                            LLVMType::I1,
+                        ));
+                        return (LLVMValue::VREG(trunc_label), LLVMType::I1);
+                    }
+                }
                 (LLVMValue::VREG(label), LLVMType::I64)
             }
-            Node::INDEX(bary_node, bidx_node) => {
+            NodeKind::INDEX(bary_node, bidx_node) => {
+                let mut signed = true;
+                if let NodeKind::IDENT(name) = &bary_node.kind {
+                    signed = self.get_symbol_if_defined(name).signed;
+                }
                 let (index_value, index_type) = self.build_expr(*bidx_node.clone());
                 let (ary_value, ary_type) = self.build_expr(*bary_node);
                 let label = self.label;
+                /* indexing a pointer steps over its elements. */
+                if let LLVMType::POINTER(elem_type) = ary_type {
+                    let alignment = elem_type.alignment();
+                    self.add_inst(Inst::GetElementPtr(
+                        label,
+                        *elem_type.clone(),
+                        ary_value,
+                        index_type,
+                        index_value,
+                    ));
+                    self.add_inst(Inst::Load(
+                        label + 1,
+                        *elem_type.clone(),
+                        LLVMValue::VREG(label),
+                        alignment,
+                    ));
+                    return self.widen(LLVMValue::VREG(label + 1), *elem_type, signed);
+                }
                 self.add_inst(Inst::GetElementPtrInbounds(
                     label,
                     ary_type.clone(),
@@ -384,14 +890,14 @@ impl Function {
                         LLVMValue::VREG(label),
                         alignment,
                     ));
-                    (LLVMValue::VREG(label + 1), *elem_type)
+                    self.widen(LLVMValue::VREG(label + 1), *elem_type, signed)
                 } else {
                     eprintln!("something wrong in index expression");
                     (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
                 }
             }
-            Node::ADDRESS(bchild) => {
-                if let Node::IDENT(name) = *bchild {
+            NodeKind::ADDRESS(bchild) => {
+                if let NodeKind::IDENT(name) = bchild.kind {
                     let llvm_symbol = self.get_symbol_if_defined(&name);
                     let llvm_value = LLVMValue::VREG(llvm_symbol.label);
                     let inner_type = Box::new(llvm_symbol.ty.clone());
@@ -401,19 +907,23 @@ impl Function {
                     (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
                 }
             }
-            Node::DEREFERENCE(bchild) => {
+            NodeKind::DEREFERENCE(bchild) => {
+                let mut signed = true;
+                if let NodeKind::IDENT(name) = &bchild.kind {
+                    signed = self.get_symbol_if_defined(name).signed;
+                }
                 let (inner, inner_type) = self.build_expr(*bchild);
                 let alignment = inner_type.alignment();
                 let label = self.label;
                 if let LLVMType::POINTER(binner) = inner_type {
                     self.add_inst(Inst::Load(label, *binner.clone(), inner, alignment));
-                    (LLVMValue::VREG(label), *binner)
+                    self.widen(LLVMValue::VREG(label), *binner, signed)
                 } else {
                     Error::LLVM.found(&"addressing with constant".to_string());
                     (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
                 }
             }
-            Node::ADD(blop, brop) => {
+            NodeKind::ADD(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
@@ -428,9 +938,12 @@ impl Function {
                     (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
                 }
             }
-            Node::SUB(blop, brop) => {
+            NodeKind::SUB(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
+                /* the distance between two pointers is in bytes */
+                let (lop, lop_type) = self.pointer_to_int(lop, lop_type);
+                let (rop, rop_type) = self.pointer_to_int(rop, rop_type);
                 let label = self.label;
                 if lop_type == rop_type {
                     self.add_inst(Inst::Sub(label, CalcMode::NSW, lop_type, lop, rop));
@@ -443,7 +956,7 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::MUL(blop, brop) => {
+            NodeKind::MUL(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
@@ -458,12 +971,17 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::DIV(blop, brop) => {
+            NodeKind::DIV(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Sdiv(label, lop_type, lop, rop));
+                    if unsigned {
+                        self.add_inst(Inst::Udiv(label, lop_type, lop, rop));
+                    } else {
+                        self.add_inst(Inst::Sdiv(label, lop_type, lop, rop));
+                    }
                     return (LLVMValue::VREG(label), rop_type);
                 } else {
                     Error::LLVM.found(&format!(
@@ -473,12 +991,17 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::MOD(blop, brop) => {
+            NodeKind::MOD(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Srem(label + 1, lop_type, lop, rop));
+                    if unsigned {
+                        self.add_inst(Inst::Urem(label, lop_type, lop, rop));
+                    } else {
+                        self.add_inst(Inst::Srem(label, lop_type, lop, rop));
+                    }
                     return (LLVMValue::VREG(label), rop_type);
                 } else {
                     Error::LLVM.found(&format!(
@@ -488,13 +1011,13 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::EQ(blop, brop) => {
+            NodeKind::EQ(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
                     self.add_inst(Inst::Icmp(label, CompareMode::EQUAL, lop_type, lop, rop));
-                    return (LLVMValue::VREG(label), rop_type);
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -503,13 +1026,13 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::NTEQ(blop, brop) => {
+            NodeKind::NTEQ(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
                     self.add_inst(Inst::Icmp(label, CompareMode::NOTEQUAL, lop_type, lop, rop));
-                    return (LLVMValue::VREG(label), rop_type);
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -518,13 +1041,19 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::LT(blop, brop) => {
+            NodeKind::LT(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Icmp(label, CompareMode::LESSTHAN, lop_type, lop, rop));
-                    return (LLVMValue::VREG(label), rop_type);
+                    let mode: CompareMode = if unsigned {
+                        CompareMode::ULESSTHAN
+                    } else {
+                        CompareMode::LESSTHAN
+                    };
+                    self.add_inst(Inst::Icmp(label, mode, lop_type, lop, rop));
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -533,19 +1062,19 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::GT(blop, brop) => {
+            NodeKind::GT(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Icmp(
-                        label,
-                        CompareMode::GREATERTHAN,
-                        lop_type,
-                        lop,
-                        rop,
-                    ));
-                    return (LLVMValue::VREG(label), rop_type);
+                    let mode: CompareMode = if unsigned {
+                        CompareMode::UGREATERTHAN
+                    } else {
+                        CompareMode::GREATERTHAN
+                    };
+                    self.add_inst(Inst::Icmp(label, mode, lop_type, lop, rop));
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -554,19 +1083,19 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::LTEQ(blop, brop) => {
+            NodeKind::LTEQ(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Icmp(
-                        label,
-                        CompareMode::LESSTHANEQUAL,
-                        lop_type,
-                        lop,
-                        rop,
-                    ));
-                    return (LLVMValue::VREG(label), rop_type);
+                    let mode: CompareMode = if unsigned {
+                        CompareMode::ULESSTHANEQUAL
+                    } else {
+                        CompareMode::LESSTHANEQUAL
+                    };
+                    self.add_inst(Inst::Icmp(label, mode, lop_type, lop, rop));
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -575,19 +1104,19 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::GTEQ(blop, brop) => {
+            NodeKind::GTEQ(blop, brop) => {
+                let unsigned: bool = self.is_unsigned(&blop) || self.is_unsigned(&brop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Icmp(
-                        label,
-                        CompareMode::GREATERTHANEQUAL,
-                        lop_type,
-                        lop,
-                        rop,
-                    ));
-                    return (LLVMValue::VREG(label), rop_type);
+                    let mode: CompareMode = if unsigned {
+                        CompareMode::UGREATERTHANEQUAL
+                    } else {
+                        CompareMode::GREATERTHANEQUAL
+                    };
+                    self.add_inst(Inst::Icmp(label, mode, lop_type, lop, rop));
+                    return (LLVMValue::VREG(label), LLVMType::I1);
                 } else {
                     Error::LLVM.found(&format!(
                         "type inference failed between {} and {}",
@@ -596,7 +1125,7 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::LSHIFT(blop, brop) => {
+            NodeKind::LSHIFT(blop, brop) => {
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
@@ -611,12 +1140,17 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::RSHIFT(blop, brop) => {
+            NodeKind::RSHIFT(blop, brop) => {
+                let signed: bool = !self.is_unsigned(&blop);
                 let (lop, lop_type) = self.build_expr(*blop);
                 let (rop, rop_type) = self.build_expr(*brop);
                 let label = self.label;
                 if lop_type == rop_type {
-                    self.add_inst(Inst::Ashr(label, lop_type, lop, rop));
+                    if signed {
+                        self.add_inst(Inst::Ashr(label, lop_type, lop, rop));
+                    } else {
+                        self.add_inst(Inst::Lshr(label, lop_type, lop, rop));
+                    }
                     return (LLVMValue::VREG(label), rop_type);
                 } else {
                     Error::LLVM.found(&format!(
@@ -626,7 +1160,75 @@ impl Function {
                     return (LLVMValue::UNKNOWN, LLVMType::UNKNOWN);
                 }
             }
-            Node::ARRAYLIT(elements, name) => {
+            NodeKind::BITAND(blop, brop) => {
+                let (lop, lop_type) = self.build_expr(*blop);
+                let (rop, rop_type) = self.build_expr(*brop);
+                let label = self.label;
+                if lop_type == rop_type {
+                    self.add_inst(Inst::And(label, lop_type, lop, rop));
+                    (LLVMValue::VREG(label), rop_type)
+                } else {
+                    Error::LLVM.found(&format!(
+                        "type inference failed between {} and {}",
+                        lop, rop
+                    ));
+                    (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
+                }
+            }
+            NodeKind::BITOR(blop, brop) => {
+                let (lop, lop_type) = self.build_expr(*blop);
+                let (rop, rop_type) = self.build_expr(*brop);
+                let label = self.label;
+                if lop_type == rop_type {
+                    self.add_inst(Inst::Or(label, lop_type, lop, rop));
+                    (LLVMValue::VREG(label), rop_type)
+                } else {
+                    Error::LLVM.found(&format!(
+                        "type inference failed between {} and {}",
+                        lop, rop
+                    ));
+                    (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
+                }
+            }
+            NodeKind::BITXOR(blop, brop) => {
+                let (lop, lop_type) = self.build_expr(*blop);
+                let (rop, rop_type) = self.build_expr(*brop);
+                let label = self.label;
+                if lop_type == rop_type {
+                    self.add_inst(Inst::Xor(label, lop_type, lop, rop));
+                    (LLVMValue::VREG(label), rop_type)
+                } else {
+                    Error::LLVM.found(&format!(
+                        "type inference failed between {} and {}",
+                        lop, rop
+                    ));
+                    (LLVMValue::UNKNOWN, LLVMType::UNKNOWN)
+                }
+            }
+            NodeKind::BITNOT(bchild) => {
+                let (child, child_type) = self.build_expr(*bchild);
+                let label = self.label;
+                self.add_inst(Inst::Xor(
+                    label,
+                    child_type.clone(),
+                    child,
+                    LLVMValue::INTEGER(-1),
+                ));
+                (LLVMValue::VREG(label), child_type)
+            }
+            NodeKind::MINUS(bchild) => {
+                let (child, child_type) = self.build_expr(*bchild);
+                let label = self.label;
+                self.add_inst(Inst::Sub(
+                    label,
+                    CalcMode::NSW,
+                    child_type.clone(),
+                    LLVMValue::INTEGER(0),
+                    child,
+                ));
+                (LLVMValue::VREG(label), child_type)
+            }
+            NodeKind::ARRAYLIT(elements, name) => {
                 for (i, elem) in elements.iter().enumerate() {
                     let (_elem_value, elem_type) = self.build_expr(elem.clone());
                     if i == elements.len() - 1 {
@@ -644,7 +1246,11 @@ impl Function {
     }
     fn get_llvmtype_from_type(&mut self, ty: &Type) -> LLVMType {
         match ty {
-            Type::INTEGER => LLVMType::I64,
+            Type::INTEGER(_, 8) => LLVMType::I8,
+            Type::INTEGER(_, 16) => LLVMType::I16,
+            Type::INTEGER(_, 32) => LLVMType::I32,
+            Type::INTEGER(_, _) => LLVMType::I64,
+            Type::BOOLEAN => LLVMType::I1,
             Type::POINTER(inner) => {
                 let inner_type = self.get_llvmtype_from_type(inner);
                 LLVMType::POINTER(Box::new(inner_type))
@@ -653,25 +1259,152 @@ impl Function {
                 let elem_type = self.get_llvmtype_from_type(elem);
                 LLVMType::ARRAY(Box::new(elem_type), *length)
             }
+            Type::FUNC(params, _ret) => {
+                let params = params
+                    .iter()
+                    .map(|p| self.get_llvmtype_from_type(p))
+                    .collect();
+                LLVMType::FUNC(params)
+            }
+            Type::ENUM(_, variants, _) => {
+                let fields: usize = variants.iter().map(|(_, f)| f.len()).max().unwrap_or(0);
+                LLVMType::ARRAY(Box::new(LLVMType::I64), 1 + fields)
+            }
             _ => LLVMType::UNKNOWN,
         }
     }
+    /* unsigned integers are shifted logically, divided and compared without the sign. */
+    fn is_unsigned(&self, n: &Node) -> bool {
+        match &n.kind {
+            NodeKind::IDENT(name) => self
+                .env
+                .get(name)
+                .or_else(|| self.globals.get(name))
+                .is_some_and(|symbol| !symbol.signed),
+            NodeKind::INDEX(base, _) | NodeKind::DEREFERENCE(base) => self.is_unsigned(base),
+            NodeKind::ADD(lch, rch)
+            | NodeKind::SUB(lch, rch)
+            | NodeKind::MUL(lch, rch)
+            | NodeKind::DIV(lch, rch)
+            | NodeKind::MOD(lch, rch)
+            | NodeKind::BITAND(lch, rch)
+            | NodeKind::BITOR(lch, rch)
+            | NodeKind::BITXOR(lch, rch) => self.is_unsigned(lch) || self.is_unsigned(rch),
+            NodeKind::LSHIFT(lch, _) | NodeKind::RSHIFT(lch, _) => self.is_unsigned(lch),
+            _ => false,
+        }
+    }
+    fn is_signed(&self, ty: &Type) -> bool {
+        match ty {
+            Type::INTEGER(signed, _) => *signed,
+            Type::BOOLEAN => false,
+            Type::POINTER(inner) => self.is_signed(inner),
+            Type::ARRAY(elem, _) => self.is_signed(elem),
+            _ => true,
+        }
+    }
+    /* narrower integers are extended to i64 when loaded, like the x64 backend does. */
+    fn widen(&mut self, value: LLVMValue, ty: LLVMType, signed: bool) -> (LLVMValue, LLVMType) {
+        match ty {
+            LLVMType::I8 | LLVMType::I16 | LLVMType::I32 => {
+                let label = self.label;
+                if signed {
+                    self.add_inst(Inst::Sext(label, ty, value, LLVMType::I64));
+                } else {
+                    self.add_inst(Inst::Zext(label, ty, value, LLVMType::I64));
+                }
+                (LLVMValue::VREG(label), LLVMType::I64)
+            }
+            _ => (value, ty),
+        }
+    }
+    /* truncate an i64 value before storing it into a narrower integer,
+    and cast the address from 'alloc' into the pointer it's stored. */
+    fn narrow(&mut self, value: LLVMValue, ty: LLVMType, dst_type: &LLVMType) -> LLVMValue {
+        match (&value, &ty, dst_type) {
+            (_, LLVMType::POINTER(src), LLVMType::POINTER(dst)) if src != dst => {
+                let label = self.label;
+                self.add_inst(Inst::BitCast(label, *src.clone(), value, *dst.clone()));
+                LLVMValue::VREG(label)
+            }
+            (LLVMValue::INTEGER(v), _, LLVMType::I8)
+            | (LLVMValue::INTEGER(v), _, LLVMType::I16)
+            | (LLVMValue::INTEGER(v), _, LLVMType::I32) => {
+                let mask: i128 = (1 << (dst_type.alignment() * 8)) - 1;
+                LLVMValue::INTEGER(v & mask)
+            }
+            (_, LLVMType::I64, LLVMType::I8)
+            | (_, LLVMType::I64, LLVMType::I16)
+            | (_, LLVMType::I64, LLVMType::I32) => {
+                let label = self.label;
+                self.add_inst(Inst::Trunc(label, ty, value, dst_type.clone()));
+                LLVMValue::VREG(label)
+            }
+            _ => value,
+        }
+    }
+    fn pointer_to_int(&mut self, value: LLVMValue, ty: LLVMType) -> (LLVMValue, LLVMType) {
+        if let LLVMType::POINTER(_) = ty {
+            let label = self.label;
+            self.add_inst(Inst::PtrToInt(label, ty, value, LLVMType::I64));
+            return (LLVMValue::VREG(label), LLVMType::I64);
+        }
+        (value, ty)
+    }
+    /* a global is used only when no local shadows it. */
+    fn is_global(&self, name: &str) -> bool {
+        !self.env.contains_key(name) && self.globals.contains_key(name)
+    }
     fn get_symbol_if_defined(&mut self, name: &str) -> &LLVMSymbol {
         if let Some(llvm_symbol) = self.env.get(name) {
             return llvm_symbol;
+        } else if let Some(llvm_symbol) = self.globals.get(name) {
+            return llvm_symbol;
         } else {
             Error::LLVM.found(&format!("{} is not defined", &name));
             return &LLVMSymbol {
                 label: 0,
                 ty: LLVMType::UNKNOWN,
+                signed: true,
             };
         }
     }
+    /* the same literals in the function share one '@.str' constant. */
+    fn build_string(&mut self, contents: String) -> (LLVMValue, LLVMType) {
+        let mut bytes: Vec<u8> = contents.into_bytes();
+        bytes.push(0x00);
+        let found = self.constants.iter().find_map(|c| match c {
+            Constant::Str(name, b) if b == &bytes => Some(name.to_string()),
+            _ => None,
+        });
+        let name: String = match found {
+            Some(name) => name,
+            None => {
+                let name = format!("@.str.{}.{}", self.name, self.const_label);
+                self.const_label += 1;
+                self.constants
+                    .push(Constant::Str(name.to_string(), bytes.clone()));
+                name
+            }
+        };
+        let value = LLVMValue::Const(format!(
+            "getelementptr inbounds ([{} x i8], [{} x i8]* {}, i64 0, i64 0)",
+            bytes.len(),
+            bytes.len(),
+            name
+        ));
+        (value, LLVMType::POINTER(Box::new(LLVMType::I8)))
+    }
     fn add_constant_array(&mut self, elements: Vec<Node>, ty: LLVMType, name: String) {
         let mut values: Vec<(LLVMType, LLVMValue)> = Vec::new();
         for elem in elements.iter() {
             let (elem_value, elem_type) = self.build_expr(elem.clone());
-            values.push((elem_type, elem_value));
+            if let LLVMType::ARRAY(array_elem_type, _) = &ty {
+                let elem_value = self.narrow(elem_value, elem_type, array_elem_type);
+                values.push((*array_elem_type.clone(), elem_value));
+            } else {
+                values.push((elem_type, elem_value));
+            }
         }
         let cons = Constant::Array(format!("@__const.{}.{}", self.name, name), ty, values);
         self.constants.push(cons);
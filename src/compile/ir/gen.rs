@@ -1,8 +1,8 @@
 use crate::ce::types::Error;
 use crate::compile::frontend;
 use crate::compile::ir::tac::{Operand, Tac};
-use frontend::frontmanager::frontmanager::FrontManager;
-use frontend::parse::node::Node;
+use frontend::frontmanager::frontmanager::{Env, FrontManager, Loop};
+use frontend::parse::node::{MatchArm, Node, NodeKind, Pattern};
 use frontend::sema::semantics::Type;
 
 use std::collections::BTreeMap;
@@ -12,16 +12,24 @@ impl FrontManager {
         let functions = self.functions.clone();
         for func in functions.iter() {
             self.cur_env = func.env.clone();
+            self.cur_scopes = func.scopes.clone();
             self.add(Tac::FUNCNAME(func.name.clone()));
             self.add(Tac::PROLOGUE(self.stack_offset));
             for (idx, arg) in func.args.iter().enumerate() {
                 let mut stack_offset: usize = 0;
-                if let Node::DEFARG(name) = arg {
+                let mut size: usize = 8;
+                if let NodeKind::DEFARG(name) = &arg.kind {
                     if let Some(sym) = self.get_symbol(name) {
                         stack_offset = sym.stack_offset;
                     }
+                    size = self.width_of(name).0;
+                }
+                self.add(Tac::PUSHARG(idx, stack_offset, size));
+            }
+            for arg in func.args.iter() {
+                if let NodeKind::DEFARG(name) = &arg.kind {
+                    self.copy_enum_arg(name);
                 }
-                self.add(Tac::PUSHARG(idx, stack_offset));
             }
             for st in func.stmts.iter() {
                 self.gen_stmt(st);
@@ -29,27 +37,48 @@ impl FrontManager {
         }
     }
     fn gen_stmt(&mut self, st: &Node) {
-        match st {
-            Node::LET(name, bexpr) | Node::ASSIGN(name, bexpr) => {
+        match &st.kind {
+            NodeKind::LET(name, bexpr) | NodeKind::ASSIGN(name, bexpr) => {
                 let expr_op: Operand = self.gen_expr(*bexpr.clone()).unwrap();
                 let mut stack_offset = 0;
                 if let Some(sym) = self.get_symbol(name) {
+                    if sym.is_global {
+                        let symbol: String = self.global_symbol(name);
+                        let value: Operand = self.wrap_to_width(expr_op, self.width_of(name));
+                        self.add(Tac::LET(Operand::GLOBAL(symbol), value));
+                        return;
+                    }
                     stack_offset = sym.stack_offset;
                 } else {
-                    Error::UNDEFINED.found(&format!("{} is not defined", &name));
+                    Error::UNDEFINED.found_at(&st.pos, &format!("{} is not defined", &name));
                 }
-                match *bexpr.clone() {
-                    Node::STRUCTLIT(_, _) => (),
-                    Node::ARRAYLIT(_, _) => (),
+                match bexpr.kind {
+                    NodeKind::STRUCTLIT(_, _) => (),
+                    NodeKind::ARRAYLIT(_, _) => (),
+                    NodeKind::ENUMLIT(_, _, _, _) => (),
                     _ => {
                         self.add(Tac::LET(
-                            Operand::ID(name.to_string(), stack_offset, None, None),
+                            Operand::ID(
+                                name.to_string(),
+                                stack_offset,
+                                None,
+                                None,
+                                self.width_of(name),
+                            ),
                             expr_op,
                         ));
                     }
                 }
             }
-            Node::IF(bcond, block, alter) => {
+            NodeKind::ASSIGNOP(name, op, bexpr) => {
+                /* 'x op= E' stores 'x op E' into x */
+                let value: Node = Node::compound_value(name, op, bexpr, &st.pos);
+                self.gen_stmt(&Node::new(
+                    NodeKind::ASSIGN(name.clone(), Box::new(value)),
+                    st.pos.clone(),
+                ));
+            }
+            NodeKind::IF(bcond, block, alter) => {
                 let cond_op: Operand = self.gen_expr(*bcond.clone()).unwrap();
                 let label: usize = self.label;
                 self.add(Tac::IFF(cond_op, format!(".L{}", label)));
@@ -65,7 +94,7 @@ impl FrontManager {
                     self.add(Tac::LABEL(format!(".L{}", label)));
                 }
             }
-            Node::CONDLOOP(bcond, block) => {
+            NodeKind::CONDLOOP(bcond, block, name) => {
                 let loop_label: usize = self.label;
                 self.add(Tac::LABEL(format!(".L{}", loop_label)));
                 self.label += 1;
@@ -73,73 +102,174 @@ impl FrontManager {
                 let break_label: usize = self.label;
                 self.add(Tac::IFF(cond_op, format!(".L{}", break_label)));
                 self.label += 1;
+                self.loops.push(Loop::new(
+                    name.clone(),
+                    format!(".L{}", loop_label),
+                    format!(".L{}", break_label),
+                ));
+                self.gen_stmt(block);
+                self.loops.pop();
+                self.add(Tac::GOTO(format!(".L{}", loop_label)));
+                self.add(Tac::LABEL(format!(".L{}", break_label)));
+            }
+            NodeKind::FOR(ident_name, bbegin, bend, block, name) => {
+                /* i = begin; condloop (i < end) { block; i = i + 1 } */
+                let pos = st.pos.clone();
+                let ident: Box<Node> =
+                    Box::new(Node::new(NodeKind::IDENT(ident_name.clone()), pos.clone()));
+                self.gen_stmt(&Node::new(
+                    NodeKind::LET(ident_name.clone(), bbegin.clone()),
+                    pos.clone(),
+                ));
+                let loop_label: usize = self.label;
+                self.add(Tac::LABEL(format!(".L{}", loop_label)));
+                self.label += 1;
+                let cond: Node = Node::new(NodeKind::LT(ident.clone(), bend.clone()), pos.clone());
+                let cond_op: Operand = self.gen_expr(cond).unwrap();
+                let continue_label: usize = self.label;
+                let break_label: usize = self.label + 1;
+                self.add(Tac::IFF(cond_op, format!(".L{}", break_label)));
+                self.label += 2;
+                self.loops.push(Loop::new(
+                    name.clone(),
+                    format!(".L{}", continue_label),
+                    format!(".L{}", break_label),
+                ));
                 self.gen_stmt(block);
+                self.loops.pop();
+                self.add(Tac::LABEL(format!(".L{}", continue_label)));
+                let one: Box<Node> = Box::new(Node::new(NodeKind::INTEGER(1), pos.clone()));
+                let step: Node = Node::new(NodeKind::ADD(ident, one), pos.clone());
+                self.gen_stmt(&Node::new(
+                    NodeKind::ASSIGN(ident_name.clone(), Box::new(step)),
+                    pos,
+                ));
                 self.add(Tac::GOTO(format!(".L{}", loop_label)));
                 self.add(Tac::LABEL(format!(".L{}", break_label)));
             }
-            Node::BLOCK(stmts) => {
+            NodeKind::BREAK(name) => {
+                let break_label: String = self.enclosing_loop(name).break_label.to_string();
+                self.add(Tac::GOTO(break_label));
+            }
+            NodeKind::CONTINUE(name) => {
+                let continue_label: String = self.enclosing_loop(name).continue_label.to_string();
+                self.add(Tac::GOTO(continue_label));
+            }
+            NodeKind::BLOCK(stmts, scope) => {
+                let env: Env = self.cur_scopes[*scope].clone();
+                let outer: Env = std::mem::replace(&mut self.cur_env, env);
                 for st in stmts.iter() {
                     self.gen_stmt(st);
                 }
+                self.cur_env = outer;
             }
-            Node::RETURN(bch) => {
+            NodeKind::MATCH(bexpr, arms) => self.gen_match(bexpr, arms),
+            NodeKind::RETURN(bch) => {
                 let ch: Node = *bch.clone();
                 let ret_op: Operand = self.gen_expr(ch).unwrap();
                 self.add(Tac::RET(ret_op));
             }
-            Node::LABEL(label) => {
+            NodeKind::LABEL(label) => {
                 self.add(Tac::LABEL(format!(".L{}", label)));
             }
-            Node::GOTO(label) => {
+            NodeKind::GOTO(label) => {
                 self.add(Tac::GOTO(format!(".L{}", label)));
             }
             _ => (),
         }
     }
     fn gen_expr(&mut self, n: Node) -> Option<Operand> {
-        match n {
-            Node::ADD(blop, brop) => self.add_binop(blop, brop, "+"),
-            Node::SUB(blop, brop) => self.add_binop(blop, brop, "-"),
-            Node::MUL(blop, brop) => self.add_binop(blop, brop, "*"),
-            Node::DIV(blop, brop) => self.add_binop(blop, brop, "/"),
-            Node::MOD(blop, brop) => self.add_binop(blop, brop, "%"),
-            Node::LT(blop, brop) => self.add_binop(blop, brop, "<"),
-            Node::GT(blop, brop) => self.add_binop(blop, brop, ">"),
-            Node::LSHIFT(blop, brop) => self.add_binop(blop, brop, "<<"),
-            Node::RSHIFT(blop, brop) => self.add_binop(blop, brop, ">>"),
-            Node::LTEQ(blop, brop) => self.add_binop(blop, brop, "<="),
-            Node::GTEQ(blop, brop) => self.add_binop(blop, brop, ">="),
-            Node::EQ(blop, brop) => self.add_binop(blop, brop, "=="),
-            Node::NTEQ(blop, brop) => self.add_binop(blop, brop, "!="),
-            Node::ADDRESS(blop) => self.add_unary(blop, "&"),
-            Node::DEREFERENCE(blop) => self.add_unary(blop, "*"),
-            Node::MINUS(blop) => self.add_unary(blop, "-"),
-            Node::CALL(name, bargs) => {
+        match n.kind {
+            NodeKind::ADD(blop, brop) => self.add_binop(blop, brop, "+"),
+            NodeKind::SUB(blop, brop) => self.add_binop(blop, brop, "-"),
+            NodeKind::MUL(blop, brop) => self.add_binop(blop, brop, "*"),
+            NodeKind::DIV(blop, brop) => self.add_signed_binop(blop, brop, "/"),
+            NodeKind::MOD(blop, brop) => self.add_signed_binop(blop, brop, "%"),
+            NodeKind::LT(blop, brop) => self.add_signed_binop(blop, brop, "<"),
+            NodeKind::GT(blop, brop) => self.add_signed_binop(blop, brop, ">"),
+            NodeKind::LSHIFT(blop, brop) => self.add_binop(blop, brop, "<<"),
+            NodeKind::RSHIFT(blop, brop) => {
+                if self.is_unsigned(&blop) {
+                    self.add_binop(blop, brop, ">>>")
+                } else {
+                    self.add_binop(blop, brop, ">>")
+                }
+            }
+            NodeKind::BITAND(blop, brop) => self.add_binop(blop, brop, "&"),
+            NodeKind::BITOR(blop, brop) => self.add_binop(blop, brop, "|"),
+            NodeKind::BITXOR(blop, brop) => self.add_binop(blop, brop, "^"),
+            NodeKind::LTEQ(blop, brop) => self.add_signed_binop(blop, brop, "<="),
+            NodeKind::GTEQ(blop, brop) => self.add_signed_binop(blop, brop, ">="),
+            NodeKind::EQ(blop, brop) => self.add_binop(blop, brop, "=="),
+            NodeKind::NTEQ(blop, brop) => self.add_binop(blop, brop, "!="),
+            NodeKind::ADDRESS(blop) => self.add_unary(blop, "&"),
+            NodeKind::DEREFERENCE(blop) => {
+                let op: String = deref_op(self.pointee_width(&blop));
+                self.add_unary(blop, &op)
+            }
+            NodeKind::MINUS(blop) => self.add_unary(blop, "-"),
+            NodeKind::NOT(blop) => self.add_unary(blop, "!"),
+            NodeKind::BITNOT(blop) => self.add_unary(blop, "~"),
+            NodeKind::LOGAND(blop, brop) => self.add_logical(blop, brop, true),
+            NodeKind::LOGOR(blop, brop) => self.add_logical(blop, brop, false),
+            NodeKind::CALL(name, bargs) => {
                 let args: Vec<Node> = *bargs.clone();
                 let len: usize = args.len();
                 for (idx, arg) in args.iter().enumerate() {
-                    let arg_op: Operand = self.gen_expr(arg.clone()).unwrap();
+                    let arg_op: Operand = self.gen_arg(arg);
                     self.add(Tac::PARAM(idx, arg_op));
                 }
                 Some(Operand::CALL(name, len))
             }
-            Node::STRUCTLIT(st_name, member_map) => {
+            /* the callee is evaluated first, the call itself follows the arguments. */
+            NodeKind::CALLPTR(bcallee, bargs) => {
+                let callee_op: Operand = self.gen_expr(*bcallee).unwrap();
+                for (idx, arg) in bargs.iter().enumerate() {
+                    let arg_op: Operand = self.gen_arg(arg);
+                    self.add(Tac::PARAM(idx, arg_op));
+                }
+                let virt = self.virt;
+                self.add(Tac::UNEX(
+                    Operand::REG(virt, 0, None, None),
+                    "call".to_string(),
+                    callee_op,
+                ));
+                self.virt += 1;
+                Some(Operand::REG(virt, 0, None, None))
+            }
+            NodeKind::FUNCADDR(name) => {
+                let virt = self.virt;
+                self.add(Tac::UNEX(
+                    Operand::REG(virt, 0, None, None),
+                    "&".to_string(),
+                    Operand::GLOBAL(name),
+                ));
+                self.virt += 1;
+                Some(Operand::REG(virt, 0, None, None))
+            }
+            NodeKind::STRUCTLIT(st_name, member_map) => {
                 let virt = self.virt;
                 let mut member_symbols = BTreeMap::new();
-                if let Some(sym) = self.cur_env.sym_table.get(&st_name) {
+                if let Some(sym) = self.cur_env.lookup(&st_name) {
                     if let Ok(s_ty) = &sym.ty {
                         if let Type::STRUCT(map, _) = s_ty {
                             member_symbols = map.clone();
                         }
                     }
                 } else {
-                    Error::UNDEFINED.found(&format!("{} is not defined", &st_name));
+                    Error::UNDEFINED.found_at(&n.pos, &format!("{} is not defined", &st_name));
                 }
                 for (member_name, member_expr) in member_map.iter() {
                     let member_op: Operand = self.gen_expr(member_expr.clone()).unwrap();
                     if let Some(member_s) = member_symbols.get(member_name) {
                         self.add(Tac::LET(
-                            Operand::ID(st_name.to_string(), 0, None, Some(member_s.stack_offset)),
+                            Operand::ID(
+                                st_name.to_string(),
+                                0,
+                                None,
+                                Some(member_s.stack_offset),
+                                width_from_type(member_s.ty.as_ref().unwrap_or(&Type::UNKNOWN)),
+                            ),
                             member_op,
                         ));
                     }
@@ -147,13 +277,53 @@ impl FrontManager {
                 self.virt += 1;
                 Some(Operand::REG(virt, 0, None, None))
             }
-            Node::ARRAYLIT(belems, name) => {
+            /* the tag is placed at the bottom of the variable, the fields follow it. */
+            NodeKind::ENUMLIT(enum_name, variant, args, target) => {
+                let stack_offset: usize = match self.get_symbol(&target) {
+                    Some(sym) => sym.stack_offset,
+                    None => {
+                        Error::UNDEFINED.found_at(&n.pos, &format!("{} is not defined", &target));
+                        0
+                    }
+                };
+                let enum_type: Type = self
+                    .cur_env
+                    .lookup_type(&enum_name)
+                    .cloned()
+                    .unwrap_or(Type::UNKNOWN);
+                let (tag, fields) = match enum_type.variant(&variant) {
+                    Some((tag, fields)) => (tag, fields.clone()),
+                    None => (0, Vec::new()),
+                };
+                for (k, (arg, offset)) in
+                    args.iter().zip(Type::payload_offsets(&fields)).enumerate()
+                {
+                    let arg_op: Operand = self.gen_expr(arg.clone()).unwrap();
+                    self.add(Tac::LET(
+                        Operand::ID(
+                            format!("{}.{}", target, k),
+                            0,
+                            None,
+                            Some(stack_offset - 8 - offset),
+                            width_from_type(&fields[k]),
+                        ),
+                        arg_op,
+                    ));
+                }
+                self.add(Tac::LET(
+                    Operand::ID(target, stack_offset, None, None, (8, true)),
+                    Operand::INTLIT(tag as i128),
+                ));
+                Some(Operand::INTLIT(tag as i128))
+            }
+            NodeKind::ARRAYLIT(belems, name) => {
                 let mut stack_offset = 0;
-                if let Some(sym) = self.cur_env.sym_table.get(&name) {
+                if let Some(sym) = self.cur_env.lookup(&name) {
                     stack_offset = sym.stack_offset;
                 } else {
-                    Error::UNDEFINED.found(&format!("{} is not defined", name));
+                    Error::UNDEFINED.found_at(&n.pos, &format!("{} is not defined", name));
                 }
+                let width = self.width_of(&name);
                 for (idx, elem) in belems.iter().enumerate() {
                     let elem_op: Operand = self.gen_expr(elem.clone()).unwrap();
                     self.add(Tac::LET(
@@ -162,21 +332,35 @@ impl FrontManager {
                             stack_offset,
                             Some(Box::new(Operand::INTLIT(idx as i128))),
                             None,
+                            width,
                         ),
                         elem_op,
                     ));
                 }
-                Some(Operand::ID(name, stack_offset, None, None))
+                Some(Operand::ID(name, stack_offset, None, None, width))
             }
-            Node::INDEX(bbase, bindex) => {
+            /* indexing a pointer reads the element at base + index * size. */
+            NodeKind::INDEX(bbase, bindex) if self.pointee_width(&bbase).is_some() => {
+                let width: (usize, bool) = self.pointee_width(&bbase).unwrap();
+                let mut offset: Box<Node> = bindex;
+                if width.0 != 1 {
+                    let size =
+                        Box::new(Node::new(NodeKind::INTEGER(width.0 as i128), n.pos.clone()));
+                    offset = Box::new(Node::new(NodeKind::MUL(offset, size), n.pos.clone()));
+                }
+                let address = Box::new(Node::new(NodeKind::ADD(bbase, offset), n.pos.clone()));
+                self.add_unary(address, &deref_op(Some(width)))
+            }
+            NodeKind::INDEX(bbase, bindex) => {
                 let base_op: Operand = self.gen_expr(*bbase.clone()).unwrap();
                 let index_op: Operand = self.gen_expr(*bindex.clone()).unwrap();
                 match base_op {
-                    Operand::ID(name, stack_offset, _, _) => Some(Operand::ID(
+                    Operand::ID(name, stack_offset, _, _, width) => Some(Operand::ID(
                         name,
                         stack_offset,
                         Some(Box::new(index_op)),
                         None,
+                        width,
                     )),
                     Operand::REG(_virt, _phys, _, _) => {
                         Some(Operand::REG(self.virt, 0, Some(Box::new(index_op)), None))
@@ -184,10 +368,35 @@ impl FrontManager {
                     _ => None,
                 }
             }
-            Node::MEMBER(st, member) => {
+            NodeKind::MEMBER(st, member) => {
                 let struct_op: Operand = self.gen_expr(*st.clone()).unwrap();
+                /* 'self.x' loads the member at the offset from the pointer. */
+                let st_type = st
+                    .name()
+                    .and_then(|name| self.get_symbol(&name))
+                    .map(|s| s.ty);
+                if let Some(Ok(Type::POINTER(inner))) = st_type {
+                    if let Some((offset, member_type)) = inner.member_offset(&member) {
+                        let addr = self.virt;
+                        self.virt += 1;
+                        self.add(Tac::EX(
+                            Operand::REG(addr, 0, None, None),
+                            "+".to_string(),
+                            struct_op,
+                            Operand::INTLIT(offset as i128),
+                        ));
+                        let virt = self.virt;
+                        self.virt += 1;
+                        self.add(Tac::UNEX(
+                            Operand::REG(virt, 0, None, None),
+                            deref_op(Some(width_from_type(&member_type))),
+                            Operand::REG(addr, 0, None, None),
+                        ));
+                        return Some(Operand::REG(virt, 0, None, None));
+                    }
+                }
                 match struct_op {
-                    Operand::ID(name, stack_offset, _, _) => {
+                    Operand::ID(name, stack_offset, _, _, _) => {
                         if let Some(s) = self.get_symbol(&name) {
                             if let Ok(st_ty) = s.ty {
                                 if let Type::STRUCT(map, _) = st_ty {
@@ -197,6 +406,9 @@ impl FrontManager {
                                             stack_offset,
                                             None,
                                             Some(member_s.stack_offset),
+                                            width_from_type(
+                                                member_s.ty.as_ref().unwrap_or(&Type::UNKNOWN),
+                                            ),
                                         ));
                                     }
                                 }
@@ -210,20 +422,227 @@ impl FrontManager {
                     _ => None,
                 }
             }
-            Node::IDENT(name) => {
+            NodeKind::IDENT(name) => {
                 let mut stack_offset = 0;
-                if let Some(sym) = self.cur_env.sym_table.get(&name) {
+                if let Some(sym) = self.cur_env.lookup(&name) {
+                    /* a global is loaded into a register where it is read. */
+                    if sym.is_global {
+                        let virt = self.virt;
+                        self.virt += 1;
+                        self.add(Tac::LET(
+                            Operand::REG(virt, 0, None, None),
+                            Operand::GLOBAL(self.global_symbol(&name)),
+                        ));
+                        return Some(Operand::REG(virt, 0, None, None));
+                    }
                     stack_offset = sym.stack_offset;
                 } else {
-                    Error::UNDEFINED.found(&format!("{} is not defined", &name));
+                    Error::UNDEFINED.found_at(&n.pos, &format!("{} is not defined", &name));
                 }
-                Some(Operand::ID(name, stack_offset, None, None))
+                let width = self.width_of(&name);
+                Some(Operand::ID(name, stack_offset, None, None, width))
+            }
+            NodeKind::INTEGER(val) => Some(Operand::INTLIT(val)),
+            NodeKind::BOOLEAN(val) => Some(Operand::INTLIT(val as i128)),
+            /* the same literals share one object in .rodata. */
+            NodeKind::STRLIT(contents) => {
+                let idx: usize = match self.strings.iter().position(|s| s == &contents) {
+                    Some(idx) => idx,
+                    None => {
+                        self.strings.push(contents);
+                        self.strings.len() - 1
+                    }
+                };
+                let virt = self.virt;
+                self.virt += 1;
+                self.add(Tac::LET(
+                    Operand::REG(virt, 0, None, None),
+                    Operand::STRLIT(idx),
+                ));
+                Some(Operand::REG(virt, 0, None, None))
             }
-            Node::INTEGER(val) => Some(Operand::INTLIT(val)),
 
             _ => None,
         }
     }
+    /* the arms are tested in order, the last one needs no test since the match is exhaustive. */
+    fn gen_match(&mut self, bexpr: &Node, arms: &[MatchArm]) {
+        let name: String = match &bexpr.kind {
+            NodeKind::IDENT(name) => name.to_string(),
+            _ => return,
+        };
+        let (stack_offset, enum_type) = match self.get_symbol(&name) {
+            Some(sym) => (sym.stack_offset, sym.ty.unwrap_or(Type::UNKNOWN)),
+            None => return,
+        };
+        let end_label: usize = self.label;
+        self.label += 1;
+        for (idx, arm) in arms.iter().enumerate() {
+            let next_label: usize = self.label;
+            self.label += 1;
+            let mut fields: Vec<Type> = Vec::new();
+            if let Pattern::VARIANT(_, variant, _) = &arm.pattern {
+                if let Some((tag, types)) = enum_type.variant(variant) {
+                    fields = types.clone();
+                    if idx + 1 < arms.len() {
+                        let virt = self.virt;
+                        self.virt += 1;
+                        self.add(Tac::EX(
+                            Operand::REG(virt, 0, None, None),
+                            "==".to_string(),
+                            Operand::ID(name.to_string(), stack_offset, None, None, (8, true)),
+                            Operand::INTLIT(tag as i128),
+                        ));
+                        self.add(Tac::IFF(
+                            Operand::REG(virt, 0, None, None),
+                            format!(".L{}", next_label),
+                        ));
+                    }
+                }
+            }
+            let env: Env = self.cur_scopes[arm.scope].clone();
+            let outer: Env = std::mem::replace(&mut self.cur_env, env);
+            if let Pattern::VARIANT(_, _, bindings) = &arm.pattern {
+                let offsets: Vec<usize> = Type::payload_offsets(&fields);
+                for (k, binding) in bindings.iter().enumerate() {
+                    if binding == "_" || k >= fields.len() {
+                        continue;
+                    }
+                    let binding_offset: usize = match self.get_symbol(binding) {
+                        Some(sym) => sym.stack_offset,
+                        None => continue,
+                    };
+                    let width: (usize, bool) = width_from_type(&fields[k]);
+                    self.add(Tac::LET(
+                        Operand::ID(binding.to_string(), binding_offset, None, None, width),
+                        Operand::ID(
+                            format!("{}.{}", name, k),
+                            0,
+                            None,
+                            Some(stack_offset - 8 - offsets[k]),
+                            width,
+                        ),
+                    ));
+                }
+            }
+            self.gen_stmt(&arm.body);
+            self.cur_env = outer;
+            self.add(Tac::GOTO(format!(".L{}", end_label)));
+            self.add(Tac::LABEL(format!(".L{}", next_label)));
+        }
+        self.add(Tac::LABEL(format!(".L{}", end_label)));
+    }
+    /* an enum is passed by its address, the other arguments by their values. */
+    fn gen_arg(&mut self, arg: &Node) -> Operand {
+        if let NodeKind::IDENT(name) = &arg.kind {
+            if let Some(Ok(Type::ENUM(_, _, _))) = self.get_symbol(name).map(|s| s.ty) {
+                return self.add_unary(Box::new(arg.clone()), "&").unwrap();
+            }
+        }
+        self.gen_expr(arg.clone()).unwrap()
+    }
+    /* the enum parameter holds the address of the caller's one, it's copied into the frame
+     * from the end, so the address in the tag is overwritten last. */
+    fn copy_enum_arg(&mut self, name: &str) {
+        let (stack_offset, size) = match self.get_symbol(name) {
+            Some(sym) => match sym.ty {
+                Ok(Type::ENUM(_, _, size)) => (sym.stack_offset, size),
+                _ => return,
+            },
+            None => return,
+        };
+        let mut chunks: Vec<(usize, usize)> = Vec::new();
+        let mut at: usize = 0;
+        while at < size {
+            let width: usize = [8, 4, 2, 1]
+                .iter()
+                .copied()
+                .find(|w| at + w <= size)
+                .unwrap();
+            chunks.push((at, width));
+            at += width;
+        }
+        for (at, width) in chunks.into_iter().rev() {
+            let address = self.virt;
+            self.add(Tac::EX(
+                Operand::REG(address, 0, None, None),
+                "+".to_string(),
+                Operand::ID(name.to_string(), stack_offset, None, None, (8, true)),
+                Operand::INTLIT(at as i128),
+            ));
+            let value = self.virt + 1;
+            self.virt += 2;
+            self.add(Tac::UNEX(
+                Operand::REG(value, 0, None, None),
+                deref_op(Some((width, false))),
+                Operand::REG(address, 0, None, None),
+            ));
+            let dst: Operand = if at == 0 {
+                Operand::ID(name.to_string(), stack_offset, None, None, (8, true))
+            } else {
+                Operand::ID(
+                    format!("{}.{}", name, at),
+                    0,
+                    None,
+                    Some(stack_offset - at),
+                    (width, false),
+                )
+            };
+            self.add(Tac::LET(dst, Operand::REG(value, 0, None, None)));
+        }
+    }
+    /* sema has already checked that the loop exists. */
+    fn enclosing_loop(&self, name: &Option<String>) -> &Loop {
+        self.loops
+            .iter()
+            .rev()
+            .find(|l| name.is_none() || &l.name == name)
+            .unwrap()
+    }
+    /* the globals are referred by their mangled names in the object. */
+    fn global_symbol(&self, name: &str) -> String {
+        match self.globals.iter().find(|g| g.name == name) {
+            Some(g) => g.symbol.to_string(),
+            None => name.to_string(),
+        }
+    }
+    fn width_of(&self, name: &str) -> (usize, bool) {
+        if let Some(sym) = self.get_symbol(name) {
+            if let Ok(ty) = sym.ty {
+                return width_from_type(&ty);
+            }
+        }
+        (8, true)
+    }
+    /* the width of the memory the pointer-typed expression points. */
+    fn pointee_width(&self, n: &Node) -> Option<(usize, bool)> {
+        match &n.kind {
+            NodeKind::IDENT(name) => match self.get_symbol(name)?.ty {
+                Ok(Type::POINTER(inner)) => Some(width_from_type(&inner)),
+                _ => None,
+            },
+            NodeKind::STRLIT(_) => Some((1, false)),
+            _ => None,
+        }
+    }
+    /* unsigned integers are shifted logically, divided and compared without the sign. */
+    fn is_unsigned(&self, n: &Node) -> bool {
+        match &n.kind {
+            NodeKind::IDENT(name) => !self.width_of(name).1,
+            NodeKind::INDEX(base, _) => self.is_unsigned(base),
+            NodeKind::DEREFERENCE(ptr) => self.pointee_width(ptr).is_some_and(|w| !w.1),
+            NodeKind::ADD(lch, rch)
+            | NodeKind::SUB(lch, rch)
+            | NodeKind::MUL(lch, rch)
+            | NodeKind::DIV(lch, rch)
+            | NodeKind::MOD(lch, rch)
+            | NodeKind::BITAND(lch, rch)
+            | NodeKind::BITOR(lch, rch)
+            | NodeKind::BITXOR(lch, rch) => self.is_unsigned(lch) || self.is_unsigned(rch),
+            NodeKind::LSHIFT(lch, _) | NodeKind::RSHIFT(lch, _) => self.is_unsigned(lch),
+            _ => false,
+        }
+    }
     fn add(&mut self, tac: Tac) {
         self.tacs.push(tac);
     }
@@ -238,6 +657,68 @@ impl FrontManager {
         self.virt += 1;
         Some(Operand::REG(virt, 0, None, None))
     }
+    /* evaluate the right operand only when the left one doesn't decide the result. */
+    fn add_logical(&mut self, blop: Box<Node>, brop: Box<Node>, is_and: bool) -> Option<Operand> {
+        let false_label: usize = self.label;
+        let true_label: usize = self.label + 1;
+        let end_label: usize = self.label + 2;
+        self.label += 3;
+        let lop: Operand = self.gen_expr(*blop.clone()).unwrap();
+        if is_and {
+            self.add(Tac::IFF(lop, format!(".L{}", false_label)));
+        } else {
+            let rhs_label: usize = self.label;
+            self.label += 1;
+            self.add(Tac::IFF(lop, format!(".L{}", rhs_label)));
+            self.add(Tac::GOTO(format!(".L{}", true_label)));
+            self.add(Tac::LABEL(format!(".L{}", rhs_label)));
+        }
+        let rop: Operand = self.gen_expr(*brop.clone()).unwrap();
+        self.add(Tac::IFF(rop, format!(".L{}", false_label)));
+        let virt = self.virt;
+        self.virt += 1;
+        self.add(Tac::LABEL(format!(".L{}", true_label)));
+        self.add(Tac::LET(
+            Operand::REG(virt, 0, None, None),
+            Operand::INTLIT(1),
+        ));
+        self.add(Tac::GOTO(format!(".L{}", end_label)));
+        self.add(Tac::LABEL(format!(".L{}", false_label)));
+        self.add(Tac::LET(
+            Operand::REG(virt, 0, None, None),
+            Operand::INTLIT(0),
+        ));
+        self.add(Tac::LABEL(format!(".L{}", end_label)));
+        Some(Operand::REG(virt, 0, None, None))
+    }
+    /* 'op' followed by 'u' is the operation on the unsigned integers */
+    fn add_signed_binop(&mut self, blop: Box<Node>, brop: Box<Node>, op: &str) -> Option<Operand> {
+        if self.is_unsigned(&blop) || self.is_unsigned(&brop) {
+            return self.add_binop(blop, brop, &format!("{}u", op));
+        }
+        self.add_binop(blop, brop, op)
+    }
+    /* a global takes a quad word, so the narrower value is shifted up and back to wrap it. */
+    fn wrap_to_width(&mut self, op: Operand, width: (usize, bool)) -> Operand {
+        let (size, signed) = width;
+        if size >= 8 {
+            return op;
+        }
+        let bits: i128 = (64 - size * 8) as i128;
+        let mut value: Operand = op;
+        for shift in ["<<", if signed { ">>" } else { ">>>" }] {
+            let virt = self.virt;
+            self.add(Tac::EX(
+                Operand::REG(virt, 0, None, None),
+                String::from(shift),
+                value,
+                Operand::INTLIT(bits),
+            ));
+            self.virt += 1;
+            value = Operand::REG(virt, 0, None, None);
+        }
+        value
+    }
     fn add_binop(&mut self, blop: Box<Node>, brop: Box<Node>, op: &str) -> Option<Operand> {
         let lop: Operand = self.gen_expr(*blop.clone()).unwrap();
         let rop: Operand = self.gen_expr(*brop.clone()).unwrap();
@@ -252,3 +733,24 @@ impl FrontManager {
         Some(Operand::REG(virt, 0, None, None))
     }
 }
+
+/* '*' loads 8 bytes, the narrower loads carry the width like '*u8' or '*i32'. */
+fn deref_op(width: Option<(usize, bool)>) -> String {
+    match width {
+        Some((size, signed)) if size < 8 => {
+            format!("*{}{}", if signed { "i" } else { "u" }, size * 8)
+        }
+        _ => "*".to_string(),
+    }
+}
+
+/* the width of the memory each access to the variable touches. */
+fn width_from_type(ty: &Type) -> (usize, bool) {
+    match ty {
+        Type::INTEGER(signed, bitsize) => (bitsize / 8, *signed),
+        Type::BOOLEAN => (1, false),
+        Type::ARRAY(elem, _) => width_from_type(elem),
+        Type::ALIAS(alt) => width_from_type(alt),
+        _ => (8, true),
+    }
+}
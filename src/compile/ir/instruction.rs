@@ -13,6 +13,7 @@ type IndexValue = LLVMValue;
 type TotalSize = usize;
 type Alignment = usize;
 type FuncName = String;
+type GlobalName = String;
 type Expr = LLVMValue;
 type ReturnType = LLVMType;
 type SrcType = LLVMType;
@@ -22,6 +23,9 @@ type Lop = LLVMValue;
 type Rop = LLVMValue;
 type Args = Vec<(LLVMValue, LLVMType)>;
 type IsVolatile = bool;
+type Incomings = Vec<(LLVMValue, String)>;
+type DefaultLabel = usize;
+type Cases = Vec<(i128, Label)>;
 
 #[derive(Clone)]
 pub enum Instruction {
@@ -29,22 +33,38 @@ pub enum Instruction {
     RetVoid,
     Alloca(Label, DstType, Alignment),
     Store(DstType, Expr, Label, Alignment),
+    StoreGlobal(DstType, Expr, GlobalName, Alignment),
     Load(Label, DstType, DstReg, Alignment),
     Add(Label, CalcMode, ReturnType, Lop, Rop),
     Sub(Label, CalcMode, ReturnType, Lop, Rop),
     Mul(Label, CalcMode, ReturnType, Lop, Rop),
     Sdiv(Label, ReturnType, Lop, Rop),
     Srem(Label, ReturnType, Lop, Rop),
+    Udiv(Label, ReturnType, Lop, Rop),
+    Urem(Label, ReturnType, Lop, Rop),
     Icmp(Label, CompareMode, ReturnType, Lop, Rop),
     Shl(Label, ReturnType, Lop, Rop),
     Ashr(Label, ReturnType, Lop, Rop),
+    Lshr(Label, ReturnType, Lop, Rop),
+    And(Label, ReturnType, Lop, Rop),
+    Or(Label, ReturnType, Lop, Rop),
+    Xor(Label, ReturnType, Lop, Rop),
+    Phi(Label, ReturnType, Incomings),
     Call(Label, ReturnType, FuncName, Args),
+    CallPtr(Label, Expr, Args),
     BitCast(Label, SrcType, Expr, DstType),
+    PtrToInt(Label, SrcType, Expr, DstType),
+    IntToPtr(Label, SrcType, Expr, DstType),
+    Sext(Label, SrcType, Expr, DstType),
+    Zext(Label, SrcType, Expr, DstType),
+    Trunc(Label, SrcType, Expr, DstType),
     GetElementPtrInbounds(Label, ReturnType, Expr, IndexType, IndexValue),
+    GetElementPtr(Label, ReturnType, Expr, IndexType, IndexValue),
     UnconditionalBranch(Label),
     ConditionalBranch(SrcType, Expr, TrueLabel, FalseLabel),
+    Switch(SrcType, Expr, DefaultLabel, Cases),
 
-    Memcpy64(Expr, Expr, TotalSize, IsVolatile),
+    Memcpy64(Expr, Expr, TotalSize, Alignment, IsVolatile),
     DoNothing,
     NOP,
 }
@@ -68,6 +88,10 @@ pub enum CompareMode {
     GREATERTHANEQUAL,
     LESSTHAN,
     LESSTHANEQUAL,
+    UGREATERTHAN,
+    UGREATERTHANEQUAL,
+    ULESSTHAN,
+    ULESSTHANEQUAL,
 }
 impl fmt::Display for CompareMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -78,6 +102,10 @@ impl fmt::Display for CompareMode {
             Self::GREATERTHANEQUAL => write!(f, "sge"),
             Self::LESSTHAN => write!(f, "slt"),
             Self::LESSTHANEQUAL => write!(f, "sle"),
+            Self::UGREATERTHAN => write!(f, "ugt"),
+            Self::UGREATERTHANEQUAL => write!(f, "uge"),
+            Self::ULESSTHAN => write!(f, "ult"),
+            Self::ULESSTHANEQUAL => write!(f, "ule"),
         }
     }
 }
@@ -94,6 +122,10 @@ impl Instruction {
                 "  store {} {}, {}* %{}, align {}",
                 ty, v, ty, label, alignment
             ),
+            Self::StoreGlobal(ty, v, name, alignment) => println!(
+                "  store {} {}, {}* @{}, align {}",
+                ty, v, ty, name, alignment
+            ),
             Self::Load(label, ty, v, alignment) => println!(
                 "  %{} = load {}, {}* {}, align {}",
                 label, ty, ty, v, alignment
@@ -116,12 +148,38 @@ impl Instruction {
             Self::Srem(label, return_type, lop, rop) => {
                 println!("  %{} = srem {} {}, {}", label, return_type, lop, rop)
             }
+            Self::Udiv(label, return_type, lop, rop) => {
+                println!("  %{} = udiv {} {}, {}", label, return_type, lop, rop)
+            }
+            Self::Urem(label, return_type, lop, rop) => {
+                println!("  %{} = urem {} {}, {}", label, return_type, lop, rop)
+            }
             Self::Shl(label, return_type, lop, rop) => {
                 println!("  %{} = shl {} {}, {}", label, return_type, lop, rop)
             }
             Self::Ashr(label, return_type, lop, rop) => {
                 println!("  %{} = ashr {} {}, {}", label, return_type, lop, rop)
             }
+            Self::Lshr(label, return_type, lop, rop) => {
+                println!("  %{} = lshr {} {}, {}", label, return_type, lop, rop)
+            }
+            Self::And(label, return_type, lop, rop) => {
+                println!("  %{} = and {} {}, {}", label, return_type, lop, rop)
+            }
+            Self::Or(label, return_type, lop, rop) => {
+                println!("  %{} = or {} {}, {}", label, return_type, lop, rop)
+            }
+            Self::Xor(label, return_type, lop, rop) => {
+                println!("  %{} = xor {} {}, {}", label, return_type, lop, rop)
+            }
+            Self::Phi(label, return_type, incomings) => {
+                let incoming_string = incomings
+                    .iter()
+                    .map(|(value, block)| format!("[ {}, %{} ]", value, block))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                println!("  %{} = phi {} {}", label, return_type, incoming_string)
+            }
             Self::Icmp(label, compare_type, return_type, lop, rop) => println!(
                 "  %{} = icmp {} {} {}, {}",
                 label, compare_type, return_type, lop, rop
@@ -148,13 +206,41 @@ impl Instruction {
                     label, return_type, func_name, arg_string
                 );
             }
+            Self::CallPtr(label, callee, args) => {
+                let arg_string = args
+                    .iter()
+                    .map(|(arg_value, arg_type)| format!("{} {}", arg_type, arg_value))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                println!("  %{} = call i64 {}({})", label, callee, arg_string);
+            }
             Self::BitCast(label, src_type, target, dst_type) => println!(
                 "  %{} = bitcast {}* {} to {}*",
                 label, src_type, target, dst_type
             ),
-            Self::Memcpy64(dst, src, total_size, is_volatile) => println!(
-            "  call void @llvm.memcpy.p0i8.p0i8.i64(i8* align 16 {}, i8* align 16 {}, i64 {}, i1 {:?})"
-            ,dst,src,total_size,is_volatile,
+            Self::PtrToInt(label, src_type, target, dst_type) => println!(
+                "  %{} = ptrtoint {} {} to {}",
+                label, src_type, target, dst_type
+            ),
+            Self::IntToPtr(label, src_type, target, dst_type) => println!(
+                "  %{} = inttoptr {} {} to {}",
+                label, src_type, target, dst_type
+            ),
+            Self::Sext(label, src_type, target, dst_type) => println!(
+                "  %{} = sext {} {} to {}",
+                label, src_type, target, dst_type
+            ),
+            Self::Zext(label, src_type, target, dst_type) => println!(
+                "  %{} = zext {} {} to {}",
+                label, src_type, target, dst_type
+            ),
+            Self::Trunc(label, src_type, target, dst_type) => println!(
+                "  %{} = trunc {} {} to {}",
+                label, src_type, target, dst_type
+            ),
+            Self::Memcpy64(dst, src, total_size, alignment, is_volatile) => println!(
+            "  call void @llvm.memcpy.p0i8.p0i8.i64(i8* align {} {}, i8* align {} {}, i64 {}, i1 {:?})"
+            ,alignment,dst,alignment,src,total_size,is_volatile,
             ),
             Self::DoNothing => println!(
                 "  call void @llvm.donothing()"
@@ -163,10 +249,25 @@ impl Instruction {
                 "  %{} = getelementptr inbounds {}, {}* {}, i64 0, {} {}",
                 label,return_type,return_type,target,idx_type,idx_value
             ),
+            Self::GetElementPtr(label,elem_type,target,idx_type,idx_value) => println!(
+                "  %{} = getelementptr inbounds {}, {}* {}, {} {}",
+                label,elem_type,elem_type,target,idx_type,idx_value
+            ),
             Self::UnconditionalBranch(label) => println!("  br label %{}",label),
             Self::ConditionalBranch(cond_type,cond_value,true_label,false_label) => println!(
                 "  br {} {}, label %{}, label %{}",
                 cond_type,cond_value,true_label,false_label),
+            Self::Switch(cond_type,cond_value,default_label,cases) => {
+                let case_string = cases
+                    .iter()
+                    .map(|(value, label)| format!("{} {}, label %{}", cond_type, value, label))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                println!(
+                    "  switch {} {}, label %{} [ {} ]",
+                    cond_type, cond_value, default_label, case_string
+                )
+            }
             Self::NOP => (),
         }
     }
@@ -2,4 +2,7 @@
 pub enum Intrinsic {
     Memcpy,
     DoNothing,
+    Syscall,
+    Alloc,
+    Free,
 }
@@ -8,6 +8,8 @@ pub enum IR {
     STOREIMM(REG, i128),
     STOREMEM(REG, OFFSET),
     STORECALL(REG, SYMBOL),
+    STORESIZEDREG(OFFSET, REG, usize),
+    STORESIZEDIMM(OFFSET, i128, usize),
     ADDREG(REG, REG),
     ADDIMM(REG, i128),
     ADDMEM(REG, OFFSET),
@@ -20,27 +22,57 @@ pub enum IR {
     DIVREG(REG, REG),
     DIVIMM(REG, i128),
     DIVMEM(REG, OFFSET),
+    UDIVREG(REG, REG),
+    UDIVIMM(REG, i128),
+    UDIVMEM(REG, OFFSET),
     MODREG(REG, REG),
     MODIMM(REG, i128),
     MODMEM(REG, OFFSET),
+    UMODREG(REG, REG),
+    UMODIMM(REG, i128),
+    UMODMEM(REG, OFFSET),
     LSHIFTREG(REG, REG),
     LSHIFTIMM(REG, i128),
     LSHIFTMEM(REG, OFFSET),
     RSHIFTREG(REG, REG),
     RSHIFTIMM(REG, i128),
     RSHIFTMEM(REG, OFFSET),
+    URSHIFTREG(REG, REG),
+    URSHIFTIMM(REG, i128),
+    URSHIFTMEM(REG, OFFSET),
+    ANDREG(REG, REG),
+    ANDIMM(REG, i128),
+    ANDMEM(REG, OFFSET),
+    ORREG(REG, REG),
+    ORIMM(REG, i128),
+    ORMEM(REG, OFFSET),
+    XORREG(REG, REG),
+    XORIMM(REG, i128),
+    XORMEM(REG, OFFSET),
     LTREG(REG, REG),
     LTIMM(REG, i128),
     LTMEM(REG, OFFSET),
+    ULTREG(REG, REG),
+    ULTIMM(REG, i128),
+    ULTMEM(REG, OFFSET),
     GTREG(REG, REG),
     GTIMM(REG, i128),
     GTMEM(REG, OFFSET),
+    UGTREG(REG, REG),
+    UGTIMM(REG, i128),
+    UGTMEM(REG, OFFSET),
     LTEQREG(REG, REG),
     LTEQIMM(REG, i128),
     LTEQMEM(REG, OFFSET),
+    ULTEQREG(REG, REG),
+    ULTEQIMM(REG, i128),
+    ULTEQMEM(REG, OFFSET),
     GTEQREG(REG, REG),
     GTEQIMM(REG, i128),
     GTEQMEM(REG, OFFSET),
+    UGTEQREG(REG, REG),
+    UGTEQIMM(REG, i128),
+    UGTEQMEM(REG, OFFSET),
     EQREG(REG, REG),
     EQIMM(REG, i128),
     EQMEM(REG, OFFSET),
@@ -48,17 +80,26 @@ pub enum IR {
     NTEQIMM(REG, i128),
     NTEQMEM(REG, OFFSET),
     NEGREG(REG),
+    NOTREG(REG),
     ADDRESSMEM(REG, OFFSET),
+    ADDRESSINDEX(REG, OFFSET),
     DEREFREG(REG),
+    DEREFSIZED(REG, usize, bool),
     LOADMEM(REG, OFFSET),
+    LOADMEMSX(REG, OFFSET, usize),
+    LOADMEMZX(REG, OFFSET, usize),
     LOADREG(REG, REG),
+    LOADGLOBAL(REG, SYMBOL),
+    LOADADDR(REG, SYMBOL),
+    STOREGLOBAL(SYMBOL, REG),
     RETURNREG(REG),
     RETURNIMM(i128),
     RETURNMEM(OFFSET),
     RETURNCALL(SYMBOL),
     CALL(SYMBOL),
+    CALLREG(REG),
     LABEL(SYMBOL),
-    PUSHARG(REG, OFFSET),
+    PUSHARG(REG, OFFSET, usize),
     ARGREG(REG, REG),
     ARGIMM(REG, i128),
     ARGMEM(REG, OFFSET),
@@ -1,15 +1,24 @@
 use crate::compile::frontend;
 use crate::compile::ir;
 use frontend::frontmanager::frontmanager::FrontManager;
-use frontend::parse::node::Func;
+use frontend::parse::node::{Func, Global, NodeKind};
+use frontend::sema::semantics::Type;
+use ir::constant::Constant;
 use ir::context::Context;
 use ir::function::Function as LLVMFunc;
+use ir::llvm_type::LLVMType;
+use ir::llvm_value::{LLVMSymbol, LLVMValue};
 use ir::module::Module;
 
+use std::collections::BTreeMap;
+
 pub struct IRBuilder {
     pub module: Module,
     pub ctx: Context,
     pub functions: Vec<Func>,
+    pub globals: Vec<Global>,
+    /* the signatures of all functions, '&f' refers them */
+    pub signatures: BTreeMap<String, Type>,
 }
 impl IRBuilder {
     fn emit(&self) {
@@ -20,30 +29,74 @@ impl IRBuilder {
         self.module.dump_declare();
     }
     fn build_module(&mut self) {
+        /* every function refers the globals through '@name'. */
+        let mut globals: BTreeMap<String, LLVMSymbol> = BTreeMap::new();
+        let mut symbols: BTreeMap<String, String> = BTreeMap::new();
+        for g in self.globals.iter() {
+            let (ty, value, signed) = match g.init.kind {
+                NodeKind::BOOLEAN(b) => (LLVMType::I1, LLVMValue::INTEGER(b as i128), false),
+                NodeKind::INTEGER(v) => {
+                    let (ty, signed) = match g.ty {
+                        Type::INTEGER(signed, 8) => (LLVMType::I8, signed),
+                        Type::INTEGER(signed, 16) => (LLVMType::I16, signed),
+                        Type::INTEGER(signed, 32) => (LLVMType::I32, signed),
+                        Type::INTEGER(signed, _) => (LLVMType::I64, signed),
+                        _ => (LLVMType::I64, true),
+                    };
+                    (ty, LLVMValue::INTEGER(g.ty.wrap(v)), signed)
+                }
+                _ => (LLVMType::I64, LLVMValue::INTEGER(0), true),
+            };
+            self.module.constants.push(Constant::Global(
+                g.symbol.to_string(),
+                ty.clone(),
+                value,
+                g.is_mutable,
+            ));
+            globals.insert(g.name.to_string(), LLVMSymbol::new(0, ty, signed));
+            symbols.insert(g.name.to_string(), g.symbol.to_string());
+        }
         let functions = self.functions.clone();
         for f in functions.iter() {
             let mut llvm_func = LLVMFunc::new(f.name.to_string(), f.args.len());
+            llvm_func.globals = globals.clone();
+            llvm_func.global_symbols = symbols.clone();
+            llvm_func.signatures = self.signatures.clone();
             llvm_func.build_function(f);
             self.module
                 .constants
                 .append(&mut llvm_func.constants.clone());
-            self.module.declares = llvm_func.declares.clone();
+            self.module.declares.extend(llvm_func.declares.clone());
             self.module.add_func(llvm_func);
         }
     }
-    fn new(module_id: String, funcs: Vec<Func>) -> Self {
+    fn new(module_id: String, funcs: Vec<Func>, globals: Vec<Global>) -> Self {
         let module = Module::new(module_id.to_string());
         let ctx = Context::new(module_id);
         Self {
             module: module,
             ctx: ctx,
             functions: funcs,
+            globals: globals,
+            signatures: BTreeMap::new(),
         }
     }
 }
-pub fn emit_llvm(file_name: String, manager: FrontManager) {
-    // manager is no longer used.
-    let mut builder = IRBuilder::new(file_name, manager.functions);
-    builder.build_module();
+/* the modules are emitted into one llvm module, their names are already mangled. */
+pub fn emit_llvm(file_name: String, managers: Vec<FrontManager>) {
+    let mut builder = IRBuilder::new(file_name, Vec::new(), Vec::new());
+    for manager in managers.iter() {
+        for f in manager.functions.iter() {
+            builder
+                .signatures
+                .insert(f.name.to_string(), Type::signature(f));
+        }
+    }
+    for manager in managers {
+        // manager is no longer used.
+        builder.functions = manager.functions;
+        builder.globals = manager.globals;
+        builder.build_module();
+    }
     builder.emit();
 }
@@ -4,13 +4,18 @@ use std::fmt;
 
 type PointerTo = Box<LLVMType>;
 type ElemType = Box<LLVMType>;
+type ParamTypes = Vec<LLVMType>;
 #[derive(Clone, PartialEq)]
 pub enum LLVMType {
     I1,
     I8,
+    I16,
+    I32,
     I64,
     POINTER(PointerTo),
     ARRAY(ElemType, usize),
+    /* a pointer to the function, every function returns i64 */
+    FUNC(ParamTypes),
     UNKNOWN,
 }
 
@@ -19,9 +24,20 @@ impl fmt::Display for LLVMType {
         match self {
             Self::I1 => write!(f, "i1"),
             Self::I8 => write!(f, "i8"),
+            Self::I16 => write!(f, "i16"),
+            Self::I32 => write!(f, "i32"),
             Self::I64 => write!(f, "i64"),
             Self::POINTER(inner) => write!(f, "{}*", inner),
             Self::ARRAY(elem_type, length) => write!(f, "[{} x {}]", length, elem_type),
+            Self::FUNC(params) => write!(
+                f,
+                "i64 ({})*",
+                params
+                    .iter()
+                    .map(|p| format!("{}", p))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
             Self::UNKNOWN => write!(f, "unknown"),
         }
     }
@@ -32,8 +48,11 @@ impl LLVMType {
         match self {
             Self::I1 => 1,
             Self::I8 => 1,
+            Self::I16 => 2,
+            Self::I32 => 4,
             Self::I64 => 8,
             Self::POINTER(_) => 8,
+            Self::FUNC(_) => 8,
             Self::ARRAY(elem_type, _) => elem_type.alignment(),
             Self::UNKNOWN => {
                 Error::LLVM.found(&"LLVMType::UNKNOWN has not alignment".to_string());
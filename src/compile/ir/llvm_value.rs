@@ -28,13 +28,15 @@ impl fmt::Display for LLVMValue {
 pub struct LLVMSymbol {
     pub label: usize,
     pub ty: LLVMType,
+    pub signed: bool,
 }
 
 impl LLVMSymbol {
-    pub fn new(label: usize, ty: LLVMType) -> Self {
+    pub fn new(label: usize, ty: LLVMType, signed: bool) -> Self {
         Self {
             label: label,
             ty: ty,
+            signed,
         }
     }
 }
@@ -35,6 +35,11 @@ impl Module {
         println!(";ModuleID = '{}'", self.id);
     }
     pub fn dump_declare(&self) {
+        if self.declares.contains(&Intrinsic::Alloc) || self.declares.contains(&Intrinsic::Free) {
+            println!("@depth.free_list = internal global i64 0");
+            println!("@depth.heap_next = internal global i64 0");
+            println!("@depth.heap_end = internal global i64 0");
+        }
         for dec in self.declares.iter() {
             match dec {
                 Intrinsic::Memcpy => {
@@ -43,6 +48,82 @@ impl Module {
                 Intrinsic::DoNothing => {
                     println!("declare void @llvm.donothing() readnone");
                 }
+                /* the same free list and bump allocator as lib/alloc_linux.s */
+                Intrinsic::Alloc => {
+                    println!("define i64 @alloc(i64 %0) {{");
+                    println!("entry:");
+                    println!("  %need.1 = add i64 %0, 23");
+                    println!("  %need = and i64 %need.1, -16");
+                    println!("  br label %find");
+                    println!("find:");
+                    println!(
+                        "  %link = phi i64* [ @depth.free_list, %entry ], [ %block.ptr, %skip ]"
+                    );
+                    println!("  %block = load i64, i64* %link, align 8");
+                    println!("  %empty = icmp eq i64 %block, 0");
+                    println!("  br i1 %empty, label %bump, label %fit");
+                    println!("fit:");
+                    println!("  %block.ptr = inttoptr i64 %block to i64*");
+                    println!("  %header = getelementptr i64, i64* %block.ptr, i64 -1");
+                    println!("  %size = load i64, i64* %header, align 8");
+                    println!("  %fits = icmp sge i64 %size, %need");
+                    println!("  br i1 %fits, label %reuse, label %skip");
+                    println!("skip:");
+                    println!("  br label %find");
+                    println!("reuse:");
+                    println!("  %rest = load i64, i64* %block.ptr, align 8");
+                    println!("  store i64 %rest, i64* %link, align 8");
+                    println!("  ret i64 %block");
+                    println!("bump:");
+                    println!("  %next = load i64, i64* @depth.heap_next, align 8");
+                    println!("  %end = load i64, i64* @depth.heap_end, align 8");
+                    println!("  %room = sub i64 %end, %next");
+                    println!("  %enough = icmp sge i64 %room, %need");
+                    println!("  br i1 %enough, label %carve, label %grow");
+                    println!("grow:");
+                    println!("  %map.1 = add i64 %need, 65535");
+                    println!("  %map = and i64 %map.1, -65536");
+                    println!("  %heap = call i64 @depth.syscall(i64 9, i64 0, i64 %map, i64 3, i64 34, i64 -1, i64 0)");
+                    println!("  %failed = icmp slt i64 %heap, 0");
+                    println!("  br i1 %failed, label %fail, label %grown");
+                    println!("fail:");
+                    println!("  ret i64 0");
+                    println!("grown:");
+                    println!("  %heap.end = add i64 %heap, %map");
+                    println!("  store i64 %heap.end, i64* @depth.heap_end, align 8");
+                    println!("  br label %carve");
+                    println!("carve:");
+                    println!("  %base = phi i64 [ %next, %bump ], [ %heap, %grown ]");
+                    println!("  %base.ptr = inttoptr i64 %base to i64*");
+                    println!("  store i64 %need, i64* %base.ptr, align 8");
+                    println!("  %addr = add i64 %base, 8");
+                    println!("  %new.next = add i64 %base, %need");
+                    println!("  store i64 %new.next, i64* @depth.heap_next, align 8");
+                    println!("  ret i64 %addr");
+                    println!("}}");
+                }
+                Intrinsic::Free => {
+                    println!("define i64 @free(i64 %0) {{");
+                    println!("entry:");
+                    println!("  %null = icmp eq i64 %0, 0");
+                    println!("  br i1 %null, label %end, label %push");
+                    println!("push:");
+                    println!("  %ptr = inttoptr i64 %0 to i64*");
+                    println!("  %head = load i64, i64* @depth.free_list, align 8");
+                    println!("  store i64 %head, i64* %ptr, align 8");
+                    println!("  store i64 %0, i64* @depth.free_list, align 8");
+                    println!("  br label %end");
+                    println!("end:");
+                    println!("  ret i64 0");
+                    println!("}}");
+                }
+                /* 'syscall(n, a1, .., a6)' calls this with the missing arguments as 0 */
+                Intrinsic::Syscall => {
+                    println!("define i64 @depth.syscall(i64 %0, i64 %1, i64 %2, i64 %3, i64 %4, i64 %5, i64 %6) {{");
+                    println!("  %8 = call i64 asm sideeffect \"syscall\", \"={{rax}},{{rax}},{{rdi}},{{rsi}},{{rdx}},{{r10}},{{r8}},{{r9}},~{{rcx}},~{{r11}},~{{memory}}\"(i64 %0, i64 %1, i64 %2, i64 %3, i64 %4, i64 %5, i64 %6)");
+                    println!("  ret i64 %8");
+                    println!("}}");
+                }
             }
         }
     }
@@ -3,20 +3,25 @@ type Physical = usize;
 type Offset = usize;
 type Index = Option<Box<Operand>>;
 type Member = Option<Offset>;
+type Width = (usize, bool); /* (bytes, signed) of the memory it points */
 #[derive(PartialOrd, Ord, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Operand {
     INTLIT(i128),
     REG(Virtual, Physical, Index, Member),
-    ID(String, Offset, Index, Member),
+    ID(String, Offset, Index, Member, Width),
     CALL(String, usize),
+    GLOBAL(String),
+    STRLIT(usize),
 }
 impl Operand {
     pub fn string(&self) -> String {
         match self {
             Self::INTLIT(value) => format!("{}", value),
             Self::REG(virt, _phys, _oind, _omember) => format!("t{}", virt),
-            Self::ID(name, _, _oind, _omember) => name.to_string(),
+            Self::ID(name, _, _oind, _omember, _width) => name.to_string(),
             Self::CALL(func, argc) => format!("call {}, {}", func, argc),
+            Self::GLOBAL(name) => name.to_string(),
+            Self::STRLIT(idx) => format!("str.{}", idx),
         }
     }
     fn dump_st(&self) -> String {
@@ -29,7 +34,7 @@ impl Operand {
                     None => format!("t{}", virt),
                 },
             },
-            Self::ID(name, _, oind, omember) => match oind {
+            Self::ID(name, _, oind, omember, _width) => match oind {
                 Some(index) => format!("{}[{}]", name, index.dump_st()),
                 None => match omember {
                     Some(member) => format!("{}.{}", name, member),
@@ -37,6 +42,8 @@ impl Operand {
                 },
             },
             Self::CALL(func, argc) => format!("call {}, {}", func, argc),
+            Self::GLOBAL(name) => format!("@{}", name),
+            Self::STRLIT(idx) => format!("&str.{}", idx),
         }
     }
 }
@@ -52,7 +59,7 @@ pub enum Tac {
     LABEL(String),
     FUNCNAME(String),
     PROLOGUE(usize),
-    PUSHARG(usize, usize),
+    PUSHARG(usize, usize, usize),
 }
 impl Tac {
     pub fn string(&self) -> String {
@@ -66,6 +73,9 @@ impl Tac {
                 op,
                 rop.dump_st()
             ),
+            Self::UNEX(lv, op, lop) if op == "call" => {
+                format!("{} <- call {}", lv.dump_st(), lop.dump_st())
+            }
             Self::UNEX(lv, op, lop) => format!("{} <- {}{}", lv.dump_st(), op, lop.dump_st(),),
             Self::RET(op) => format!("ret {}", op.dump_st()),
             Self::LET(lv, op) => format!("{} <- {}", lv.dump_st(), op.dump_st()),
@@ -73,7 +83,7 @@ impl Tac {
             Self::GOTO(label) => format!("goto {}", label),
             Self::PARAM(reg, arg) => format!("param {} {}", reg + 1, arg.dump_st()),
             Self::PROLOGUE(offset) => format!("prologue {}", offset),
-            Self::PUSHARG(_reg, offset) => format!("pusharg {}", offset),
+            Self::PUSHARG(_reg, offset, _size) => format!("pusharg {}", offset),
         }
     }
 }
@@ -6,44 +6,187 @@ extern crate clap;
 extern crate colored;
 use colored::*;
 
+use crate::ce::types::{Diagnostics, Error};
 use crate::util;
 use frontend::frontmanager::frontmanager::FrontManager;
 use ir::llvm;
 use ir::tac::Tac;
 
-pub fn compile(
+/* the modules shipped with the compiler, '$DEPTH_ROOT/lib' overrides them. */
+const EMBEDDED_MODULES: [(&str, &str); 1] = [("std.dep", include_str!("../../lib/std.dep"))];
+
+/* a source file or a module imported from it */
+struct Unit {
     file_name: String,
+    funcs: Vec<frontend::parse::node::Func>,
+    globals: Vec<frontend::parse::node::Global>,
+    /* the indices of the imported units */
+    imports: Vec<usize>,
+}
+
+/* compile each source and the modules imported from them into the assembly,
+ * returns the file name, the assembly and the functions for the debug sections of each of them. */
+pub fn compile(
+    sources: Vec<String>,
     matches: &clap::ArgMatches,
-) -> (String, Vec<frontend::parse::node::Func>) {
-    if !file_name.contains(".dep") {
-        return (util::read_file(&file_name), vec![]);
+) -> Vec<(String, String, Vec<frontend::parse::node::Func>)> {
+    let mut outputs: Vec<(String, String, Vec<frontend::parse::node::Func>)> = Vec::new();
+    let mut units: Vec<Unit> = Vec::new();
+
+    /* the modules are resolved from the directory of the entry file */
+    let root: String = match sources.iter().find(|s| s.contains(".dep")) {
+        Some(entry) => match std::path::Path::new(entry).parent() {
+            Some(dir) => dir.to_string_lossy().to_string(),
+            None => String::new(),
+        },
+        None => String::new(),
+    };
+    for file_name in sources.iter() {
+        if !file_name.contains(".dep") {
+            outputs.push((file_name.to_string(), util::read_file(file_name), vec![]));
+            continue;
+        }
+        load_unit(
+            file_name.to_string(),
+            util::read_file(file_name),
+            String::new(),
+            &root,
+            matches,
+            &mut units,
+        );
     }
 
-    /* tokenize */
-    let tokens: Vec<frontend::token::token::Token> = lex_phase(file_name.to_string(), &matches);
+    let mut managers: Vec<FrontManager> = Vec::new();
+    for unit in units.iter() {
+        let mut front_manager: FrontManager =
+            FrontManager::new(unit.funcs.clone(), unit.globals.clone());
+        front_manager.warn_shadow = matches.is_present("Wshadow");
+        for idx in unit.imports.iter() {
+            front_manager
+                .imported
+                .append(&mut units[*idx].funcs.clone());
+        }
 
-    /* parse */
-    let funcs: Vec<frontend::parse::node::Func> = parse_phase(&matches, tokens);
-    let mut front_manager: FrontManager = FrontManager::new(funcs);
+        /* semantic-analyze */
+        front_manager.semantics();
+        front_manager.diag.abort_if_errors();
 
-    /* semantic-analyze */
-    front_manager.semantics();
+        /* the generic functions are replaced with their instances */
+        front_manager.monomorphize();
 
-    /* constant-fold with ast */
-    front_manager.constant_folding();
+        /* constant-fold with ast */
+        front_manager.constant_folding();
+        front_manager.diag.abort_if_errors();
+        managers.push(front_manager);
+    }
 
     /* emit-llvm path */
     if matches.is_present("emit-llvm") {
-        llvm::emit_llvm(file_name, front_manager);
+        let entry: String = units.first().map_or(String::new(), |u| u.file_name.clone());
+        llvm::emit_llvm(entry, managers);
         std::process::exit(0);
     }
 
+    for (unit, front_manager) in units.iter().zip(managers) {
+        let (assembly, functions) = backend_phase(front_manager, matches);
+        outputs.push((unit.file_name.to_string(), assembly, functions));
+    }
+    outputs
+}
+
+/* lex and parse the file once, then the modules it imports. */
+fn load_unit(
+    file_name: String,
+    source: String,
+    prefix: String,
+    root: &str,
+    matches: &clap::ArgMatches,
+    units: &mut Vec<Unit>,
+) -> usize {
+    if let Some(idx) = units.iter().position(|u| u.file_name == file_name) {
+        return idx;
+    }
+
+    /* collect the errors in lex and parse */
+    let mut diag: Diagnostics = Diagnostics::new();
+
+    /* tokenize */
+    let tokens: Vec<(
+        frontend::token::token::Token,
+        frontend::token::token::Position,
+    )> = lex_phase(file_name.to_string(), source, &matches, &mut diag);
+
+    /* parse */
+    let (funcs, globals, imports) = parse_phase(&matches, tokens, &prefix, &mut diag);
+    diag.abort_if_errors();
+
+    /* register before the imports, so a cyclic import refers this unit */
+    let idx: usize = units.len();
+    units.push(Unit {
+        file_name,
+        funcs,
+        globals,
+        imports: vec![],
+    });
+    let mut resolved: Vec<usize> = Vec::new();
+    for import in imports.iter() {
+        match find_module(root, &import.path) {
+            Some((path, source)) => resolved.push(load_unit(
+                path,
+                source,
+                import.path.join("."),
+                root,
+                matches,
+                units,
+            )),
+            None => diag.error(
+                Error::UNDEFINED,
+                &import.pos,
+                &format!("not found such a module -> {}", import.path.join("::")),
+            ),
+        }
+    }
+    diag.abort_if_errors();
+    units[idx].imports = resolved;
+    idx
+}
+
+/* 'import a::b' refers 'a/b.dep' in the directory of the entry file, then in $DEPTH_ROOT/lib,
+ * then the modules embedded in the compiler such as 'std'.
+ * returns the file name and the source of the module. */
+fn find_module(root: &str, path: &[String]) -> Option<(String, String)> {
+    let relative: String = path.join("/") + ".dep";
+    let mut candidates: Vec<std::path::PathBuf> = vec![std::path::Path::new(root).join(&relative)];
+    if let Ok(depth_root) = std::env::var("DEPTH_ROOT") {
+        candidates.push(
+            std::path::Path::new(&depth_root)
+                .join("lib")
+                .join(&relative),
+        );
+    }
+    if let Some(candidate) = candidates.into_iter().find(|candidate| candidate.is_file()) {
+        let file_name: String = candidate.to_string_lossy().to_string();
+        let source: String = util::read_file(&file_name);
+        return Some((file_name, source));
+    }
+    EMBEDDED_MODULES
+        .iter()
+        .find(|(name, _)| *name == relative)
+        .map(|(name, source)| (name.to_string(), source.to_string()))
+}
+
+fn backend_phase(
+    mut front_manager: FrontManager,
+    matches: &clap::ArgMatches,
+) -> (String, Vec<frontend::parse::node::Func>) {
     /* escape functions for debug section*/
     let functions = front_manager.functions.clone();
+    let globals = front_manager.globals.clone();
 
     /* generate three-address-code from ast */
     front_manager.gen_tacs();
     let tacs: Vec<Tac> = front_manager.tacs;
+    let strings: Vec<String> = front_manager.strings;
 
     /* backend */
     let mut optimizer: backend::Optimizer = backend::Optimizer::new(tacs);
@@ -54,11 +197,21 @@ pub fn compile(
         optimizer.dump_cfg();
     }
 
-    /* TODO: not implemented yet */
     if matches.is_present("Opt1") {
+        /* constant propagation and copy propagation */
         optimizer.build_cfg_for_reaching();
         optimizer.reaching_definition();
+        optimizer.propagation();
+
+        /* dead code elimination */
+        optimizer.dead_code_elimination();
+
+        /* common subexpression elimination */
         optimizer.available_expression();
+        if matches.is_present("dump-available") {
+            optimizer.dump_available();
+        }
+        optimizer.eliminate_common_subexpression();
     }
 
     /* append the information for liveness */
@@ -79,20 +232,32 @@ pub fn compile(
     }
 
     /* codegen */
-    (backend::codegen::genx64(optimizer.tacs), functions)
+    (
+        backend::codegen::genx64(optimizer.tacs, &globals, &strings),
+        functions,
+    )
 }
 
-fn lex_phase(file_name: String, matches: &clap::ArgMatches) -> Vec<frontend::token::token::Token> {
-    let filecontent: String = util::read_file(&file_name);
-
+fn lex_phase(
+    file_name: String,
+    filecontent: String,
+    matches: &clap::ArgMatches,
+    diag: &mut Diagnostics,
+) -> Vec<(
+    frontend::token::token::Token,
+    frontend::token::token::Position,
+)> {
     /* lex */
-    let tokens: Vec<frontend::token::token::Token> = frontend::lex::lexing::lexing(filecontent);
+    let tokens: Vec<(
+        frontend::token::token::Token,
+        frontend::token::token::Position,
+    )> = frontend::lex::lexing::lexing(file_name, filecontent, diag);
 
     /* render tokens to stderr */
     if matches.is_present("dump-token") {
         eprintln!("{}", "--------dumptoken--------".blue().bold());
-        for t in tokens.iter() {
-            eprintln!("{}", t.string().green().bold());
+        for (t, pos) in tokens.iter() {
+            eprintln!("{}\t{}", pos.string(), t.string().green().bold());
         }
     }
 
@@ -101,14 +266,23 @@ fn lex_phase(file_name: String, matches: &clap::ArgMatches) -> Vec<frontend::tok
 
 fn parse_phase(
     matches: &clap::ArgMatches,
-    tokens: Vec<frontend::token::token::Token>,
-) -> Vec<frontend::parse::node::Func> {
+    tokens: Vec<(
+        frontend::token::token::Token,
+        frontend::token::token::Position,
+    )>,
+    prefix: &str,
+    diag: &mut Diagnostics,
+) -> (
+    Vec<frontend::parse::node::Func>,
+    Vec<frontend::parse::node::Global>,
+    Vec<frontend::parse::node::Import>,
+) {
     /* parse */
-    let funcs: Vec<frontend::parse::node::Func> = frontend::parse::parser::parsing(tokens);
+    let (funcs, globals, imports) = frontend::parse::parser::parsing(tokens, prefix, diag);
 
     /* render ast by string to stderr */
     if matches.is_present("dump-ast") {
         frontend::parse::node::dump_ast(&funcs);
     }
-    funcs
+    (funcs, globals, imports)
 }
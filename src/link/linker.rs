@@ -1,3 +1,6 @@
+use crate::assemble;
+use crate::ce::types::Error;
+use crate::object::debug::DebugSymbol;
 use crate::object::elf::elf64;
 use elf64::ELF;
 
@@ -5,29 +8,170 @@ pub struct Linker {
     pub obj: ELF,
 }
 impl Linker {
-    pub fn linking(mut elf_file: ELF) -> ELF {
+    pub fn linking(mut objects: Vec<ELF>) -> ELF {
         //exec_file.condition();
+        let mut elf_file: ELF = if objects.len() == 1 {
+            objects.remove(0)
+        } else {
+            Self::merge(objects)
+        };
         elf_file.linking();
         elf_file
     }
+    /* combine the relocatable objects into one, each section is placed after the same section of the previous objects.
+     * the symbols are unified by their names so a reference is resolved with the definition in another object. */
+    fn merge(objects: Vec<ELF>) -> ELF {
+        let mut text: Vec<u8> = Vec::new();
+        let mut data: Vec<u8> = Vec::new();
+        let mut bss_size: u64 = 0;
+        let mut rodata: Vec<u8> = Vec::new();
+        let mut relas: Vec<elf64::Rela> = Vec::new();
+        let mut documents: Vec<u8> = Vec::new();
+        let mut debugs: Vec<(String, DebugSymbol)> = Vec::new();
+
+        /* names[i] is the name of symbols[i + 1], symbols[0] is the null symbol. */
+        let mut names: Vec<String> = Vec::new();
+        let mut symbols: Vec<elf64::Symbol> = vec![elf64::init_nullsym()];
+        let mut errors: usize = 0;
+
+        for obj in objects.iter() {
+            let bss_number: usize = obj.get_section_number(".bss");
+            let bases: Vec<(usize, u64)> = vec![
+                (obj.get_section_number(".text"), text.len() as u64),
+                (obj.get_section_number(".data"), data.len() as u64),
+                (bss_number, bss_size),
+                (obj.get_section_number(".rodata"), rodata.len() as u64),
+            ];
+            let strtab: Vec<u8> = obj.get_section(".strtab");
+
+            /* the index of each symbol of the object in the merged symbol table. */
+            let mut indices: Vec<usize> = vec![0];
+            for mut symbol in obj.get_symbols().into_iter().skip(1) {
+                let name: String = ELF::collect_name(strtab[symbol.st_name as usize..].to_vec());
+                if let Some((_, base)) = bases
+                    .iter()
+                    .find(|(number, _)| *number == symbol.st_shndx as usize)
+                {
+                    symbol.st_value += base;
+                }
+                if let Some(idx) = names.iter().position(|n| *n == name) {
+                    if symbol.st_shndx != elf64::SHN_UNDEF {
+                        if symbols[idx + 1].st_shndx != elf64::SHN_UNDEF {
+                            Error::ELF.found(&format!("multiple definition of '{}'", name));
+                            errors += 1;
+                        }
+                        symbols[idx + 1] = symbol;
+                    }
+                    indices.push(idx + 1);
+                } else {
+                    names.push(name);
+                    symbols.push(symbol);
+                    indices.push(symbols.len() - 1);
+                }
+            }
+
+            for mut rela in obj.get_relas(".rela.text") {
+                rela.r_offset += text.len() as u64;
+                let symbol_index: u64 = indices[elf64::Rela::bind(rela.r_info)] as u64;
+                rela.r_info = (symbol_index << 32) | (rela.r_info & 0xffffffff);
+                relas.push(rela);
+            }
+
+            /* the debug symbols refer their names in .strtab, so keep the names to rebuild them. */
+            let debug: Vec<u8> = obj.get_section(".dbg.depth");
+            for chunk in debug.chunks(DebugSymbol::size()) {
+                let debug_symbol: DebugSymbol = DebugSymbol::new_unsafe(chunk.to_vec());
+                debugs.push((debug_symbol.get_name(obj), debug_symbol));
+            }
+            documents.append(&mut obj.get_section(".documents"));
+
+            text.append(&mut obj.get_section(".text"));
+            data.append(&mut obj.get_section(".data"));
+            bss_size += obj.shdrs[bss_number].sh_size;
+            rodata.append(&mut obj.get_section(".rodata"));
+        }
+
+        for (name, symbol) in names.iter().zip(symbols[1..].iter()) {
+            if symbol.st_shndx == elf64::SHN_UNDEF {
+                Error::ELF.found(&format!("undefined reference to '{}'", name));
+                errors += 1;
+            }
+        }
+        if errors != 0 {
+            std::process::exit(1);
+        }
+
+        /* the offsets of the names in the merged .strtab, which starts with a null byte. */
+        let mut offsets: Vec<u32> = Vec::new();
+        let mut offset: u32 = 1;
+        for (name, symbol) in names.iter().zip(symbols[1..].iter_mut()) {
+            symbol.st_name = offset;
+            offsets.push(offset);
+            offset += name.len() as u32 + 1;
+        }
+
+        let mut elf_file: ELF = assemble::build_object(assemble::Sections {
+            text,
+            symbols,
+            strtab: elf64::strtab(names.iter().map(|n| n.as_str()).collect()),
+            relas,
+            data,
+            bss_size,
+            rodata,
+        });
+        let mut debug_section_binary: Vec<u8> = Vec::new();
+        for (name, mut debug_symbol) in debugs {
+            debug_symbol.d_name = match names.iter().position(|n| *n == name) {
+                Some(idx) => offsets[idx] as u8,
+                None => 0,
+            };
+            debug_section_binary.append(&mut debug_symbol.to_vec());
+        }
+        assemble::finish_object(&mut elf_file, debug_section_binary, documents);
+        elf_file
+    }
 }
 
 pub static BASE_ADDRESS: u64 = 0x400000;
 pub static PAGE_SIZE: u64 = 0x1000;
 impl ELF {
     fn linking(&mut self) {
+        self.conditioning_section_offset();
+        self.place_sections();
         self.init_phdr();
         self.prepare_ehdr_for_staticlink();
         self.padding();
-        self.conditioning_section_offset();
         self.link_symbols();
+    }
+    /* .text starts at BASE_ADDRESS, .data and .bss are placed in the page after .text,
+     * and .rodata in the page after them, keeping the file offset congruent with the address. */
+    fn place_sections(&mut self) {
         let text_number: usize = self.get_section_number(".text");
         self.shdrs[text_number].sh_addr = BASE_ADDRESS;
+        let text_end: u64 = BASE_ADDRESS + self.shdrs[text_number].sh_size;
+        let data_number: usize = self.get_section_number(".data");
+        let data_offset: u64 = self.shdrs[data_number].sh_offset;
+        let data_addr: u64 = text_end.div_ceil(PAGE_SIZE) * PAGE_SIZE + data_offset % PAGE_SIZE;
+        self.shdrs[data_number].sh_addr = data_addr;
+        let bss_number: usize = self.get_section_number(".bss");
+        let bss_addr: u64 = data_addr + self.shdrs[data_number].sh_size;
+        self.shdrs[bss_number].sh_addr = bss_addr;
+        let bss_end: u64 = bss_addr + self.shdrs[bss_number].sh_size;
+        let rodata_number: usize = self.get_section_number(".rodata");
+        let rodata_offset: u64 = self.shdrs[rodata_number].sh_offset;
+        self.shdrs[rodata_number].sh_addr =
+            bss_end.div_ceil(PAGE_SIZE) * PAGE_SIZE + rodata_offset % PAGE_SIZE;
     }
     fn link_symbols(&mut self) {
         let strtab: Vec<u8> = self.get_section(".strtab");
         let mut symbols: Vec<elf64::Symbol> = self.get_symbols();
         for symbol in symbols.iter_mut() {
+            let shndx: usize = symbol.st_shndx as usize;
+            if shndx != elf64::SHN_UNDEF as usize && shndx != 1 {
+                /* an object in .data or .bss */
+                symbol.st_value += self.shdrs[shndx].sh_addr;
+                continue;
+            }
             if strtab[symbol.st_name as usize] as char == '_' {
                 self.ehdr.e_entry = BASE_ADDRESS + symbol.st_value;
             }
@@ -51,7 +195,7 @@ impl ELF {
     fn prepare_ehdr_for_staticlink(&mut self) {
         self.ehdr.e_type = elf64::ET_EXEC;
         self.ehdr.e_phoff = elf64::Ehdr::size() as u64; // sizeof(Ehdr)
-        self.ehdr.e_phnum = 1;
+        self.ehdr.e_phnum = self.phdrs.as_ref().map_or(0, |phdrs| phdrs.len()) as u16;
         self.ehdr.e_phentsize = elf64::Phdr::size() as u16; // sizeof(Phdr)
         self.ehdr.e_shoff = PAGE_SIZE
             + self.sections[1..]
@@ -97,10 +241,28 @@ impl ELF {
         phdr.p_filesz = text.len() as u64; // remove the hardcode
         phdr.p_memsz = text.len() as u64; // remove the hardcode
         phdr.p_flags = elf64::PF_R | elf64::PF_X | elf64::PF_W;
-        self.phdrs = Some(vec![phdr]);
+        let mut phdrs: Vec<elf64::Phdr> = vec![phdr];
+
+        /* a writable segment for .data and .bss */
+        let data_number: usize = self.get_section_number(".data");
+        let bss_number: usize = self.get_section_number(".bss");
+        let data_shdr: &elf64::Shdr = &self.shdrs[data_number];
+        let memsz: u64 = data_shdr.sh_size + self.shdrs[bss_number].sh_size;
+        if memsz != 0 {
+            phdrs.push(load_segment(data_shdr, memsz, elf64::PF_R | elf64::PF_W));
+        }
+
+        /* a read-only segment for .rodata */
+        let rodata_number: usize = self.get_section_number(".rodata");
+        let rodata_shdr: &elf64::Shdr = &self.shdrs[rodata_number];
+        if rodata_shdr.sh_size != 0 {
+            phdrs.push(load_segment(rodata_shdr, rodata_shdr.sh_size, elf64::PF_R));
+        }
+        self.phdrs = Some(phdrs);
     }
     fn padding(&mut self) {
-        for _ in 0..PAGE_SIZE - elf64::Ehdr::size() as u64 - elf64::Phdr::size() as u64 {
+        let phdrs_size: u64 = elf64::Phdr::size() as u64 * self.ehdr.e_phnum as u64;
+        for _ in 0..PAGE_SIZE - elf64::Ehdr::size() as u64 - phdrs_size {
             self.sections[0].push(0x00);
         }
     }
@@ -112,3 +274,16 @@ impl ELF {
             .collect::<()>();
     }
 }
+/* the segment which starts with the section and spans memsz bytes in memory. */
+fn load_segment(shdr: &elf64::Shdr, memsz: u64, flags: u32) -> elf64::Phdr {
+    let mut phdr: elf64::Phdr = elf64::init_phdr();
+    phdr.p_type = elf64::PT_LOAD;
+    phdr.p_offset = shdr.sh_offset;
+    phdr.p_vaddr = shdr.sh_addr;
+    phdr.p_paddr = shdr.sh_addr;
+    phdr.p_align = PAGE_SIZE;
+    phdr.p_filesz = shdr.sh_size;
+    phdr.p_memsz = memsz;
+    phdr.p_flags = flags;
+    phdr
+}
@@ -1,27 +1,57 @@
 extern crate libc;
 use libc::c_void;
 
-use crate::object::elf::elf64::ELF;
+use crate::object::elf::elf64::{self, ELF};
 pub struct ELFLoader {}
 
 const PAGE_SIZE: libc::size_t = 4096;
+const STACK_SIZE: libc::size_t = 8 * 1024 * 1024;
+/* the types of the auxiliary vector */
+const AT_NULL: u64 = 0;
+const AT_PAGESZ: u64 = 6;
+/* the exit status when the program can't be loaded, as the shell does */
+const LOAD_FAILURE: i32 = 127;
 
 impl ELFLoader {
-    pub fn load(elf_file: ELF) -> i32 {
-        let (program, page) = Self::setup_page_with_using_mmap();
+    /* 'args' are passed to the program as argv, it runs until it exits. */
+    pub fn load(elf_file: ELF, args: &[String]) -> i32 {
         let binary = elf_file.to_vec();
-        if let Some(unwrapped_phdrs) = elf_file.phdrs {
-            let offset = unwrapped_phdrs[0].p_offset as usize;
-            let segment_size = unwrapped_phdrs[0].p_filesz as usize;
+        if let Some(unwrapped_phdrs) = &elf_file.phdrs {
+            for phdr in unwrapped_phdrs.iter() {
+                if phdr.p_type != elf64::PT_LOAD {
+                    continue;
+                }
+                let offset = phdr.p_offset as usize;
+                let segment_size = phdr.p_filesz as usize;
 
-            /* get segment from binary */
-            let load_segment = binary[offset..offset + segment_size].to_vec();
-            let pointer_to_segment = load_segment.as_ptr();
+                /* get segment from binary */
+                let load_segment = binary[offset..offset + segment_size].to_vec();
+                let pointer_to_segment = load_segment.as_ptr();
 
+                let program: *mut u8 =
+                    Self::setup_page_with_using_mmap(phdr.p_vaddr as usize, phdr.p_memsz as usize);
+                if program.is_null() {
+                    eprintln!("can't map the segment at {:#x}", phdr.p_vaddr);
+                    return LOAD_FAILURE;
+                }
+                unsafe {
+                    program.copy_from_nonoverlapping(pointer_to_segment, segment_size as usize);
+                }
+            }
+            let stack_pointer: *mut u64 = Self::setup_stack(args);
+            if stack_pointer.is_null() {
+                eprintln!("can't map the stack");
+                return LOAD_FAILURE;
+            }
+            /* _start reads the stack as execve(2) left it, then exits by the system call. */
             unsafe {
-                program.copy_from_nonoverlapping(pointer_to_segment, segment_size as usize);
-                let f: fn() -> i32 = ::std::mem::transmute(page);
-                f()
+                std::arch::asm!(
+                    "mov rsp, {0}",
+                    "jmp {1}",
+                    in(reg) stack_pointer,
+                    in(reg) elf_file.ehdr.e_entry,
+                    options(noreturn)
+                );
             }
         } else {
             eprintln!("not found program header table");
@@ -29,23 +59,70 @@ impl ELFLoader {
         }
     }
 
-    fn setup_page_with_using_mmap() -> (*mut u8, *mut c_void) {
+    /* argc, argv, envp and auxv from the top of a new stack, the strings are placed above them. */
+    fn setup_stack(args: &[String]) -> *mut u64 {
+        let envs: Vec<String> = std::env::vars_os()
+            .map(|(key, value)| format!("{}={}", key.to_string_lossy(), value.to_string_lossy()))
+            .collect();
+        unsafe {
+            let base: *mut c_void = libc::mmap(
+                ::std::ptr::null_mut(),
+                STACK_SIZE,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_ANONYMOUS | libc::MAP_PRIVATE,
+                -1,
+                0,
+            );
+            if base == libc::MAP_FAILED {
+                return ::std::ptr::null_mut();
+            }
+
+            /* copy the strings with the terminating null byte */
+            let mut top: *mut u8 = (base as *mut u8).add(STACK_SIZE);
+            let mut copy = |contents: &String| -> u64 {
+                top = top.sub(contents.len() + 1);
+                top.copy_from_nonoverlapping(contents.as_ptr(), contents.len());
+                *top.add(contents.len()) = 0;
+                top as u64
+            };
+            let arg_ptrs: Vec<u64> = args.iter().map(&mut copy).collect();
+            let env_ptrs: Vec<u64> = envs.iter().map(&mut copy).collect();
+
+            let mut words: Vec<u64> = vec![args.len() as u64];
+            words.extend(arg_ptrs);
+            words.push(0);
+            words.extend(env_ptrs);
+            words.push(0);
+            words.extend_from_slice(&[AT_PAGESZ, PAGE_SIZE as u64, AT_NULL, 0]);
+
+            /* rsp is aligned to 16 bytes at the entry point */
+            let sp: usize = (top as usize - words.len() * 8) & !0xf;
+            let stack_pointer: *mut u64 = sp as *mut u64;
+            stack_pointer.copy_from_nonoverlapping(words.as_ptr(), words.len());
+            stack_pointer
+        }
+    }
+
+    /* map the pages which cover [vaddr, vaddr + memsz), the rest of them are zero-filled. */
+    fn setup_page_with_using_mmap(vaddr: usize, memsz: usize) -> *mut u8 {
         unsafe {
-            let program: *mut u8;
-            /* set the pointer to 0x400000 because target binary expect to load to 0x400000 */
-            let start: *mut c_void = 0x400000 as *mut c_void;
+            /* set the pointer to the address the target binary expects to be loaded */
+            let start: usize = vaddr / PAGE_SIZE * PAGE_SIZE;
+            let length: usize = (vaddr + memsz - start).div_ceil(PAGE_SIZE) * PAGE_SIZE;
 
             /* mmap with the flags it can execute */
             let page: *mut c_void = libc::mmap(
-                start,
-                PAGE_SIZE,
+                start as *mut c_void,
+                length,
                 libc::PROT_EXEC | libc::PROT_READ | libc::PROT_WRITE,
                 libc::MAP_ANONYMOUS | libc::MAP_PRIVATE,
                 0,
                 0,
             );
-            program = ::std::mem::transmute(page);
-            return (program, page);
+            if page as usize != start {
+                return ::std::ptr::null_mut();
+            }
+            (page as *mut u8).add(vaddr - start)
         }
     }
 }
@@ -18,6 +18,11 @@ mod load;
 use load::elf::ELFLoader;
 mod util;
 
+/* the runtime linked with the entry file, '$DEPTH_ROOT/lib' overrides them. */
+const STARTUP_ROUTINE: &str = include_str!("../lib/start_up_linux.s");
+/* the heap allocator behind 'alloc'/'free' */
+const ALLOC_ROUTINE: &str = include_str!("../lib/alloc_linux.s");
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let yaml = load_yaml!("cli.yml");
     let matches = App::from_yaml(yaml).get_matches();
@@ -35,9 +40,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn linux_main(matches: &clap::ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    if let Err(r) = std::env::var("DEPTH_ROOT") {
-        panic!("{} -> DEPTH_ROOT", r);
-    };
     return if matches.is_present("readelf") {
         analyze::analyze_elf(matches)
     } else if matches.is_present("checksec") {
@@ -53,39 +55,69 @@ fn linux_generate_binary_main(
     matches: &clap::ArgMatches,
 ) -> Result<(), Box<dyn std::error::Error>> {
     /* compile phase */
-    let startup_routine =
-        util::read_file(&(std::env::var("DEPTH_ROOT").unwrap() + "/lib/start_up_linux.s").as_str());
-    let file_name = matches.value_of("source").unwrap();
-    let (mut assembly, debug_funcs) = compile::compile(file_name.to_string(), &matches);
-    assembly += &startup_routine;
+    let startup_routine: String = runtime_routine("start_up_linux.s", STARTUP_ROUTINE);
+    let alloc_routine: String = runtime_routine("alloc_linux.s", ALLOC_ROUTINE);
+    let sources: Vec<String> = matches
+        .values_of("source")
+        .unwrap()
+        .map(|s| s.to_string())
+        .collect();
+
+    /* the object files are linked as they are. */
+    let (objects, compiled): (Vec<String>, Vec<String>) =
+        sources.iter().cloned().partition(|s| s.ends_with(".o"));
+    let has_unit: bool = compiled.iter().any(|s| s.ends_with(".dep"));
+    let mut outputs = if compiled.is_empty() {
+        vec![]
+    } else {
+        compile::compile(compiled, &matches)
+    };
+
+    /* the startup routine and the allocator are linked once, with the first compiled unit */
+    if has_unit {
+        outputs[0].1 += &startup_routine;
+        outputs[0].1 += &alloc_routine;
+    }
 
     /* if 'stop-c' given so output the assembly-code to file. */
     if matches.is_present("stop-c") {
-        let output_path: String = file_name.split(".").collect::<Vec<&str>>()[0].to_string() + ".s";
-        let mut file = File::create(output_path)?;
-        file.write_all(assembly.as_bytes())?;
+        for (file_name, assembly, _) in outputs.iter() {
+            let mut file = File::create(output_path(file_name, "s"))?;
+            file.write_all(assembly.as_bytes())?;
+        }
         std::process::exit(0);
     }
 
     /* assembly phase */
-    let elf_binary: ELF = if !file_name.contains(".o") {
-        assemble::assemble(assembly.to_string(), &matches, debug_funcs)
-    } else {
+    let mut elf_binaries: Vec<(String, ELF)> = Vec::new();
+    for (file_name, assembly, debug_funcs) in outputs {
+        let elf_binary: ELF = assemble::assemble(assembly, &matches, debug_funcs);
+        elf_binaries.push((file_name, elf_binary));
+    }
+    for file_name in objects {
         // read the object file then construct ELF struct.
-        ELF::read_elf(&file_name.to_string())
-    };
+        let elf_binary: ELF = ELF::read_elf(&file_name);
+        elf_binaries.push((file_name, elf_binary));
+    }
 
     /* if 'stop-a' given so output the object-file. */
     if matches.is_present("stop-a") {
-        let output_path = file_name.split(".").collect::<Vec<&str>>()[0].to_string() + ".o";
-        util::output_file_with_binary(output_path, elf_binary);
+        for (file_name, elf_binary) in elf_binaries {
+            util::output_file_with_binary(output_path(&file_name, "o"), elf_binary);
+        }
     } else {
-        /* link the object-file  */
-        let exec_file: ELF = link::linker::Linker::linking(elf_binary);
+        /* link the object-files  */
+        let elf_binaries: Vec<ELF> = elf_binaries.into_iter().map(|(_, elf)| elf).collect();
+        let exec_file: ELF = link::linker::Linker::linking(elf_binaries);
 
         /* if 'run' given then the loader load the binary and execute machine code. */
         if matches.is_present("run") {
-            let return_value = ELFLoader::load(exec_file);
+            /* argv[0] is the entry file, the rest are given after '--' */
+            let mut args: Vec<String> = vec![sources[0].to_string()];
+            if let Some(program_args) = matches.values_of("program-args") {
+                args.extend(program_args.map(|arg| arg.to_string()));
+            }
+            let return_value = ELFLoader::load(exec_file, &args);
             std::process::exit(return_value);
         } else {
             /* then we generate a.out from ET_EXEC. */
@@ -95,3 +127,24 @@ fn linux_generate_binary_main(
 
     Ok(())
 }
+
+/* '$DEPTH_ROOT/lib/<file_name>' if it exists, otherwise the one embedded in the compiler */
+fn runtime_routine(file_name: &str, embedded: &str) -> String {
+    if let Ok(depth_root) = std::env::var("DEPTH_ROOT") {
+        let path: std::path::PathBuf = std::path::Path::new(&depth_root)
+            .join("lib")
+            .join(file_name);
+        if let Ok(routine) = std::fs::read_to_string(path) {
+            return routine;
+        }
+    }
+    embedded.to_string()
+}
+
+/* 'dir/name.dep' -> 'dir/name.s', only the last extension is replaced. */
+fn output_path(file_name: &str, extension: &str) -> String {
+    std::path::Path::new(file_name)
+        .with_extension(extension)
+        .to_string_lossy()
+        .to_string()
+}
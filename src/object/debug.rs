@@ -93,7 +93,7 @@ fn find_debug_name(elf_file: &ELF, func_name: String) -> u8 {
 
 fn count_dup_pointer(t: &Type) -> u8 {
     match t {
-        Type::INTEGER => 0,
+        Type::INTEGER(_, _) => 0,
         Type::POINTER(ptr_to) => count_dup_pointer(ptr_to) + 1,
         Type::ALIAS(alias) => count_dup_pointer(alias),
         _ => 0,
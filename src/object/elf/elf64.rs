@@ -59,6 +59,10 @@ impl ELF {
         let mut offset = 0x40;
         for (idx, shdr) in self.shdrs.iter_mut().enumerate() {
             shdr.sh_offset += offset;
+            /* .bss takes no space in the file. */
+            if shdr.sh_type == SHT_NOBITS as u32 {
+                continue;
+            }
             offset += shdr.sh_size;
             shdr.sh_size = self.sections[idx].len() as u64;
         }
@@ -755,6 +759,48 @@ pub fn init_texthdr(size: u64) -> Shdr {
         sh_entsize: 0,
     }
 }
+pub fn init_datahdr(size: u64) -> Shdr {
+    Shdr {
+        sh_name: 0,
+        sh_type: SHT_PROGBITS as u32,
+        sh_flags: SHF_ALLOC | SHF_WRITE,
+        sh_addr: 0,
+        sh_offset: 0,
+        sh_size: size,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 8,
+        sh_entsize: 0,
+    }
+}
+pub fn init_rodatahdr(size: u64) -> Shdr {
+    Shdr {
+        sh_name: 0,
+        sh_type: SHT_PROGBITS as u32,
+        sh_flags: SHF_ALLOC,
+        sh_addr: 0,
+        sh_offset: 0,
+        sh_size: size,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 1,
+        sh_entsize: 0,
+    }
+}
+pub fn init_bsshdr(size: u64) -> Shdr {
+    Shdr {
+        sh_name: 0,
+        sh_type: SHT_NOBITS as u32,
+        sh_flags: SHF_ALLOC | SHF_WRITE,
+        sh_addr: 0,
+        sh_offset: 0,
+        sh_size: size,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 8,
+        sh_entsize: 0,
+    }
+}
 pub fn init_symtabhdr(size: u64) -> Shdr {
     Shdr {
         sh_name: 0,
@@ -1156,6 +1202,22 @@ pub fn init_sym(name: Elf64Word, bind: u8, size: u64, value: u64) -> Symbol {
         st_size: size,
     }
 }
+pub fn init_objsym(
+    name: Elf64Word,
+    bind: u8,
+    shndx: Elf64Section,
+    size: u64,
+    value: u64,
+) -> Symbol {
+    Symbol {
+        st_name: name,
+        st_info: (bind << 4) + STT_OBJECT,
+        st_other: 0,
+        st_shndx: shndx,
+        st_value: value,
+        st_size: size,
+    }
+}
 pub fn init_refsym(name: Elf64Word, bind: u8) -> Symbol {
     Symbol {
         st_name: name,
@@ -1188,6 +1250,7 @@ pub const R_X86_64_JUMP_SLOT: u64 = 7;
 pub const R_X86_64_RELATIVE: u64 = 8;
 pub const R_X86_64_GOTPCREL: u64 = 9;
 pub const R_X86_64_32: u64 = 9;
+pub const R_X86_64_32S: u64 = 11;
 
 /* AMD x86-64 relocations.  */
 //#define R_X86_64_NONE		0	/* No reloc */
@@ -1278,13 +1341,6 @@ impl Rela {
         }
         bb
     }
-    pub fn new() -> Rela {
-        Rela {
-            r_offset: 0,
-            r_info: 0,
-            r_addend: -4,
-        }
-    }
     pub fn to_stdout(&self, elf_file: &ELF, related_symtab_sh_link: usize) -> Row {
         let mut cells: Vec<Cell> = Vec::new();
         ELF::add_cell(&mut cells, &format!("0x{:x}", self.r_offset));
@@ -1331,6 +1387,8 @@ impl Rela {
             "R_X86_64_GOTPCREL".to_string()
         } else if check_type(R_X86_64_32) {
             "R_X86_64_32".to_string()
+        } else if check_type(R_X86_64_32S) {
+            "R_X86_64_32S".to_string()
         } else {
             "Invalid".to_string()
         };
@@ -59,7 +59,7 @@ def test_optimize1():
     for filename, expect in cases.items():
         fn = f"test/{filename}"
         f = open(fn)
-        p = subprocess.Popen(f"./target/debug/depth {fn} --Opt1 ; ./a.out", shell=True)
+        p = subprocess.Popen(f"./target/debug/depth {fn} --Opt1 --run", shell=True)
         exit_status = p.wait()
         if exit_status != expect:
             print(
@@ -104,10 +104,10 @@ if __name__ == "__main__":
     test_compile()
     compile_time = time.time() - start
     print(f"test-volatile time -> {Color.BLUE}{round(compile_time,2)}{Color.CLEAR}s")
-    # start = time.time()
-    # test_optimize1()
-    # compile_time = time.time() - start
-    # print(f"test-optimize1 time -> {Color.BLUE}{round(compile_time,2)}{Color.CLEAR}s")
+    start = time.time()
+    test_optimize1()
+    compile_time = time.time() - start
+    print(f"test-optimize1 time -> {Color.BLUE}{round(compile_time,2)}{Color.CLEAR}s")
     start = time.time()
     test_llvm()
     compile_time = time.time() - start